@@ -2,10 +2,10 @@ use crate::types::{Constant, IsPrime};
 use crate::{
     complex::NumStr::{
         Comma, Division, Exponent, Func, LeftBracket, LeftCurlyBracket, Matrix, Minus,
-        Multiplication, Num, Plus, RightBracket, RightCurlyBracket, Vector,
+        Multiplication, Num, Plus, RightBracket, RightCurlyBracket, Tensor, Vector,
     },
     math::do_math,
-    misc::{do_math_with_var, place_funcvar, place_var},
+    misc::{INTERRUPT, do_math_with_var, place_funcvar, place_var},
     parse::simplify,
     units::{Number, Options, Units},
 };
@@ -13,8 +13,14 @@ use crate::{
 use rayon::iter::{IntoParallelIterator, ParallelIterator};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
+use std::cell::{Cell, RefCell};
 use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::io::{Write, stdout};
 use std::marker::PhantomData;
+use std::sync::OnceLock;
+use std::sync::atomic::Ordering as AtomicOrdering;
+use std::time::Instant;
 #[derive(Clone, PartialEq, Debug)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum NumStr<
@@ -26,6 +32,7 @@ pub enum NumStr<
     Func(String),
     Vector(Vec<Number<I, F, C>>),
     Matrix(Vec<Vec<Number<I, F, C>>>),
+    Tensor(Vec<usize>, Vec<Number<I, F, C>>),
     LeftBracket,
     RightBracket,
     LeftCurlyBracket,
@@ -60,6 +67,9 @@ pub enum NumStr<
     Implies,
     Nor,
     Converse,
+    BitAnd,
+    BitOr,
+    BitXor,
 }
 impl<
     Integer: crate::types::Integer<Float, Complex>,
@@ -78,6 +88,7 @@ impl<I: crate::types::Integer<F, D>, F: crate::types::Float<I, D>, D: crate::typ
         Self {
             number,
             units,
+            rational: None,
             phantom: PhantomData,
         }
     }
@@ -90,6 +101,7 @@ impl<I: crate::types::Integer<F, D>, F: crate::types::Float<I, D>, D: crate::typ
         Self {
             number,
             units,
+            rational: None,
             phantom: PhantomData,
         }
     }
@@ -99,9 +111,43 @@ impl<I: crate::types::Integer<F, D>, F: crate::types::Float<I, D>, D: crate::typ
         Self {
             number,
             units,
+            rational: None,
             phantom: PhantomData,
         }
     }
+    pub fn from_rational(rational: (I, I), units: Option<Units>, prec: u32) -> Number<I, F, D> {
+        let number = D::with_val(prec, &rational.0) / F::with_val(prec, &rational.1);
+        let prec = number.prec();
+        Self {
+            number,
+            units,
+            rational: reduce_rational(rational.0, rational.1, prec),
+            phantom: PhantomData,
+        }
+    }
+    // the exact value if one is known, integers that fit the precision count as exact,
+    // a stored fraction is ignored once number has been changed out from under it
+    pub fn rational(&self) -> Option<(I, I)> {
+        if !self.number.imag().is_zero() || !self.number.real().is_finite() {
+            return None;
+        }
+        let prec = self.number.prec();
+        if let Some((p, q)) = self.rational.as_ref().filter(|(p, q)| {
+            let cap = rational_cap(q, prec);
+            p.significant_bits() <= cap
+                && q.significant_bits() <= cap
+                && D::with_val(prec, p) / F::with_val(prec, q) == self.number
+        }) {
+            return Some((p.clone(), q.clone()));
+        }
+        if self.number.real().clone().fract().is_zero() {
+            let p = self.number.real().to_integer()?;
+            if p.significant_bits() <= prec {
+                return Some((p, I::from(1)));
+            }
+        }
+        None
+    }
     pub fn real(&self) -> &F {
         self.number.real()
     }
@@ -109,6 +155,62 @@ impl<I: crate::types::Integer<F, D>, F: crate::types::Float<I, D>, D: crate::typ
         self.number.imag()
     }
 }
+// integers may grow as large as the backend allows, fractions only as large as the precision
+pub fn rational_cap<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    q: &Integer,
+    prec: u32,
+) -> u32 {
+    if q == &1 {
+        Integer::max_bits()
+    } else {
+        prec.min(Integer::max_bits())
+    }
+}
+// lowest terms with a positive denominator, dropped once either part outgrows its cap
+pub fn reduce_rational<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    p: Integer,
+    q: Integer,
+    prec: u32,
+) -> Option<(Integer, Integer)> {
+    if q.cmp0() == Ordering::Equal {
+        return None;
+    }
+    let g = p.clone().gcd(&q);
+    let (p, q) = if q.cmp0() == Ordering::Less {
+        (-p / g.clone(), -q / g)
+    } else {
+        (p / g.clone(), q / g)
+    };
+    let cap = rational_cap(&q, prec);
+    (p.significant_bits() <= cap && q.significant_bits() <= cap).then_some((p, q))
+}
+// applies f to the exact values of a and b when both have one
+fn exact<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    a: &Number<Integer, Float, Complex>,
+    b: &Number<Integer, Float, Complex>,
+    f: impl Fn(Integer, Integer, Integer, Integer) -> Option<(Integer, Integer)>,
+) -> Option<(Integer, Integer)> {
+    // plain floats such as graphing samples never start an exact computation
+    if a.rational.is_none() && b.rational.is_none() {
+        return None;
+    }
+    let (p, q) = a.rational()?;
+    let (r, s) = b.rational()?;
+    let (p, q) = f(p, q, r, s)?;
+    reduce_rational(p, q, a.number.prec())
+}
 pub fn add<
     Integer: crate::types::Integer<Float, Complex>,
     Float: crate::types::Float<Integer, Complex>,
@@ -117,10 +219,13 @@ pub fn add<
     a: &Number<Integer, Float, Complex>,
     b: &Number<Integer, Float, Complex>,
 ) -> Number<Integer, Float, Complex> {
-    Number::from(
-        a.number.clone() + b.number.clone(),
-        if a.units == b.units { a.units } else { None },
-    )
+    let units = if a.units == b.units { a.units } else { None };
+    match exact(a, b, |p, q, r, s| {
+        Some((p * s.clone() + r * q.clone(), q * s))
+    }) {
+        Some(r) => Number::from_rational(r, units, a.number.prec()),
+        None => Number::from(a.number.clone() + b.number.clone(), units),
+    }
 }
 pub fn sub<
     Integer: crate::types::Integer<Float, Complex>,
@@ -130,10 +235,13 @@ pub fn sub<
     a: &Number<Integer, Float, Complex>,
     b: &Number<Integer, Float, Complex>,
 ) -> Number<Integer, Float, Complex> {
-    Number::from(
-        a.number.clone() - b.number.clone(),
-        if a.units == b.units { a.units } else { None },
-    )
+    let units = if a.units == b.units { a.units } else { None };
+    match exact(a, b, |p, q, r, s| {
+        Some((p * s.clone() - r * q.clone(), q * s))
+    }) {
+        Some(r) => Number::from_rational(r, units, a.number.prec()),
+        None => Number::from(a.number.clone() - b.number.clone(), units),
+    }
 }
 #[allow(clippy::type_complexity)]
 pub fn set_prec<
@@ -163,6 +271,7 @@ impl<
             Matrix(m) => m
                 .iter_mut()
                 .for_each(|v| v.iter_mut().for_each(|n| n.set_prec(prec))),
+            Tensor(_, t) => t.iter_mut().for_each(|n| n.set_prec(prec)),
             _ => {}
         }
     }
@@ -175,6 +284,14 @@ impl<
             a: &Number<I, F, C>,
             b: &Number<I, F, C>,
         ) -> Number<I, F, C> {
+            let units = match (a.units, b.units) {
+                (Some(a), Some(b)) => Some(a.mul(&b)),
+                (Some(a), None) | (None, Some(a)) => Some(a),
+                (None, None) => None,
+            };
+            if let Some(r) = exact(a, b, |p, q, r, s| Some((p * r, q * s))) {
+                return Number::from_rational(r, units, a.number.prec());
+            }
             Number::from(
                 {
                     let a = a.number.clone();
@@ -198,11 +315,7 @@ impl<
                         a * b.clone()
                     }
                 },
-                match (a.units, b.units) {
-                    (Some(a), Some(b)) => Some(a.mul(&b)),
-                    (Some(a), None) | (None, Some(a)) => Some(a),
-                    (None, None) => None,
-                },
+                units,
             )
         }
         Ok(match (self, b) {
@@ -260,6 +373,13 @@ impl<
                     })
                     .collect(),
             ),
+            (Num(b), Tensor(s, a)) | (Tensor(s, a), Num(b)) => {
+                Tensor(s.clone(), a.iter().map(|a| m(a, b)).collect())
+            }
+            (Tensor(s, a), Tensor(t, b)) if s == t => Tensor(
+                s.clone(),
+                a.iter().zip(b.iter()).map(|(a, b)| m(a, b)).collect(),
+            ),
             _ => return Err("mul err"),
         })
     }
@@ -317,6 +437,30 @@ impl<
             a: &Number<I, F, C>,
             b: &Number<I, F, C>,
         ) -> Number<I, F, C> {
+            let units = match (a.units, b.units) {
+                (Some(a), None) => Some(a.pow(b.number.real().to_f64())),
+                _ => None,
+            };
+            // integer powers stay exact as long as the result fits the precision
+            let prec = a.number.prec();
+            if let Some(r) = exact(a, b, |p, q, r, s| {
+                let e = r.to_i32().filter(|_| s == 1)?;
+                let k = e.unsigned_abs();
+                let cap = rational_cap(if e < 0 { &p } else { &q }, prec);
+                if (e <= 0 && p.cmp0() == Ordering::Equal)
+                    || p.significant_bits().saturating_mul(k) > cap
+                    || q.significant_bits().saturating_mul(k) > cap
+                {
+                    return None;
+                }
+                Some(if e < 0 {
+                    (q.pow(k), p.pow(k))
+                } else {
+                    (p.pow(k), q.pow(k))
+                })
+            }) {
+                return Number::from_rational(r, units, prec);
+            }
             Number::from(
                 {
                     let a = a.number.clone();
@@ -344,10 +488,7 @@ impl<
                         pow_nth(a, b)
                     }
                 },
-                match (a.units, b.units) {
-                    (Some(a), None) => Some(a.pow(b.number.real().to_f64())),
-                    _ => None,
-                },
+                units,
             )
         }
         Ok(match (self, b) {
@@ -430,6 +571,12 @@ impl<
                     })
                     .collect(),
             ),
+            (Num(a), Tensor(s, b)) => Tensor(s.clone(), b.iter().map(|b| p(a, b)).collect()),
+            (Tensor(s, a), Num(b)) => Tensor(s.clone(), a.iter().map(|a| p(a, b)).collect()),
+            (Tensor(s, a), Tensor(t, b)) if s == t => Tensor(
+                s.clone(),
+                a.iter().zip(b.iter()).map(|(a, b)| p(a, b)).collect(),
+            ),
             _ => return Err("pow err"),
         })
     }
@@ -485,6 +632,12 @@ impl<
                     })
                     .collect(),
             ),
+            (Num(a), Tensor(s, b)) => Tensor(s.clone(), b.iter().map(|b| func(a, b)).collect()),
+            (Tensor(s, a), Num(b)) => Tensor(s.clone(), a.iter().map(|a| func(a, b)).collect()),
+            (Tensor(s, a), Tensor(t, b)) if s == t => Tensor(
+                s.clone(),
+                a.iter().zip(b.iter()).map(|(a, b)| func(a, b)).collect(),
+            ),
             _ => return Err("operation err"),
         })
     }
@@ -513,6 +666,24 @@ impl<
             _ => Err("failed to get matrix"),
         }
     }
+    #[allow(clippy::type_complexity)]
+    pub fn tensor(
+        &self,
+    ) -> Result<(Vec<usize>, Vec<Number<Integer, Float, Complex>>), &'static str> {
+        match self {
+            Num(n) => Ok((Vec::new(), vec![*n.clone()])),
+            Vector(v) => Ok((vec![v.len()], v.clone())),
+            Matrix(m) => {
+                let len = m.first().map(|v| v.len()).unwrap_or_default();
+                if m.iter().any(|v| v.len() != len) {
+                    return Err("ragged matrix");
+                }
+                Ok((vec![m.len(), len], m.iter().flatten().cloned().collect()))
+            }
+            Tensor(s, t) => Ok((s.clone(), t.clone())),
+            _ => Err("failed to get tensor"),
+        }
+    }
 }
 pub fn and<
     Integer: crate::types::Integer<Float, Complex>,
@@ -669,6 +840,20 @@ pub fn not<
             }
             Ok(Matrix(k))
         }
+        Tensor(s, t) => Ok(Tensor(
+            s.clone(),
+            t.iter()
+                .map(|a| {
+                    Number::from(
+                        Complex::with_val(
+                            a.number.prec(),
+                            a.number.is_zero() && a.number.real() != &1,
+                        ),
+                        None,
+                    )
+                })
+                .collect(),
+        )),
         _ => Err("bad not input"),
     }
 }
@@ -680,6 +865,17 @@ pub fn div<
     a: &Number<Integer, Float, Complex>,
     b: &Number<Integer, Float, Complex>,
 ) -> Number<Integer, Float, Complex> {
+    let units = match (a.units, b.units) {
+        (Some(a), Some(b)) => Some(a.div(&b)),
+        (Some(a), None) => Some(a),
+        (None, Some(b)) => Some(Units::default().div(&b)),
+        (None, None) => None,
+    };
+    if let Some(r) = exact(a, b, |p, q, r, s| {
+        (r.cmp0() != Ordering::Equal).then(|| (p * s, q * r))
+    }) {
+        return Number::from_rational(r, units, a.number.prec());
+    }
     Number::from(
         {
             let a = a.number.clone();
@@ -696,12 +892,7 @@ pub fn div<
                 a / b.clone()
             }
         },
-        match (a.units, b.units) {
-            (Some(a), Some(b)) => Some(a.div(&b)),
-            (Some(a), None) => Some(a),
-            (None, Some(b)) => Some(Units::default().div(&b)),
-            (None, None) => None,
-        },
+        units,
     )
 }
 pub fn root<
@@ -844,6 +1035,321 @@ pub fn shr<
         None,
     )
 }
+fn bitwise<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    a: &Number<Integer, Float, Complex>,
+    b: &Number<Integer, Float, Complex>,
+    op: fn(Integer, &Integer) -> Integer,
+) -> Number<Integer, Float, Complex> {
+    let (a, b) = (&a.number, &b.number);
+    let int = |n: &Complex| {
+        if n.imag().is_zero() && n.real().is_finite() && n.real().clone().fract().is_zero() {
+            n.real().to_integer()
+        } else {
+            None
+        }
+    };
+    Number::from(
+        match (int(a), int(b)) {
+            (Some(x), Some(y)) => Complex::with_val(a.prec(), op(x, &y)),
+            _ => Complex::with_val(a.prec(), Constant::Nan),
+        },
+        None,
+    )
+}
+pub fn bitand<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    a: &Number<Integer, Float, Complex>,
+    b: &Number<Integer, Float, Complex>,
+) -> Number<Integer, Float, Complex> {
+    bitwise(a, b, Integer::bit_and)
+}
+pub fn bitor<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    a: &Number<Integer, Float, Complex>,
+    b: &Number<Integer, Float, Complex>,
+) -> Number<Integer, Float, Complex> {
+    bitwise(a, b, Integer::bit_or)
+}
+pub fn bitxor<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    a: &Number<Integer, Float, Complex>,
+    b: &Number<Integer, Float, Complex>,
+) -> Number<Integer, Float, Complex> {
+    bitwise(a, b, Integer::bit_xor)
+}
+// reduces n to a bits wide two's complement word
+pub fn wrap_word<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    n: Integer,
+    bits: u32,
+    signed: bool,
+) -> Integer {
+    let m = Integer::from(2).pow(bits);
+    let r = md(n, &m);
+    if signed && r >= Integer::from(2).pow(bits - 1) {
+        r - m
+    } else {
+        r
+    }
+}
+// truncates every real entry to an integer and wraps it to the word size
+pub fn word<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    a: NumStr<Integer, Float, Complex>,
+    (bits, signed): (u32, bool),
+) -> NumStr<Integer, Float, Complex> {
+    let wrap = |n: Number<Integer, Float, Complex>| {
+        if n.number.imag().is_zero() && n.number.real().is_finite() {
+            let prec = n.number.prec();
+            let i = n
+                .number
+                .real()
+                .clone()
+                .trunc()
+                .to_integer()
+                .unwrap_or_default();
+            Number::from(Complex::with_val(prec, wrap_word(i, bits, signed)), n.units)
+        } else {
+            n
+        }
+    };
+    match a {
+        Num(n) => NumStr::new(wrap(*n)),
+        Vector(v) => Vector(v.into_iter().map(wrap).collect()),
+        Matrix(m) => Matrix(
+            m.into_iter()
+                .map(|v| v.into_iter().map(wrap).collect())
+                .collect(),
+        ),
+        a => a,
+    }
+}
+// ieee 754 half, single or double bit pattern of x
+pub fn float_bits<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    x: &Float,
+    bits: u32,
+) -> Option<Integer> {
+    match bits {
+        16 => Some(Integer::new() + ieee_bits(x, 5, 10)),
+        32 => Some(Integer::new() + ieee_bits(x, 8, 23)),
+        64 => Some(Integer::new() + x.to_f64().to_bits()),
+        _ => None,
+    }
+}
+// rounds to nearest even like the hardware conversion, straight from x so the
+// narrower formats are not rounded twice through f64
+fn ieee_bits<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    x: &Float,
+    ebits: u32,
+    mbits: u32,
+) -> u64 {
+    let sign = (x.is_sign_negative() as u64) << (ebits + mbits);
+    let inf = sign | (((1 << ebits) - 1) << mbits);
+    if x.is_nan() {
+        return inf | (1 << (mbits - 1));
+    }
+    if x.is_infinite() {
+        return inf;
+    }
+    let a = x.clone().abs();
+    if a.is_zero() {
+        return sign;
+    }
+    let bias = (1 << (ebits - 1)) - 1;
+    let one = Float::with_val(a.prec(), 1);
+    let mut e = (((a.to_f64().to_bits() >> 52) & 0x7ff) as i32 - 1023).max(1 - bias);
+    while a >= one.clone() << (e + 1) {
+        e += 1
+    }
+    while e > 1 - bias && a < one.clone() << e {
+        e -= 1
+    }
+    if e > bias {
+        return inf;
+    }
+    let scaled = a << (mbits as i32 - e);
+    let floor = scaled.clone().floor();
+    let half = (scaled - floor.clone()) * 2;
+    let mut m = floor
+        .to_integer()
+        .and_then(|m| m.to_u128())
+        .unwrap_or_default() as u64;
+    if half > 1 || (half == 1 && m % 2 == 1) {
+        m += 1
+    }
+    if m == 1 << (mbits + 1) {
+        e += 1;
+        m >>= 1
+    }
+    if e > bias {
+        inf
+    } else if m < 1 << mbits {
+        sign | m
+    } else {
+        sign | (((e + bias) as u64) << mbits) | (m - (1 << mbits))
+    }
+}
+pub fn from_bits<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    n: &Integer,
+    bits: u32,
+) -> Option<f64> {
+    if n.cmp0() == Ordering::Less || n.significant_bits() > bits {
+        return None;
+    }
+    let n = n.to_u128()? as u64;
+    match bits {
+        16 => Some(f16_value(n as u16)),
+        32 => Some(f32::from_bits(n as u32) as f64),
+        64 => Some(f64::from_bits(n)),
+        _ => None,
+    }
+}
+fn f16_value(h: u16) -> f64 {
+    let sign = if h & 0x8000 == 0 { 1.0 } else { -1.0 };
+    let (e, m) = (((h >> 10) & 0x1f) as i32, (h & 0x3ff) as f64);
+    sign * match e {
+        0 => m * 2f64.powi(-24),
+        31 if m == 0.0 => f64::INFINITY,
+        31 => f64::NAN,
+        _ => (1.0 + m / 1024.0) * 2f64.powi(e - 15),
+    }
+}
+// signed fixed point with m integer and n fraction bits, as (unsigned bit pattern, raw value)
+// where the value is raw / 2^n, rounded to nearest and saturated to the range
+pub fn qformat<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    x: &Float,
+    m: u32,
+    n: u32,
+) -> Option<(Integer, Integer)> {
+    let raw = (x.clone() * Float::with_val(x.prec(), 2).pow(n))
+        .round()
+        .to_integer()?;
+    let max = Integer::from(2).pow(m + n);
+    let raw = if raw >= max {
+        max - 1
+    } else if raw < -max.clone() {
+        -max
+    } else {
+        raw
+    };
+    Some((wrap_word(raw.clone(), m + n + 1, false), raw))
+}
+// negabinary or balanced ternary digits of x, with T for the -1 digit
+pub fn alt_base_digits<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    x: &Float,
+    balanced: bool,
+    places: usize,
+) -> Option<String> {
+    if !x.is_finite() {
+        return None;
+    }
+    let prec = x.prec();
+    let third: Float = Float::with_val(prec, 1) / 3;
+    // the fraction digits reach [-1/2, 1/2] in balanced ternary and (-2/3, 1/3] in negabinary
+    let i = if balanced {
+        x.clone().round()
+    } else {
+        (x.clone() - third.clone()).ceil()
+    };
+    let mut f = x.clone() - i.clone();
+    let mut n = i.to_integer()?;
+    let mut int = Vec::new();
+    while n != 0 {
+        if balanced {
+            let r = md(n.clone(), &Integer::from(3));
+            if r == 2 {
+                int.push('T');
+                n = (n + 1) / 3
+            } else {
+                int.push(if r == 1 { '1' } else { '0' });
+                n = (n - r) / 3
+            }
+        } else {
+            let (q, r) = n.div_rem(Integer::from(-2));
+            if r == 0 {
+                int.push('0');
+                n = q
+            } else {
+                int.push('1');
+                n = if r.cmp0() == Ordering::Less { q + 1 } else { q }
+            }
+        }
+    }
+    let mut out: String = if int.is_empty() {
+        "0".to_string()
+    } else {
+        int.iter().rev().collect()
+    };
+    let mut frac = String::new();
+    while !f.is_zero() && frac.len() < places.min(prec as usize) {
+        if balanced {
+            f *= 3;
+            let d = f.clone().round();
+            if d > 0 {
+                frac.push('1');
+                f -= 1
+            } else if d < 0 {
+                frac.push('T');
+                f += 1
+            } else {
+                frac.push('0')
+            }
+        } else {
+            f *= -2;
+            if f > third {
+                frac.push('1');
+                f -= 1
+            } else {
+                frac.push('0')
+            }
+        }
+    }
+    let frac = frac.trim_end_matches('0');
+    if !frac.is_empty() {
+        out.push('.');
+        out.push_str(frac)
+    }
+    Some(out)
+}
 pub fn ne<
     Integer: crate::types::Integer<Float, Complex>,
     Float: crate::types::Float<Integer, Complex>,
@@ -984,6 +1490,22 @@ pub fn rem<
     a: &Number<Integer, Float, Complex>,
     b: &Number<Integer, Float, Complex>,
 ) -> Number<Integer, Float, Complex> {
+    // a - floor(a/b) b on the exact values
+    if let Some(r) = exact(a, b, |p, q, r, s| {
+        if r.cmp0() == Ordering::Equal {
+            return None;
+        }
+        let d = q.clone() * r.clone();
+        let (mut k, m) = (p.clone() * s.clone()).div_rem(d.clone());
+        if m.cmp0() != Ordering::Equal
+            && (m.cmp0() == Ordering::Less) != (d.cmp0() == Ordering::Less)
+        {
+            k -= 1
+        }
+        Some((p * s.clone() - k * d, q * s))
+    }) {
+        return Number::from_rational(r, None, a.number.prec());
+    }
     let a = &a.number;
     let b = &b.number;
     let c = a.clone() / b.clone();
@@ -1512,6 +2034,7 @@ pub fn mvec<
 ) -> Result<NumStr<Integer, Float, Complex>, &'static str> {
     let mut vec = Vec::new();
     let mut mat = Vec::new();
+    let mut ten = Vec::new();
     let mut test = true;
     let mut body = |z: isize| -> Result<(), &'static str> {
         match do_math_with_var(
@@ -1549,6 +2072,7 @@ pub fn mvec<
                 }
                 mat.extend(m)
             }
+            t @ (Matrix(_) | Tensor(_, _)) => ten.push(t),
             _ => return Err("cant create 3d matrix"),
         }
         Ok(())
@@ -1562,7 +2086,13 @@ pub fn mvec<
             body(z)?
         }
     }
-    if mat.is_empty() {
+    if !ten.is_empty() {
+        if vec.is_empty() && mat.is_empty() {
+            stack(&ten)
+        } else {
+            Err("cant create 3d matrix")
+        }
+    } else if mat.is_empty() {
         if vec.is_empty() {
             Err("start>end")
         } else {
@@ -1572,6 +2102,50 @@ pub fn mvec<
         Ok(Matrix(mat))
     }
 }
+// the points of start..=end for which the predicate is nonzero, in order
+#[allow(clippy::type_complexity)]
+pub fn set_builder<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    function: Vec<NumStr<Integer, Float, Complex>>,
+    func_vars: Vec<(String, Vec<NumStr<Integer, Float, Complex>>)>,
+    var: &str,
+    start: &Float,
+    end: &Float,
+    options: Options,
+) -> Result<NumStr<Integer, Float, Complex>, &'static str> {
+    let bound = |n: &Float| {
+        if n.clone().fract().is_zero() {
+            n.to_integer()
+                .filter(|n| n.significant_bits() < isize::BITS)
+                .and_then(|n| n.to_isize())
+                .ok_or("start/end too large")
+        } else {
+            Err("fractional start/end")
+        }
+    };
+    let (start, end) = (bound(start)?, bound(end)?);
+    let mut vec = Vec::new();
+    for z in start.min(end)..=end.max(start) {
+        let n = Number::from(Complex::with_val(options.prec, z), None);
+        if !do_math_with_var(
+            function.clone(),
+            options,
+            func_vars.clone(),
+            var,
+            NumStr::new(n.clone()),
+        )?
+        .num()?
+        .number
+        .is_zero()
+        {
+            vec.push(n)
+        }
+    }
+    Ok(Vector(vec))
+}
 #[allow(clippy::type_complexity)]
 pub fn sum<
     Integer: crate::types::Integer<Float, Complex>,
@@ -1728,28 +2302,187 @@ pub fn identity<
     }
     mat
 }
-pub fn determinant<
+#[allow(clippy::type_complexity)]
+fn blocks<
     Integer: crate::types::Integer<Float, Complex>,
     Float: crate::types::Float<Integer, Complex>,
     Complex: crate::types::Complex<Integer, Float>,
 >(
-    a: &[Vec<Number<Integer, Float, Complex>>],
-) -> Result<Number<Integer, Float, Complex>, &'static str> {
-    if !a.is_empty() && (0..a.len()).all(|j| a.len() == a[j].len()) {
-        Ok(Number::from(
-            match a.len() {
-                1 => a[0][0].number.clone(),
-                2 => {
-                    a[0][0].number.clone() * a[1][1].number.clone()
-                        - a[1][0].number.clone() * a[0][1].number.clone()
+    a: &[NumStr<Integer, Float, Complex>],
+    column: bool,
+) -> Result<Vec<Vec<Vec<Number<Integer, Float, Complex>>>>, &'static str> {
+    a.iter()
+        .map(|a| match a {
+            Num(n) => Ok(vec![vec![*n.clone()]]),
+            Vector(v) if column => Ok(v.iter().map(|n| vec![n.clone()]).collect()),
+            Vector(v) => Ok(vec![v.clone()]),
+            Matrix(m) => {
+                if m.iter().any(|v| v.len() != m[0].len()) {
+                    Err("ragged matrix")
+                } else {
+                    Ok(m.clone())
                 }
-                3 => {
-                    a[0][0].number.clone()
-                        * (a[1][1].number.clone() * a[2][2].number.clone()
-                            - a[1][2].number.clone() * a[2][1].number.clone())
-                        + a[0][1].number.clone()
-                            * (a[1][2].number.clone() * a[2][0].number.clone()
-                                - a[1][0].number.clone() * a[2][2].number.clone())
+            }
+            _ => Err("cant stack"),
+        })
+        .collect()
+}
+pub fn hstack<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    a: &[NumStr<Integer, Float, Complex>],
+) -> Result<NumStr<Integer, Float, Complex>, &'static str> {
+    if a.iter().all(|a| matches!(a, Num(_) | Vector(_))) {
+        let mut vec = Vec::new();
+        for a in a {
+            match a {
+                Num(n) => vec.push(*n.clone()),
+                Vector(v) => vec.extend(v.clone()),
+                _ => {}
+            }
+        }
+        return Ok(Vector(vec));
+    }
+    let mut blocks = blocks(a, true)?.into_iter();
+    let mut mat = blocks.next().unwrap_or_default();
+    for b in blocks {
+        if b.len() != mat.len() {
+            return Err("row count mismatch");
+        }
+        for (row, b) in mat.iter_mut().zip(b) {
+            row.extend(b)
+        }
+    }
+    Ok(Matrix(mat))
+}
+pub fn vstack<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    a: &[NumStr<Integer, Float, Complex>],
+) -> Result<NumStr<Integer, Float, Complex>, &'static str> {
+    let mut blocks = blocks(a, false)?.into_iter();
+    let mut mat = blocks.next().unwrap_or_default();
+    for b in blocks {
+        if b.first().map(|v| v.len()) != mat.first().map(|v| v.len()) {
+            return Err("column count mismatch");
+        }
+        mat.extend(b)
+    }
+    Ok(Matrix(mat))
+}
+pub fn blockdiag<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    a: &[NumStr<Integer, Float, Complex>],
+    prec: u32,
+) -> Result<NumStr<Integer, Float, Complex>, &'static str> {
+    let blocks = blocks(a, false)?;
+    let cols = blocks
+        .iter()
+        .map(|b| b.first().map(|v| v.len()).unwrap_or_default())
+        .sum::<usize>();
+    let mut mat = Vec::new();
+    let mut start = 0;
+    for b in blocks {
+        let len = b.first().map(|v| v.len()).unwrap_or_default();
+        for row in b {
+            let mut vec = vec![Number::from(Complex::new(prec), None); cols];
+            vec.splice(start..start + len, row);
+            mat.push(vec)
+        }
+        start += len
+    }
+    Ok(Matrix(mat))
+}
+pub fn kron<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    a: &NumStr<Integer, Float, Complex>,
+    b: &NumStr<Integer, Float, Complex>,
+) -> Result<NumStr<Integer, Float, Complex>, &'static str> {
+    let m = |a: &Number<Integer, Float, Complex>, b: &Number<Integer, Float, Complex>| {
+        Number::from(a.number.clone() * &b.number, mul_units(a.units, b.units))
+    };
+    Ok(match (a, b) {
+        (Vector(a), Vector(b)) => {
+            Vector(a.iter().flat_map(|a| b.iter().map(|b| m(a, b))).collect())
+        }
+        _ => {
+            let a = blocks(std::slice::from_ref(a), false)?.remove(0);
+            let b = blocks(std::slice::from_ref(b), false)?.remove(0);
+            Matrix(
+                a.iter()
+                    .flat_map(|a| {
+                        b.iter().map(|b| {
+                            a.iter()
+                                .flat_map(|a| b.iter().map(|b| m(a, b)))
+                                .collect::<Vec<Number<Integer, Float, Complex>>>()
+                        })
+                    })
+                    .collect(),
+            )
+        }
+    })
+}
+pub fn repmat<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    a: &NumStr<Integer, Float, Complex>,
+    rows: usize,
+    cols: Option<usize>,
+) -> Result<NumStr<Integer, Float, Complex>, &'static str> {
+    Ok(match (a, cols) {
+        (Num(n), None) => Vector(vec![*n.clone(); rows]),
+        (Vector(v), None) => Vector(v.iter().cycle().take(v.len() * rows).cloned().collect()),
+        (_, cols) => {
+            let a = blocks(std::slice::from_ref(a), false)?.remove(0);
+            let cols = cols.unwrap_or(1);
+            Matrix(
+                a.iter()
+                    .map(|v| v.iter().cycle().take(v.len() * cols).cloned().collect())
+                    .cycle()
+                    .take(a.len() * rows)
+                    .collect(),
+            )
+        }
+    })
+}
+pub fn determinant<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    a: &[Vec<Number<Integer, Float, Complex>>],
+) -> Result<Number<Integer, Float, Complex>, &'static str> {
+    if !a.is_empty() && (0..a.len()).all(|j| a.len() == a[j].len()) {
+        let units = a[0][0].units.map(|b| b.pow(a.len() as f64));
+        if let Some(r) = rational_determinant(a) {
+            return Ok(Number::from_rational(r, units, a[0][0].number.prec()));
+        }
+        Ok(Number::from(
+            match a.len() {
+                1 => a[0][0].number.clone(),
+                2 => {
+                    a[0][0].number.clone() * a[1][1].number.clone()
+                        - a[1][0].number.clone() * a[0][1].number.clone()
+                }
+                3 => {
+                    a[0][0].number.clone()
+                        * (a[1][1].number.clone() * a[2][2].number.clone()
+                            - a[1][2].number.clone() * a[2][1].number.clone())
+                        + a[0][1].number.clone()
+                            * (a[1][2].number.clone() * a[2][0].number.clone()
+                                - a[1][0].number.clone() * a[2][2].number.clone())
                         + a[0][2].number.clone()
                             * (a[1][0].number.clone() * a[2][1].number.clone()
                                 - a[1][1].number.clone() * a[2][0].number.clone())
@@ -1768,12 +2501,53 @@ pub fn determinant<
                     det
                 }
             },
-            a[0][0].units.map(|b| b.pow(a.len() as f64)),
+            units,
         ))
     } else {
         Err("not square")
     }
 }
+// elimination over fractions, gives up once an entry is inexact or outgrows the precision
+fn rational_determinant<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    a: &[Vec<Number<Integer, Float, Complex>>],
+) -> Option<(Integer, Integer)> {
+    let prec = a[0][0].number.prec();
+    let mut m = a
+        .iter()
+        .map(|r| r.iter().map(|n| n.rational()).collect::<Option<Vec<_>>>())
+        .collect::<Option<Vec<_>>>()?;
+    let n = m.len();
+    let mut det = (Integer::from(1), Integer::from(1));
+    for i in 0..n {
+        let Some(k) = (i..n).find(|&k| m[k][i].0.cmp0() != Ordering::Equal) else {
+            return Some((Integer::new(), Integer::from(1)));
+        };
+        if k != i {
+            m.swap(i, k);
+            det.0 = -det.0
+        }
+        let pivot = m[i].clone();
+        let (p, q) = pivot[i].clone();
+        det = reduce_rational(det.0 * p.clone(), det.1 * q.clone(), prec)?;
+        for row in m.iter_mut().skip(i + 1) {
+            let (r, s) = row[i].clone();
+            if r.cmp0() == Ordering::Equal {
+                continue;
+            }
+            let (f, g) = reduce_rational(r * q.clone(), s * p.clone(), prec)?;
+            for (e, (x, y)) in row.iter_mut().zip(pivot.iter()).skip(i) {
+                let (x, y) = reduce_rational(f.clone() * x.clone(), g.clone() * y.clone(), prec)?;
+                let (u, v) = e.clone();
+                *e = reduce_rational(u * y.clone() - x * v.clone(), v * y, prec)?;
+            }
+        }
+    }
+    Some(det)
+}
 pub fn transpose<
     Integer: crate::types::Integer<Float, Complex>,
     Float: crate::types::Float<Integer, Complex>,
@@ -1795,6 +2569,442 @@ pub fn transpose<
     }
     b
 }
+pub fn from_tensor<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    shape: Vec<usize>,
+    data: Vec<Number<Integer, Float, Complex>>,
+) -> NumStr<Integer, Float, Complex> {
+    match shape.len() {
+        0 => NumStr::new(data.into_iter().next().unwrap()),
+        1 => Vector(data),
+        2 => Matrix(if shape[1] == 0 {
+            vec![Vec::new(); shape[0]]
+        } else {
+            data.chunks(shape[1]).map(|v| v.to_vec()).collect()
+        }),
+        _ => Tensor(shape, data),
+    }
+}
+pub fn stack<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    a: &[NumStr<Integer, Float, Complex>],
+) -> Result<NumStr<Integer, Float, Complex>, &'static str> {
+    let mut shape = Vec::new();
+    let mut data = Vec::new();
+    for (i, t) in a.iter().enumerate() {
+        let (s, t) = t.tensor()?;
+        if i == 0 {
+            shape = s
+        } else if shape != s {
+            return Err("tensor dimension mismatch");
+        }
+        data.extend(t)
+    }
+    shape.insert(0, a.len());
+    Ok(from_tensor(shape, data))
+}
+pub fn reshape<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    a: &NumStr<Integer, Float, Complex>,
+    shape: Vec<usize>,
+) -> Result<NumStr<Integer, Float, Complex>, &'static str> {
+    let (_, data) = a.tensor()?;
+    if shape.iter().product::<usize>() != data.len() {
+        return Err("reshape size mismatch");
+    }
+    Ok(from_tensor(shape, data))
+}
+pub fn permute<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    a: &NumStr<Integer, Float, Complex>,
+    axes: &[usize],
+) -> Result<NumStr<Integer, Float, Complex>, &'static str> {
+    let (shape, data) = a.tensor()?;
+    let mut seen = vec![false; shape.len()];
+    if axes.len() != shape.len()
+        || axes
+            .iter()
+            .any(|&n| n >= shape.len() || std::mem::replace(&mut seen[n], true))
+    {
+        return Err("invalid axes");
+    }
+    let mut strides = vec![1; shape.len()];
+    for i in (0..shape.len().saturating_sub(1)).rev() {
+        strides[i] = strides[i + 1] * shape[i + 1]
+    }
+    let new_shape = axes.iter().map(|&n| shape[n]).collect::<Vec<usize>>();
+    let mut out = Vec::with_capacity(data.len());
+    let mut index = vec![0; shape.len()];
+    for _ in 0..data.len() {
+        out.push(
+            data[index
+                .iter()
+                .zip(axes)
+                .map(|(i, &n)| i * strides[n])
+                .sum::<usize>()]
+            .clone(),
+        );
+        for j in (0..index.len()).rev() {
+            index[j] += 1;
+            if index[j] < new_shape[j] {
+                break;
+            }
+            index[j] = 0
+        }
+    }
+    Ok(from_tensor(new_shape, out))
+}
+pub fn tensordot<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    a: &NumStr<Integer, Float, Complex>,
+    b: &NumStr<Integer, Float, Complex>,
+    axes_a: &[usize],
+    axes_b: &[usize],
+) -> Result<NumStr<Integer, Float, Complex>, &'static str> {
+    let rank_a = a.tensor()?.0.len();
+    let rank_b = b.tensor()?.0.len();
+    if axes_a.len() != axes_b.len()
+        || axes_a.iter().any(|&n| n >= rank_a)
+        || axes_b.iter().any(|&n| n >= rank_b)
+    {
+        return Err("invalid axes");
+    }
+    let sub_a = (0..rank_a).collect::<Vec<usize>>();
+    let mut sub_b = (rank_a..rank_a + rank_b).collect::<Vec<usize>>();
+    for (i, j) in axes_a.iter().zip(axes_b) {
+        sub_b[*j] = *i
+    }
+    let out = sub_a
+        .iter()
+        .filter(|n| !axes_a.contains(n))
+        .chain(sub_b.iter().filter(|&&n| n >= rank_a))
+        .copied()
+        .collect::<Vec<usize>>();
+    einsum(&[(a.clone(), sub_a), (b.clone(), sub_b)], Some(&out))
+}
+#[allow(clippy::type_complexity)]
+pub fn einsum<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    operands: &[(NumStr<Integer, Float, Complex>, Vec<usize>)],
+    out: Option<&[usize]>,
+) -> Result<NumStr<Integer, Float, Complex>, &'static str> {
+    let mut tensors = Vec::with_capacity(operands.len());
+    let mut labels: Vec<(usize, usize, usize)> = Vec::new();
+    for (t, sub) in operands {
+        let (shape, data) = t.tensor()?;
+        if shape.len() != sub.len() {
+            return Err("subscript length mismatch");
+        }
+        for (l, d) in sub.iter().zip(shape.iter()) {
+            match labels.iter_mut().find(|(k, _, _)| k == l) {
+                Some((_, n, count)) if n == d => *count += 1,
+                Some(_) => return Err("tensor dimension mismatch"),
+                None => labels.push((*l, *d, 1)),
+            }
+        }
+        let mut strides = vec![1; shape.len()];
+        for i in (0..shape.len().saturating_sub(1)).rev() {
+            strides[i] = strides[i + 1] * shape[i + 1]
+        }
+        tensors.push((data, strides, sub));
+    }
+    let out = match out {
+        Some(out) => {
+            if out
+                .iter()
+                .enumerate()
+                .any(|(i, l)| out[..i].contains(l) || !labels.iter().any(|(k, _, _)| k == l))
+            {
+                return Err("invalid output subscripts");
+            }
+            out.to_vec()
+        }
+        None => {
+            let mut out = labels
+                .iter()
+                .filter(|(_, _, count)| *count == 1)
+                .map(|(l, _, _)| *l)
+                .collect::<Vec<usize>>();
+            out.sort();
+            out
+        }
+    };
+    let order = out
+        .iter()
+        .copied()
+        .chain(
+            labels
+                .iter()
+                .map(|(l, _, _)| *l)
+                .filter(|l| !out.contains(l)),
+        )
+        .collect::<Vec<usize>>();
+    let dims = order
+        .iter()
+        .map(|l| labels.iter().find(|(k, _, _)| k == l).unwrap().1)
+        .collect::<Vec<usize>>();
+    let positions = tensors
+        .iter()
+        .map(|(_, _, sub)| {
+            sub.iter()
+                .map(|l| order.iter().position(|k| k == l).unwrap())
+                .collect::<Vec<usize>>()
+        })
+        .collect::<Vec<Vec<usize>>>();
+    let out_len = dims[..out.len()].iter().product::<usize>();
+    let inner_len = dims[out.len()..].iter().product::<usize>();
+    let prec = tensors
+        .iter()
+        .find_map(|(d, _, _)| d.first().map(|n| n.number.prec()))
+        .unwrap_or(512);
+    let mut data = Vec::with_capacity(out_len);
+    let mut index = vec![0; order.len()];
+    for _ in 0..out_len {
+        let mut sum: Option<Number<Integer, Float, Complex>> = None;
+        for _ in 0..inner_len {
+            let mut product: Option<Number<Integer, Float, Complex>> = None;
+            for ((t, strides, _), pos) in tensors.iter().zip(positions.iter()) {
+                let n = &t[pos
+                    .iter()
+                    .zip(strides)
+                    .map(|(p, s)| index[*p] * s)
+                    .sum::<usize>()];
+                product = Some(match product {
+                    Some(p) => {
+                        Number::from(p.number * n.number.clone(), mul_units(p.units, n.units))
+                    }
+                    None => n.clone(),
+                });
+            }
+            let product = product.unwrap_or(Number::from(Complex::with_val(prec, 1), None));
+            sum = Some(match sum {
+                Some(s) => add(&s, &product),
+                None => product,
+            });
+            for j in (out.len()..index.len()).rev() {
+                index[j] += 1;
+                if index[j] < dims[j] {
+                    break;
+                }
+                index[j] = 0
+            }
+        }
+        data.push(sum.unwrap_or(Number::from(Complex::new(prec), None)));
+        for j in (0..out.len()).rev() {
+            index[j] += 1;
+            if index[j] < dims[j] {
+                break;
+            }
+            index[j] = 0
+        }
+    }
+    Ok(from_tensor(dims[..out.len()].to_vec(), data))
+}
+#[allow(clippy::type_complexity)]
+fn index_positions<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    shape: &[usize],
+    spec: &[(usize, NumStr<Integer, Float, Complex>)],
+) -> Result<(Vec<usize>, Vec<usize>), &'static str> {
+    let resolve = |n: &Number<Integer, Float, Complex>, len: usize| {
+        if !n.number.real().clone().fract().is_zero() {
+            return Err("non integer index");
+        }
+        let n = n.number.real().to_integer().unwrap_or_default();
+        let n = if n.cmp0() == Ordering::Less {
+            n + len
+        } else {
+            n
+        };
+        match n.to_usize() {
+            Some(n) if n < len => Ok(n),
+            _ => Err("out of range"),
+        }
+    };
+    let mut strides = vec![1; shape.len()];
+    for i in (0..shape.len().saturating_sub(1)).rev() {
+        strides[i] = strides[i + 1] * shape[i + 1]
+    }
+    if let [(2, v)] = spec {
+        let (mask_shape, mask) = v.tensor()?;
+        if mask_shape.len() > 1 {
+            if mask_shape != shape {
+                return Err("mask size mismatch");
+            }
+            let positions = mask
+                .iter()
+                .enumerate()
+                .filter(|(_, m)| !m.number.is_zero())
+                .map(|(i, _)| i)
+                .collect::<Vec<usize>>();
+            return Ok((vec![positions.len()], positions));
+        }
+    }
+    if spec.len() > shape.len() {
+        return Err("too many indices");
+    }
+    let mut kept = Vec::new();
+    let mut axes = Vec::with_capacity(shape.len());
+    for (k, &len) in shape.iter().enumerate() {
+        let selection = match spec.get(k) {
+            None => {
+                kept.push(len);
+                (0..len).collect()
+            }
+            Some((0, Num(n))) => vec![resolve(n, len)?],
+            Some((2, Num(_))) => return Err("mask must be a list"),
+            Some((1, Vector(v))) if v.len() == 3 => {
+                let value = |n: &Number<Integer, Float, Complex>| {
+                    if n.number.real().is_nan() {
+                        None
+                    } else {
+                        Some(
+                            n.number
+                                .real()
+                                .to_integer()
+                                .unwrap_or_default()
+                                .to_isize()
+                                .unwrap_or_default(),
+                        )
+                    }
+                };
+                let bound = |n: &Number<Integer, Float, Complex>| {
+                    value(n).map(|n| if n < 0 { n + len as isize } else { n })
+                };
+                let step = value(&v[2]).unwrap_or(1);
+                let mut selection = Vec::new();
+                if step > 0 {
+                    let mut i = bound(&v[0]).unwrap_or(0).clamp(0, len as isize);
+                    let end = bound(&v[1]).unwrap_or(len as isize).clamp(0, len as isize);
+                    while i < end {
+                        selection.push(i as usize);
+                        i += step
+                    }
+                } else if step < 0 {
+                    let mut i = bound(&v[0])
+                        .unwrap_or(len as isize - 1)
+                        .clamp(-1, len as isize - 1);
+                    let end = bound(&v[1]).unwrap_or(-1).clamp(-1, len as isize - 1);
+                    while i > end {
+                        selection.push(i as usize);
+                        i += step
+                    }
+                } else {
+                    return Err("zero step");
+                }
+                kept.push(selection.len());
+                selection
+            }
+            Some((2, Vector(v))) => {
+                if v.len() != len {
+                    return Err("mask size mismatch");
+                }
+                let selection = v
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, m)| !m.number.is_zero())
+                    .map(|(i, _)| i)
+                    .collect::<Vec<usize>>();
+                kept.push(selection.len());
+                selection
+            }
+            Some((0, Vector(v))) => {
+                kept.push(v.len());
+                v.iter()
+                    .map(|n| resolve(n, len))
+                    .collect::<Result<Vec<usize>, &'static str>>()?
+            }
+            _ => return Err("invalid index"),
+        };
+        axes.push(selection);
+    }
+    let mut positions = Vec::with_capacity(axes.iter().map(|a| a.len()).product());
+    if axes.iter().all(|a| !a.is_empty()) {
+        let mut index = vec![0; axes.len()];
+        'outer: loop {
+            positions.push(
+                index
+                    .iter()
+                    .enumerate()
+                    .map(|(k, i)| axes[k][*i] * strides[k])
+                    .sum(),
+            );
+            for j in (0..index.len()).rev() {
+                index[j] += 1;
+                if index[j] < axes[j].len() {
+                    continue 'outer;
+                }
+                index[j] = 0
+            }
+            break;
+        }
+    }
+    Ok((kept, positions))
+}
+#[allow(clippy::type_complexity)]
+pub fn index<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    a: &NumStr<Integer, Float, Complex>,
+    spec: &[(usize, NumStr<Integer, Float, Complex>)],
+) -> Result<NumStr<Integer, Float, Complex>, &'static str> {
+    let (shape, data) = a.tensor()?;
+    let (kept, positions) = index_positions(&shape, spec)?;
+    Ok(from_tensor(
+        kept,
+        positions.into_iter().map(|i| data[i].clone()).collect(),
+    ))
+}
+#[allow(clippy::type_complexity)]
+pub fn index_set<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    a: &NumStr<Integer, Float, Complex>,
+    value: &NumStr<Integer, Float, Complex>,
+    spec: &[(usize, NumStr<Integer, Float, Complex>)],
+) -> Result<NumStr<Integer, Float, Complex>, &'static str> {
+    let (shape, mut data) = a.tensor()?;
+    let (_, positions) = index_positions(&shape, spec)?;
+    match value {
+        Num(n) => positions.iter().for_each(|i| data[*i] = *n.clone()),
+        _ => {
+            let (_, values) = value.tensor()?;
+            if values.len() != positions.len() {
+                return Err("assignment size mismatch");
+            }
+            positions
+                .into_iter()
+                .zip(values)
+                .for_each(|(i, n)| data[i] = n);
+        }
+    }
+    Ok(from_tensor(shape, data))
+}
 #[allow(clippy::type_complexity)]
 pub fn minors<
     Integer: crate::types::Integer<Float, Complex>,
@@ -1829,7 +3039,10 @@ pub fn cofactor<
             for (j, l) in k.iter_mut().enumerate() {
                 *l = if (i + j) % 2 == 1 {
                     let a = determinant(&submatrix(a, i, j))?;
-                    Number::from(-a.number, a.units)
+                    match a.rational() {
+                        Some((p, q)) => Number::from_rational((-p, q), a.units, a.number.prec()),
+                        None => Number::from(-a.number, a.units),
+                    }
                 } else {
                     determinant(&submatrix(a, i, j))?
                 };
@@ -1880,37 +3093,759 @@ pub fn nth_prime<
     }
     num
 }
-pub fn prime_factors<
-    F: crate::types::Float<I, D>,
-    I: crate::types::Integer<F, D>,
-    D: crate::types::Complex<I, F>,
->(
-    mut n: I,
-) -> Vec<(I, isize)> {
-    if n < 2 {
-        return Vec::new();
+// prints which stage a long factorization is in and stops early once INTERRUPT is raised,
+// a flag left over from before the factorization started is cleared rather than honoured
+struct Progress {
+    enabled: bool,
+    printed: bool,
+    start: Instant,
+    last: Instant,
+}
+impl Progress {
+    fn new(enabled: bool) -> Self {
+        INTERRUPT.store(false, AtomicOrdering::Relaxed);
+        Self {
+            enabled,
+            printed: false,
+            start: Instant::now(),
+            last: Instant::now(),
+        }
     }
-    let mut mat = Vec::new();
-    let mut prime = I::from(2);
-    let mut k = 0;
-    loop {
-        let (temp, rem) = n.clone().div_rem(prime.clone());
-        if rem == 0 {
-            k += 1;
-            if temp == 1 {
-                mat.push((prime.clone(), k));
+    fn tick(&mut self, stage: &dyn Fn() -> String) -> Result<(), &'static str> {
+        if INTERRUPT.load(AtomicOrdering::Relaxed) {
+            self.finish();
+            return Err("interrupted");
+        }
+        if self.enabled && self.last.elapsed().as_millis() > 128 {
+            self.last = Instant::now();
+            self.printed = true;
+            print!(
+                "\x1b[G\x1b[Kfactoring: {} {}s",
+                stage(),
+                self.start.elapsed().as_secs()
+            );
+            stdout().flush().unwrap()
+        }
+        Ok(())
+    }
+    fn finish(&mut self) {
+        if self.printed {
+            print!("\x1b[G\x1b[K");
+            stdout().flush().unwrap();
+            self.printed = false
+        }
+    }
+}
+fn md<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    a: Integer,
+    n: &Integer,
+) -> Integer {
+    let r = a % n.clone();
+    if r.cmp0() == Ordering::Less { r + n } else { r }
+}
+// pollard's rho with brent's cycle detection, batching the gcds over m steps
+fn pollard_brent<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    n: &Integer,
+    c: u32,
+    limit: usize,
+    progress: &mut Progress,
+) -> Result<Option<Integer>, &'static str> {
+    let f = |x: Integer| md(x.clone() * x + c, n);
+    let m = 128;
+    let (mut y, mut r, mut q, mut g) = (Integer::from(2), 1, Integer::from(1), Integer::from(1));
+    let (mut x, mut ys) = (y.clone(), y.clone());
+    while g == 1 {
+        x = y.clone();
+        for _ in 0..r {
+            y = f(y)
+        }
+        let mut k = 0;
+        while k < r && g == 1 {
+            ys = y.clone();
+            for _ in 0..m.min(r - k) {
+                y = f(y);
+                q = md(q * (x.clone() - &y).abs(), n)
+            }
+            g = q.clone().gcd(n);
+            k += m
+        }
+        r *= 2;
+        if r > limit {
+            return Ok(None);
+        }
+        progress.tick(&|| format!("rho c={c} {r} steps"))?;
+    }
+    if g == *n {
+        loop {
+            ys = f(ys);
+            g = (x.clone() - &ys).abs().gcd(n);
+            if g != 1 {
+                break;
+            }
+        }
+    }
+    Ok(if g == *n { None } else { Some(g) })
+}
+// one stage 1 curve of lenstra's elliptic curve method on a montgomery curve from
+// suyama's parametrization, using x/z coordinates so no inversions are needed
+fn ecm<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    n: &Integer,
+    sigma: u32,
+    primes: &[u64],
+    b1: u64,
+) -> Option<Integer> {
+    let mul = |a: Integer, b: &Integer| md(a * b, n);
+    let u = md(Integer::from(sigma) * sigma - 5, n);
+    let v = md(Integer::from(sigma) * 4, n);
+    let u3 = mul(mul(u.clone(), &u), &u);
+    let vu = md(v.clone() - &u, n);
+    let t = mul(mul(mul(vu.clone(), &vu), &vu), &md(u.clone() * 3 + &v, n));
+    let d = mul(u3.clone() * 16, &v);
+    let inv = match modinv(d.clone(), n) {
+        Some(inv) => inv,
+        None => {
+            let g = d.gcd(n);
+            return if g != *n { Some(g) } else { None };
+        }
+    };
+    let a24 = mul(t, &inv);
+    let dbl = |x: &Integer, z: &Integer| {
+        let s = md(x.clone() + z, n);
+        let s = mul(s.clone(), &s);
+        let d = md(x.clone() - z, n);
+        let d = mul(d.clone(), &d);
+        let e = md(s.clone() - &d, n);
+        (mul(s, &d), mul(e.clone(), &md(d + mul(a24.clone(), &e), n)))
+    };
+    let add = |p: &(Integer, Integer), q: &(Integer, Integer), diff: &(Integer, Integer)| {
+        let a = mul(md(p.0.clone() - &p.1, n), &md(q.0.clone() + &q.1, n));
+        let b = mul(md(p.0.clone() + &p.1, n), &md(q.0.clone() - &q.1, n));
+        let s = md(a.clone() + &b, n);
+        let d = md(a - b, n);
+        (
+            mul(diff.1.clone(), &mul(s.clone(), &s)),
+            mul(diff.0.clone(), &mul(d.clone(), &d)),
+        )
+    };
+    let mut pt = (u3, mul(mul(v.clone(), &v), &v));
+    for p in primes.iter().take_while(|p| **p <= b1) {
+        let mut k = *p;
+        while k * p <= b1 {
+            k *= p
+        }
+        let mut r0 = pt.clone();
+        let mut r1 = dbl(&pt.0, &pt.1);
+        for bit in (0..63 - k.leading_zeros()).rev() {
+            if k >> bit & 1 == 1 {
+                r0 = add(&r0, &r1, &pt);
+                r1 = dbl(&r1.0, &r1.1)
+            } else {
+                r1 = add(&r0, &r1, &pt);
+                r0 = dbl(&r0.0, &r0.1)
+            }
+        }
+        pt = r0
+    }
+    let g = pt.1.gcd(n);
+    if g != 1 && g != *n { Some(g) } else { None }
+}
+// primes below 2^16 for trial division and below 3*10^6 for ecm stage 1, each sieved once
+static TRIAL_PRIMES: OnceLock<Vec<u64>> = OnceLock::new();
+static ECM_PRIMES: OnceLock<Vec<u64>> = OnceLock::new();
+fn small_primes(n: u64) -> Vec<u64> {
+    let mut sieve = vec![true; n as usize + 1];
+    let mut primes = Vec::new();
+    for i in 2..=n as usize {
+        if sieve[i] {
+            primes.push(i as u64);
+            for j in (i * i..=n as usize).step_by(i) {
+                sieve[j] = false
+            }
+        }
+    }
+    primes
+}
+// splits a composite n with no small factors into two nontrivial factors
+fn find_factor<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    n: &Integer,
+    progress: &mut Progress,
+) -> Result<Integer, &'static str> {
+    for c in 1..4 {
+        if let Some(g) = pollard_brent(n, c, 1 << 16, progress)? {
+            return Ok(g);
+        }
+    }
+    let primes = ECM_PRIMES.get_or_init(|| small_primes(3_000_000));
+    let mut sigma = 6;
+    for (b1, curves) in [
+        (2_000, 25),
+        (11_000, 90),
+        (50_000, 300),
+        (250_000, 700),
+        (1_000_000, 1_800),
+        (3_000_000, 5_100),
+    ] {
+        for curve in 0..curves {
+            progress.tick(&|| format!("ecm B1={b1} curve {}/{curves}", curve + 1))?;
+            if let Some(g) = ecm(n, sigma, primes, b1) {
+                return Ok(g);
+            }
+            sigma += 1
+        }
+    }
+    for c in 4..20 {
+        if let Some(g) = pollard_brent(n, c, 1 << 30, progress)? {
+            return Ok(g);
+        }
+    }
+    Err("failed to factor")
+}
+// trial division by primes below 2^16, then pollard rho and ecm on what remains
+pub fn factorize<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    mut n: Integer,
+    progress: bool,
+) -> Result<Vec<(Integer, isize)>, &'static str> {
+    if n < 2 {
+        return Ok(Vec::new());
+    }
+    let mut factors: Vec<(Integer, isize)> = Vec::new();
+    for p in TRIAL_PRIMES.get_or_init(|| small_primes(1 << 16)) {
+        if n == 1 {
+            break;
+        }
+        let mut k = 0;
+        loop {
+            let (q, r) = n.clone().div_rem(Integer::from(*p as usize));
+            if r != 0 {
                 break;
             }
-            n = temp;
+            n = q;
+            k += 1
+        }
+        if k != 0 {
+            factors.push((Integer::from(*p as usize), k))
+        }
+    }
+    let mut progress = Progress::new(progress);
+    let mut stack = if n == 1 { Vec::new() } else { vec![n] };
+    let mut large = Vec::new();
+    while let Some(m) = stack.pop() {
+        if m.is_probably_prime(100) != IsPrime::No {
+            large.push(m)
         } else {
-            if k != 0 {
-                mat.push((prime.clone(), k));
+            let g = find_factor(&m, &mut progress)?;
+            stack.push(m / &g);
+            stack.push(g)
+        }
+    }
+    progress.finish();
+    large.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+    for p in large {
+        match factors.last_mut() {
+            Some(last) if last.0 == p => last.1 += 1,
+            _ => factors.push((p, 1)),
+        }
+    }
+    Ok(factors)
+}
+pub fn prime_factors<
+    F: crate::types::Float<I, D>,
+    I: crate::types::Integer<F, D>,
+    D: crate::types::Complex<I, F>,
+>(
+    n: I,
+) -> Result<Vec<(I, isize)>, &'static str> {
+    factorize(n, false)
+}
+// every positive divisor of n in increasing order
+pub fn divisors<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    factors: &[(Integer, isize)],
+) -> Vec<Integer> {
+    let mut divs = vec![Integer::from(1)];
+    for (p, k) in factors {
+        let len = divs.len();
+        let mut pk = Integer::from(1);
+        for _ in 0..*k {
+            pk *= p;
+            for i in 0..len {
+                divs.push(divs[i].clone() * &pk)
             }
-            prime = prime.next_prime();
-            k = 0;
         }
     }
-    mat
+    divs.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+    divs
+}
+// inverse of a mod n, none when they share a factor
+pub fn modinv<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    a: Integer,
+    n: &Integer,
+) -> Option<Integer> {
+    let (g, x, _) = egcd(md(a, n), n.clone());
+    if g == 1 { Some(md(x, n)) } else { None }
+}
+// returns (g, x, y) with a*x + b*y = g = gcd(a, b)
+pub fn egcd<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    a: Integer,
+    b: Integer,
+) -> (Integer, Integer, Integer) {
+    let (mut r0, mut r1) = (a, b);
+    let (mut s0, mut s1) = (Integer::from(1), Integer::new());
+    let (mut t0, mut t1) = (Integer::new(), Integer::from(1));
+    while r1 != 0 {
+        let (q, r) = r0.div_rem(r1.clone());
+        r0 = r1;
+        r1 = r;
+        (s0, s1) = (s1.clone(), s0 - q.clone() * s1);
+        (t0, t1) = (t1.clone(), t0 - q * t1);
+    }
+    if r0.cmp0() == Ordering::Less {
+        (-r0, -s0, -t0)
+    } else {
+        (r0, s0, t0)
+    }
+}
+// b^e mod m by square and multiply over the bits of e, negative e uses the inverse of b
+pub fn modpow<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    b: Integer,
+    e: Integer,
+    m: &Integer,
+) -> Option<Integer> {
+    let m = m.clone().abs();
+    if m == 0 {
+        return None;
+    }
+    let (b, e) = if e.cmp0() == Ordering::Less {
+        (modinv(b, &m)?, -e)
+    } else {
+        (md(b, &m), e)
+    };
+    let mut r = md(Integer::from(1), &m);
+    for c in e.to_string_radix(2).chars() {
+        r = md(r.clone() * r, &m);
+        if c == '1' {
+            r = md(r * &b, &m)
+        }
+    }
+    Some(r)
+}
+// floor of the square root of a non negative n via newton's method
+pub fn isqrt<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    n: &Integer,
+) -> Integer {
+    if *n < 2 {
+        return n.clone();
+    }
+    let mut x = n.clone();
+    let mut y = (x.clone() + 1) / Integer::from(2);
+    while y < x {
+        x = y;
+        y = (x.clone() + n.clone() / x.clone()) / Integer::from(2)
+    }
+    x
+}
+// smallest non negative x with x = r_i mod m_i for every i, moduli need not be coprime
+pub fn crt<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    r: Vec<Integer>,
+    m: Vec<Integer>,
+) -> Option<(Integer, Integer)> {
+    let mut x = Integer::new();
+    let mut l = Integer::from(1);
+    for (r, m) in r.into_iter().zip(m) {
+        let m = m.abs();
+        if m == 0 {
+            return None;
+        }
+        let (g, p, _) = egcd(l.clone(), m.clone());
+        let (t, rem) = (r - &x).div_rem(g.clone());
+        if rem != 0 {
+            return None;
+        }
+        let mg = m / &g;
+        x += l.clone() * md(t * p, &mg);
+        l *= mg;
+        x = md(x, &l)
+    }
+    Some((x, l))
+}
+// smallest x >= 0 with g^x = h mod n using baby step giant step
+pub fn discrete_log<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    g: Integer,
+    h: Integer,
+    n: Integer,
+) -> Result<Option<Integer>, &'static str> {
+    let n = n.abs();
+    if n == 0 {
+        return Ok(None);
+    }
+    let (g, h) = (md(g, &n), md(h, &n));
+    INTERRUPT.store(false, AtomicOrdering::Relaxed);
+    let m: Integer = isqrt(&n) + 1;
+    let Some(steps) = m.to_usize().filter(|m| *m <= 1 << 24) else {
+        return Err("modulus too large");
+    };
+    let mut baby = Vec::with_capacity(steps);
+    let mut e = md(Integer::from(1), &n);
+    for j in 0..steps {
+        baby.push((e.clone(), j));
+        e = md(e * &g, &n)
+    }
+    baby.sort_by(|a, b| {
+        a.0.partial_cmp(&b.0)
+            .unwrap_or(Ordering::Equal)
+            .then(a.1.cmp(&b.1))
+    });
+    baby.dedup_by(|a, b| a.0 == b.0);
+    let Some(factor) = modpow(g, -m.clone(), &n) else {
+        return Ok(None);
+    };
+    let mut y = h;
+    for i in 0..steps {
+        if let Ok(k) = baby.binary_search_by(|a| a.0.partial_cmp(&y).unwrap_or(Ordering::Equal)) {
+            return Ok(Some(m * i + baby[k].1));
+        }
+        if INTERRUPT.load(AtomicOrdering::Relaxed) {
+            return Err("interrupted");
+        }
+        y = md(y * &factor, &n)
+    }
+    Ok(None)
+}
+// smallest generator of the multiplicative group mod n, none when the group is not cyclic
+pub fn primitive_root<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    n: Integer,
+    progress: bool,
+) -> Result<Option<Integer>, &'static str> {
+    if n < 1 {
+        return Ok(None);
+    } else if n <= 4 {
+        return Ok(Some(n - 1));
+    }
+    let f = factorize(n.clone(), progress)?;
+    let odd: Vec<&(Integer, isize)> = f.iter().filter(|p| p.0 != 2).collect();
+    let twos = f.iter().find(|p| p.0 == 2).map(|p| p.1).unwrap_or(0);
+    if odd.len() != 1 || twos > 1 {
+        return Ok(None);
+    }
+    let (p, k) = odd[0];
+    let mut phi: Integer = p.clone() - 1;
+    for _ in 1..*k {
+        phi *= p
+    }
+    let qs = factorize(phi.clone(), progress)?;
+    let mut g = Integer::from(2);
+    while g < n {
+        if g.clone().gcd(&n) == 1
+            && qs.iter().all(|(q, _)| {
+                modpow(g.clone(), phi.clone() / q.clone(), &n).is_some_and(|r| r != 1)
+            })
+        {
+            return Ok(Some(g));
+        }
+        g += 1
+    }
+    Ok(None)
+}
+// jacobi symbol (a/n) for odd positive n, equal to the legendre symbol when n is prime
+pub fn jacobi_symbol<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    a: Integer,
+    mut n: Integer,
+) -> Option<i32> {
+    let two = Integer::from(2);
+    if n < 1 || md(n.clone(), &two) == 0 {
+        return None;
+    }
+    let (four, eight) = (Integer::from(4), Integer::from(8));
+    let mut a = md(a, &n);
+    let mut t = 1;
+    while a != 0 {
+        while md(a.clone(), &two) == 0 {
+            a /= two.clone();
+            let r = md(n.clone(), &eight);
+            if r == 3 || r == 5 {
+                t = -t
+            }
+        }
+        std::mem::swap(&mut a, &mut n);
+        if md(a.clone(), &four) == 3 && md(n.clone(), &four) == 3 {
+            t = -t
+        }
+        a = md(a, &n)
+    }
+    Some(if n == 1 { t } else { 0 })
+}
+// euler's phi, the count of 1..=n coprime to n
+pub fn totient<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    factors: &[(Integer, isize)],
+) -> Integer {
+    let mut t = Integer::from(1);
+    for (p, k) in factors {
+        t *= p.clone() - 1;
+        for _ in 1..*k {
+            t *= p
+        }
+    }
+    t
+}
+pub fn mobius<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    factors: &[(Integer, isize)],
+) -> i32 {
+    if factors.iter().any(|(_, k)| *k > 1) {
+        0
+    } else if factors.len() % 2 == 0 {
+        1
+    } else {
+        -1
+    }
+}
+// sum of the k-th powers of the divisors
+pub fn sigma<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    k: u32,
+    factors: &[(Integer, isize)],
+) -> Integer {
+    let mut s = Integer::from(1);
+    for (p, e) in factors {
+        let pk = p.clone().pow(k);
+        let mut term = Integer::from(1);
+        let mut sum = Integer::from(1);
+        for _ in 0..*e {
+            term *= &pk;
+            sum += &term
+        }
+        s *= sum
+    }
+    s
+}
+// exponent of the multiplicative group mod n
+pub fn carmichael<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    factors: &[(Integer, isize)],
+) -> Integer {
+    let mut l = Integer::from(1);
+    for (p, k) in factors {
+        let lp = if *p != 2 {
+            (p.clone() - 1) * p.clone().pow(*k as u32 - 1)
+        } else if *k < 3 {
+            Integer::from(*k)
+        } else {
+            Integer::from(2).pow(*k as u32 - 2)
+        };
+        let g = l.clone().gcd(&lp);
+        l = l * lp / g
+    }
+    l
+}
+fn sieve_range(a: u64, b: u64, base: &[u64], out: &mut Vec<u64>) {
+    const SEGMENT: u64 = 1 << 16;
+    let mut lo = a.max(2);
+    while lo <= b {
+        let hi = b.min(lo + SEGMENT - 1);
+        let mut seg = vec![true; (hi - lo + 1) as usize];
+        for &p in base {
+            if p * p > hi {
+                break;
+            }
+            let mut m = (p * p).max(lo.div_ceil(p) * p);
+            while m <= hi {
+                seg[(m - lo) as usize] = false;
+                m += p
+            }
+        }
+        out.extend(
+            seg.iter()
+                .enumerate()
+                .filter(|(_, b)| **b)
+                .map(|(i, _)| lo + i as u64),
+        );
+        lo = hi + 1
+    }
+}
+// every prime in a..=b via a segmented sieve of eratosthenes
+pub fn primes(a: u64, b: u64) -> Result<Vec<u64>, &'static str> {
+    if b < a || b < 2 {
+        return Ok(Vec::new());
+    }
+    if b - a > 1 << 32 {
+        return Err("range too large");
+    }
+    let root = (b as f64).sqrt() as u64 + 1;
+    let mut base = Vec::new();
+    sieve_range(2, root, &small_primes(root.isqrt() + 1), &mut base);
+    let mut out = Vec::new();
+    sieve_range(a, b, &base, &mut out);
+    Ok(out)
+}
+// product of the first TABLE_PRIMES primes, phi(x, TABLE_PRIMES) repeats with this period
+const PRIMORIAL: u64 = 2 * 3 * 5 * 7 * 11 * 13;
+const TABLE_PRIMES: usize = 6;
+// largest input primepi takes, checked against the known pi(10^12) and a few seconds of work
+const PRIMEPI_LIMIT: u64 = 1_000_000_000_000;
+struct PrimeCount {
+    primes: Vec<u64>,
+    limit: u64,
+    // phi(r, TABLE_PRIMES) for every r below PRIMORIAL
+    table: Vec<u32>,
+    // phi of the small arguments the recursion keeps revisiting
+    memo: RefCell<HashMap<(u64, usize), u64>>,
+    calls: Cell<u64>,
+}
+impl PrimeCount {
+    fn new(limit: u64) -> Self {
+        INTERRUPT.store(false, AtomicOrdering::Relaxed);
+        let primes = primes(2, limit).unwrap_or_default();
+        let mut table = Vec::with_capacity(PRIMORIAL as usize);
+        let mut count = 0;
+        for r in 0..PRIMORIAL {
+            if r != 0 && primes[..TABLE_PRIMES].iter().all(|p| r % p != 0) {
+                count += 1
+            }
+            table.push(count)
+        }
+        Self {
+            primes,
+            limit,
+            table,
+            memo: RefCell::new(HashMap::new()),
+            calls: Cell::new(0),
+        }
+    }
+    fn pi(&self, x: u64) -> Result<u64, &'static str> {
+        if x <= self.limit {
+            Ok(self.primes.partition_point(|p| *p <= x) as u64)
+        } else {
+            self.lehmer(x)
+        }
+    }
+    // legendre's phi, the count of 1..=x with no prime factor among the first a primes
+    fn phi(&self, x: u64, a: usize) -> Result<u64, &'static str> {
+        self.calls.set(self.calls.get() + 1);
+        if self.calls.get() % (1 << 16) == 0 && INTERRUPT.load(AtomicOrdering::Relaxed) {
+            return Err("interrupted");
+        }
+        Ok(match a {
+            0 => x,
+            1 => x - x / 2,
+            2 => x - x / 2 - x / 3 + x / 6,
+            TABLE_PRIMES => {
+                x / PRIMORIAL * self.table[PRIMORIAL as usize - 1] as u64
+                    + self.table[(x % PRIMORIAL) as usize] as u64
+            }
+            _ if x <= self.limit && self.primes[a - 1] * self.primes[a - 1] >= x => {
+                (self.pi(x)? + 1).saturating_sub(a as u64)
+            }
+            _ if x < self.primes[a - 1] => 1.min(x),
+            _ if x < 1 << 24 => {
+                if let Some(n) = self.memo.borrow().get(&(x, a)) {
+                    return Ok(*n);
+                }
+                let n = self.phi(x, a - 1)? - self.phi(x / self.primes[a - 1], a - 1)?;
+                self.memo.borrow_mut().insert((x, a), n);
+                n
+            }
+            _ => self.phi(x, a - 1)? - self.phi(x / self.primes[a - 1], a - 1)?,
+        })
+    }
+    fn lehmer(&self, x: u64) -> Result<u64, &'static str> {
+        let a = self.pi(((x as f64).powf(0.25) as u64 + 1).min(x.isqrt()))? as usize;
+        let b = self.pi(x.isqrt())? as usize;
+        let c = self.pi(((x as f64).cbrt() as u64 + 1).min(x.isqrt()))? as usize;
+        let mut sum = self.phi(x, a)? + ((b + a - 2) * (b - a + 1) / 2) as u64;
+        for i in a + 1..=b {
+            if INTERRUPT.load(AtomicOrdering::Relaxed) {
+                return Err("interrupted");
+            }
+            let w = x / self.primes[i - 1];
+            sum -= self.pi(w)?;
+            if i <= c {
+                let bi = self.pi(w.isqrt())? as usize;
+                for j in i..=bi {
+                    sum -= self.pi(w / self.primes[j - 1])? - (j - 1) as u64
+                }
+            }
+        }
+        Ok(sum)
+    }
+}
+// number of primes <= n, sieving small n and using lehmer's formula past the sieve
+pub fn primepi(n: u64) -> Result<u64, &'static str> {
+    if n > PRIMEPI_LIMIT {
+        return Err("primepi input too large");
+    }
+    let limit = ((n as f64).powf(2.0 / 3.0) as u64)
+        .clamp(1 << 16, 1 << 25)
+        .min(n.max(2))
+        .max(PRIMORIAL.isqrt());
+    PrimeCount::new(limit).pi(n)
 }
 pub fn sort<
     Integer: crate::types::Integer<Float, Complex>,
@@ -1951,41 +3886,197 @@ pub fn sort_mat<
     a.sort_by(|x, y| x.len().partial_cmp(&y.len()).unwrap_or(Ordering::Equal));
     a
 }
-pub fn eigenvalues<
+// an interval set is a two dimensional tensor with rows {start, end, start closed, end closed}
+// kept sorted and disjoint, plain vectors and numbers being sets of points and the empty set an
+// empty vector. from_tensor turns every other two dimensional shape into a matrix, so the tensor
+// variant alone tags a value as an interval set and plain matrices keep their usual meaning
+pub type Interval<I, F, C> = (Number<I, F, C>, Number<I, F, C>, bool, bool);
+pub fn is_interval_set<
     Integer: crate::types::Integer<Float, Complex>,
     Float: crate::types::Float<Integer, Complex>,
     Complex: crate::types::Complex<Integer, Float>,
 >(
-    mat: &[Vec<Number<Integer, Float, Complex>>],
-    real: bool,
-) -> Result<NumStr<Integer, Float, Complex>, &'static str> {
-    if !mat.is_empty() && (0..mat.len()).all(|j| mat.len() == mat[j].len()) {
-        match mat.len() {
-            1 => Ok(NumStr::new(mat[0][0].clone())),
-            2 => {
-                let pr = mat[0][0].number.prec();
-                let mut mat = Matrix(mat.into());
-                mat.set_prec(pr * 2);
-                let mat = mat.mat()?;
-                let mut v = Vector(quadratic(
-                    Number::from(Complex::with_val(mat[0][0].number.prec(), 1), None),
-                    Number::from(
-                        -mat[0][0].number.clone() - mat[1][1].number.clone(),
-                        mat[0][0].units,
-                    ),
-                    Number::from(
-                        mat[0][0].number.clone() * mat[1][1].number.clone()
-                            - mat[0][1].number.clone() * mat[1][0].number.clone(),
-                        mat[0][0].units.map(|a| a.pow(2.0)),
-                    ),
-                    real,
-                ));
-                v.set_prec(pr);
-                Ok(v)
-            }
-            3 => {
-                let pr = mat[0][0].number.prec();
-                let mut mat = Matrix(mat.into());
+    a: &NumStr<Integer, Float, Complex>,
+) -> bool {
+    matches!(a, Tensor(s, _) if s.len() == 2 && s[1] == 4)
+}
+pub fn to_intervals<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    a: &NumStr<Integer, Float, Complex>,
+) -> Result<Vec<Interval<Integer, Float, Complex>>, &'static str> {
+    match a {
+        Num(n) => Ok(vec![(*n.clone(), *n.clone(), true, true)]),
+        Vector(v) => Ok(v
+            .iter()
+            .map(|n| (n.clone(), n.clone(), true, true))
+            .collect()),
+        Tensor(_, t) if is_interval_set(a) => Ok(t
+            .chunks(4)
+            .map(|r| {
+                (
+                    r[0].clone(),
+                    r[1].clone(),
+                    !r[2].number.real().is_zero(),
+                    !r[3].number.real().is_zero(),
+                )
+            })
+            .collect()),
+        _ => Err("not an interval set"),
+    }
+}
+pub fn from_intervals<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    a: Vec<Interval<Integer, Float, Complex>>,
+    prec: u32,
+) -> NumStr<Integer, Float, Complex> {
+    let a = normalize_intervals(a);
+    if a.is_empty() {
+        return Vector(Vec::new());
+    }
+    let b = |b: bool| Number::from(Complex::with_val(prec, b), None);
+    Tensor(
+        vec![a.len(), 4],
+        a.into_iter()
+            .flat_map(|(s, e, l, r)| [s, e, b(l), b(r)])
+            .collect(),
+    )
+}
+pub fn normalize_intervals<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    mut a: Vec<Interval<Integer, Float, Complex>>,
+) -> Vec<Interval<Integer, Float, Complex>> {
+    a.retain(|(s, e, l, r)| {
+        let (s, e) = (s.number.real(), e.number.real());
+        s < e || (s == e && *l && *r)
+    });
+    a.sort_by(|x, y| {
+        x.0.number
+            .real()
+            .partial_cmp(y.0.number.real())
+            .unwrap_or(Ordering::Equal)
+            .then(y.2.cmp(&x.2))
+    });
+    let mut out: Vec<Interval<Integer, Float, Complex>> = Vec::new();
+    for (s, e, l, r) in a {
+        if let Some(last) = out.last_mut() {
+            let (start, end) = (s.number.real(), last.1.number.real());
+            if start < end || (start == end && (last.3 || l)) {
+                match e.number.real().partial_cmp(end) {
+                    Some(Ordering::Greater) => {
+                        last.1 = e;
+                        last.3 = r
+                    }
+                    Some(Ordering::Equal) => last.3 |= r,
+                    _ => {}
+                }
+                continue;
+            }
+        }
+        out.push((s, e, l, r))
+    }
+    out
+}
+pub fn interval_complement<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    a: Vec<Interval<Integer, Float, Complex>>,
+    prec: u32,
+) -> Vec<Interval<Integer, Float, Complex>> {
+    let inf = |c| Number::from(Complex::with_val(prec, c), None);
+    let mut out = Vec::new();
+    let mut start = (inf(Constant::NegInfinity), false);
+    for (s, e, l, r) in normalize_intervals(a) {
+        out.push((start.0, s, start.1, !l));
+        start = (e, !r)
+    }
+    out.push((start.0, inf(Constant::Infinity), start.1, false));
+    normalize_intervals(out)
+}
+pub fn interval_intersection<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    a: &[Interval<Integer, Float, Complex>],
+    b: &[Interval<Integer, Float, Complex>],
+) -> Vec<Interval<Integer, Float, Complex>> {
+    let mut out = Vec::new();
+    for x in a {
+        for y in b {
+            let (s, l) = match x.0.number.real().partial_cmp(y.0.number.real()) {
+                Some(Ordering::Less) => (y.0.clone(), y.2),
+                Some(Ordering::Greater) => (x.0.clone(), x.2),
+                _ => (x.0.clone(), x.2 && y.2),
+            };
+            let (e, r) = match x.1.number.real().partial_cmp(y.1.number.real()) {
+                Some(Ordering::Less) => (x.1.clone(), x.3),
+                Some(Ordering::Greater) => (y.1.clone(), y.3),
+                _ => (x.1.clone(), x.3 && y.3),
+            };
+            out.push((s, e, l, r))
+        }
+    }
+    normalize_intervals(out)
+}
+pub fn interval_contains<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    a: &[Interval<Integer, Float, Complex>],
+    x: &Float,
+) -> bool {
+    a.iter().any(|(s, e, l, r)| {
+        let (s, e) = (s.number.real(), e.number.real());
+        (s < x || (s == x && *l)) && (x < e || (x == e && *r))
+    })
+}
+pub fn eigenvalues<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    mat: &[Vec<Number<Integer, Float, Complex>>],
+    real: bool,
+) -> Result<NumStr<Integer, Float, Complex>, &'static str> {
+    if !mat.is_empty() && (0..mat.len()).all(|j| mat.len() == mat[j].len()) {
+        match mat.len() {
+            1 => Ok(NumStr::new(mat[0][0].clone())),
+            2 => {
+                let pr = mat[0][0].number.prec();
+                let mut mat = Matrix(mat.into());
+                mat.set_prec(pr * 2);
+                let mat = mat.mat()?;
+                let mut v = Vector(quadratic(
+                    Number::from(Complex::with_val(mat[0][0].number.prec(), 1), None),
+                    Number::from(
+                        -mat[0][0].number.clone() - mat[1][1].number.clone(),
+                        mat[0][0].units,
+                    ),
+                    Number::from(
+                        mat[0][0].number.clone() * mat[1][1].number.clone()
+                            - mat[0][1].number.clone() * mat[1][0].number.clone(),
+                        mat[0][0].units.map(|a| a.pow(2.0)),
+                    ),
+                    real,
+                ));
+                v.set_prec(pr);
+                Ok(v)
+            }
+            3 => {
+                let pr = mat[0][0].number.prec();
+                let mut mat = Matrix(mat.into());
                 mat.set_prec(pr * 2);
                 let mat = mat.mat()?;
                 let mut v = Vector(cubic(
@@ -2483,928 +4574,2664 @@ pub fn range<
     }
     Ok(ran)
 }
-pub fn mul_units(a: Option<Units>, b: Option<Units>) -> Option<Units> {
-    match (a, b) {
-        (Some(a), None) => Some(a),
-        (None, Some(b)) => Some(b),
-        (Some(a), Some(b)) => Some(a.mul(&b)),
-        _ => None,
-    }
-}
-pub fn div_units(a: Option<Units>, b: Option<Units>) -> Option<Units> {
-    match (a, b) {
-        (Some(a), None) => Some(a),
-        (None, Some(b)) => Some(b.pow(-1.0)),
-        (Some(a), Some(b)) => Some(a.div(&b)),
-        _ => None,
-    }
+// true when f vanishes at the working precision, leaving an eighth of the bits for rounding error
+fn negligible<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    f: &Float,
+) -> bool {
+    let prec = f.prec();
+    f.clone().abs() < Float::with_val(prec, 1) >> (prec - prec / 8)
 }
-pub fn quadratic<
+#[allow(clippy::type_complexity)]
+pub fn gram_schmidt<
     Integer: crate::types::Integer<Float, Complex>,
     Float: crate::types::Float<Integer, Complex>,
     Complex: crate::types::Complex<Integer, Float>,
 >(
-    a: Number<Integer, Float, Complex>,
-    b: Number<Integer, Float, Complex>,
-    c: Number<Integer, Float, Complex>,
-    real: bool,
-) -> Vec<Number<Integer, Float, Complex>> {
-    if a.number.is_zero() {
-        return if b.number.is_zero() {
-            vec![Number::from(Complex::new(a.number.prec()), None)]
-        } else {
-            let units = div_units(c.units, b.units);
-            let b = b.number;
-            let c = c.number;
-            let mut r = -c / b;
-            if -r.imag().clone().abs().log10() > a.number.prec() / 4 {
-                r = r.real().clone().into();
-            }
-            if real && !r.imag().is_zero() {
-                vec![Number::from(
-                    Complex::with_val(a.number.prec(), Constant::Nan),
-                    None,
-                )]
-            } else {
-                vec![Number::from(r, units)]
-            }
-        };
-    }
-    let units = div_units(c.units, a.units).map(|a| a.root(2.0));
-    let a = a.number;
-    let b = b.number;
-    let c = c.number;
-    let p: Complex = sqr(b.clone());
-    let p: Complex = p - (c * a.clone() * 4);
-    let p = p.sqrt();
-    let a: Complex = a * 2;
-    let mut z1 = (p.clone() - b.clone()) / a.clone();
-    let mut z2 = (-p - b) / a.clone();
-    if -z1.imag().clone().abs().log10() > a.prec() / 4 {
-        z1 = z1.real().clone().into();
-    }
-    if -z2.imag().clone().abs().log10() > a.prec() / 4 {
-        z2 = z2.real().clone().into();
+    a: &[Vec<Number<Integer, Float, Complex>>],
+) -> Result<Vec<Vec<Number<Integer, Float, Complex>>>, &'static str> {
+    if a.is_empty() || a.iter().any(|b| a[0].len() != b.len()) {
+        return Err("invalid matrix");
     }
-    if real {
-        let mut vec = Vec::new();
-        if z1.imag().is_zero() {
-            vec.push(Number::from(z1, units))
+    let prec = a[0][0].number.prec();
+    let norm = |v: &[Complex]| {
+        v.iter()
+            .fold(Float::new(prec), |s, x| s + sqr(x.clone().abs()).real())
+            .sqrt()
+    };
+    let mut basis: Vec<Vec<Complex>> = Vec::new();
+    for v in a {
+        let mut v: Vec<Complex> = v.iter().map(|n| n.number.clone()).collect();
+        let n = norm(&v);
+        if n.is_zero() {
+            continue;
         }
-        if z2.imag().is_zero() {
-            vec.push(Number::from(z2, units))
+        for _ in 0..2 {
+            for q in &basis {
+                let d = q
+                    .iter()
+                    .zip(v.iter())
+                    .fold(Complex::new(prec), |s, (q, v)| s + q.clone().conj() * v);
+                for (v, q) in v.iter_mut().zip(q.iter()) {
+                    *v -= d.clone() * q
+                }
+            }
         }
-        if vec.is_empty() {
-            vec![Number::from(
-                Complex::with_val(a.prec(), Constant::Nan),
-                None,
-            )]
-        } else {
-            vec
+        let m = norm(&v);
+        if !negligible(&(m.clone() / n)) {
+            basis.push(v.into_iter().map(|v| v / m.clone()).collect())
         }
-    } else {
-        vec![Number::from(z1, units), Number::from(z2, units)]
     }
+    Ok(basis
+        .into_iter()
+        .map(|v| v.into_iter().map(|v| Number::from(v, None)).collect())
+        .collect())
 }
-pub fn cubic<
+// eigenvalues of a real symmetric matrix via cyclic jacobi rotations
+fn jacobi<
     Integer: crate::types::Integer<Float, Complex>,
     Float: crate::types::Float<Integer, Complex>,
     Complex: crate::types::Complex<Integer, Float>,
 >(
-    a: Number<Integer, Float, Complex>,
-    b: Number<Integer, Float, Complex>,
-    c: Number<Integer, Float, Complex>,
-    d: Number<Integer, Float, Complex>,
-    real: bool,
-) -> Vec<Number<Integer, Float, Complex>> {
-    let units = div_units(d.units, a.units).map(|a| a.root(3.0));
-    if a.number.is_zero() {
-        return quadratic(b, c, d, real);
-    }
-    let d = d.number;
-    if d.is_zero() {
-        let mut vec = quadratic(a, b, c, real);
-        vec.push(Number::from(Complex::new(d.prec()), vec[0].units));
-        return vec;
-    }
-    let a = a.number;
-    let b = b.number;
-    let c = c.number;
-    let prec = a.prec();
-    let threerecip = Float::with_val(prec, 3).recip();
-    if b.is_zero() && c.is_zero() {
-        let reuse = pow_nth(d / a.clone(), threerecip.clone().into());
-        let mut z1 = -reuse.clone();
-        let mut z2 = reuse.clone() * Complex::with_val(prec, -1).pow(threerecip.clone());
-        let mut z3: Complex = -reuse * Complex::with_val(prec, -1).pow(threerecip * 2);
-        if -z1.imag().clone().abs().log10() > a.prec() / 4 {
-            z1 = z1.real().clone().into();
+    mut a: Vec<Vec<Float>>,
+) -> Vec<Float> {
+    let n = a.len();
+    for _ in 0..64 {
+        let mut done = true;
+        for p in 0..n {
+            for q in p + 1..n {
+                let scale = a[p][p].clone().abs() + a[q][q].clone().abs() + a[p][q].clone().abs();
+                // a zero entry between two zero diagonal entries would give 0/0
+                if a[p][q].is_zero() || negligible(&(a[p][q].clone() / scale)) {
+                    continue;
+                }
+                done = false;
+                let theta: Float = (a[q][q].clone() - &a[p][p]) / (a[p][q].clone() * 2);
+                let r: Float = theta.clone() * theta.clone() + 1;
+                let t = (theta.clone().abs() + r.sqrt()).recip();
+                let t = if theta.is_sign_negative() { -t } else { t };
+                let r: Float = t.clone() * t.clone() + 1;
+                let c = r.sqrt().recip();
+                let s = t * c.clone();
+                for r in a.iter_mut() {
+                    let (x, y) = (r[p].clone(), r[q].clone());
+                    r[p] = c.clone() * &x - s.clone() * &y;
+                    r[q] = s.clone() * x + c.clone() * y;
+                }
+                let (x, y) = (a[p].clone(), a[q].clone());
+                for (k, (x, y)) in x.into_iter().zip(y).enumerate() {
+                    a[p][k] = c.clone() * &x - s.clone() * &y;
+                    a[q][k] = s.clone() * x + c.clone() * y;
+                }
+            }
         }
-        if -z2.imag().clone().abs().log10() > a.prec() / 4 {
-            z2 = z2.real().clone().into();
+        if done {
+            break;
         }
-        if -z3.imag().clone().abs().log10() > a.prec() / 4 {
-            z3 = z3.real().clone().into();
+    }
+    (0..n).map(|i| a[i][i].clone()).collect()
+}
+// singular values in descending order, from the real embedding of the smaller gram matrix
+pub fn singular_values<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    a: &[Vec<Number<Integer, Float, Complex>>],
+) -> Result<Vec<Float>, &'static str> {
+    if a.is_empty() || a[0].is_empty() || a.iter().any(|b| a[0].len() != b.len()) {
+        return Err("invalid matrix");
+    }
+    let prec = a[0][0].number.prec();
+    let a: Vec<Vec<Complex>> = if a.len() < a[0].len() {
+        transpose(a)
+            .iter()
+            .map(|r| r.iter().map(|n| n.number.clone().conj()).collect())
+            .collect()
+    } else {
+        a.iter()
+            .map(|r| r.iter().map(|n| n.number.clone()).collect())
+            .collect()
+    };
+    let n = a[0].len();
+    let mut sym = vec![vec![Float::new(prec); 2 * n]; 2 * n];
+    for i in 0..n {
+        for j in 0..n {
+            let (re, im) = a
+                .iter()
+                .fold(Complex::new(prec), |s, r| s + r[i].clone().conj() * &r[j])
+                .into_real_imag();
+            sym[i][j] = re.clone();
+            sym[i + n][j + n] = re;
+            sym[i][j + n] = -im.clone();
+            sym[i + n][j] = im;
         }
-        return if real {
-            let mut vec = Vec::new();
-            if z1.imag().is_zero() {
-                vec.push(Number::from(z1, units))
-            }
-            if z2.imag().is_zero() {
-                vec.push(Number::from(z2, units))
-            }
-            if z3.imag().is_zero() {
-                vec.push(Number::from(z3, units))
-            }
-            if vec.is_empty() {
-                vec![Number::from(Complex::with_val(prec, Constant::Nan), None)]
+    }
+    let mut eig = jacobi(sym);
+    eig.sort_by(|a, b| b.partial_cmp(a).unwrap_or(Ordering::Equal));
+    Ok(eig
+        .into_iter()
+        .step_by(2)
+        .map(|e| {
+            if e.is_sign_negative() {
+                Float::new(prec)
             } else {
-                vec
+                e.sqrt()
             }
-        } else {
-            vec![
-                Number::from(z1, units),
-                Number::from(z2, units),
-                Number::from(z3, units),
-            ]
-        };
-    }
-    let b = b / a.clone();
-    let c = c / a.clone();
-    let d = d / a.clone();
-    // https://en.wikipedia.org/wiki/Cubic_equation#General_cubic_formula
-    let d0: Complex = sqr(b.clone()) - c.clone() * 3;
-    let d1: Complex = cube(b.clone()) * 2 - b.clone() * c.clone() * 9 + d.clone() * 27;
-    let c: Complex = sqr(d1.clone()) - cube(d0.clone()) * 4;
-    let c: Complex = (d1 + c.sqrt()) / 2;
-    let c = pow_nth(c, threerecip.clone().into());
-    let omega: Complex = (Complex::with_val(prec, 3).sqrt().mul_i(false) - 1) / 2;
-    let mut z1: Complex = if d0.is_zero() {
-        -(b.clone() + c.clone()) / 3
-    } else {
-        -(b.clone() + c.clone() + d0.clone() / c.clone()) / 3
+        })
+        .collect())
+}
+pub fn matrix_norm<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    a: &[Vec<Number<Integer, Float, Complex>>],
+    p: Float,
+) -> Result<Number<Integer, Float, Complex>, &'static str> {
+    if a.is_empty() || a[0].is_empty() || a.iter().any(|b| a[0].len() != b.len()) {
+        return Err("invalid matrix");
+    }
+    let prec = a[0][0].number.prec();
+    let max = |v: Vec<Float>| {
+        v.into_iter()
+            .fold(Float::new(prec), |m, x| if x > m { x } else { m })
     };
-    let c0 = c.clone() * omega.clone();
-    let mut z2: Complex = if d0.is_zero() {
-        -(b.clone() + c0.clone()) / 3
-    } else {
-        -(b.clone() + c0.clone() + d0.clone() / c0) / 3
+    let sum = |v: &[Number<Integer, Float, Complex>]| {
+        v.iter()
+            .fold(Float::new(prec), |s, x| s + x.number.clone().abs().real())
     };
-    let c1 = c * omega.conj();
-    let mut z3: Complex = if d0.is_zero() {
-        -(b + c1.clone()) / 3
+    let n = if p.is_infinite() {
+        max(a.iter().map(|r| sum(r)).collect())
+    } else if p == 1 {
+        max(transpose(a).iter().map(|r| sum(r)).collect())
+    } else if p == 2 {
+        singular_values(a)?.remove(0)
     } else {
-        -(b + c1.clone() + d0 / c1) / 3
+        return Err("unsupported norm");
     };
-    if -z1.imag().clone().abs().log10() > a.prec() / 4 {
-        z1 = z1.real().clone().into();
-    }
-    if -z2.imag().clone().abs().log10() > a.prec() / 4 {
-        z2 = z2.real().clone().into();
-    }
-    if -z3.imag().clone().abs().log10() > a.prec() / 4 {
-        z3 = z3.real().clone().into();
+    Ok(Number::from(Complex::with_val(prec, n), a[0][0].units))
+}
+// square root of the sum of the squared magnitudes of every entry
+pub fn frobenius<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    a: &[Vec<Number<Integer, Float, Complex>>],
+) -> Result<Number<Integer, Float, Complex>, &'static str> {
+    if a.is_empty() || a[0].is_empty() {
+        return Err("invalid matrix");
     }
-    if real {
-        let mut vec = Vec::new();
-        if z1.imag().is_zero() {
-            vec.push(Number::from(z1, units))
-        }
-        if z2.imag().is_zero() {
-            vec.push(Number::from(z2, units))
-        }
-        if z3.imag().is_zero() {
-            vec.push(Number::from(z3, units))
+    let prec = a[0][0].number.prec();
+    let n = a
+        .iter()
+        .flatten()
+        .fold(Float::new(prec), |s, x| {
+            s + sqr(x.number.clone().abs()).real()
+        })
+        .sqrt();
+    Ok(Number::from(Complex::with_val(prec, n), a[0][0].units))
+}
+pub fn cond<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    a: &[Vec<Number<Integer, Float, Complex>>],
+) -> Result<Number<Integer, Float, Complex>, &'static str> {
+    let s = singular_values(a)?;
+    let prec = a[0][0].number.prec();
+    let (max, min) = (s[0].clone(), s[s.len() - 1].clone());
+    Ok(Number::from(
+        Complex::with_val(
+            prec,
+            if max.is_zero() || negligible(&(min.clone() / &max)) {
+                Float::with_val(prec, f64::INFINITY)
+            } else {
+                max / min
+            },
+        ),
+        None,
+    ))
+}
+pub fn is_hermitian<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    a: &[Vec<Number<Integer, Float, Complex>>],
+) -> bool {
+    a.iter().all(|r| r.len() == a.len())
+        && a.iter().enumerate().all(|(i, r)| {
+            r.iter().enumerate().all(|(j, x)| {
+                negligible(
+                    (x.number.clone() - a[j][i].number.clone().conj())
+                        .abs()
+                        .real(),
+                )
+            })
+        })
+}
+// unitary in the complex case, checked via a^H a = I
+pub fn is_orthogonal<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    a: &[Vec<Number<Integer, Float, Complex>>],
+) -> bool {
+    if a.is_empty() || a.iter().any(|r| r.len() != a.len()) {
+        return false;
+    }
+    let prec = a[0][0].number.prec();
+    (0..a.len()).all(|i| {
+        (0..a.len()).all(|j| {
+            let d = a.iter().fold(Complex::new(prec), |s, r| {
+                s + r[i].number.clone().conj() * &r[j].number
+            }) - Complex::with_val(prec, (i == j) as usize);
+            negligible(d.abs().real())
+        })
+    })
+}
+// hermitian with a successful cholesky factorization
+pub fn is_posdef<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    a: &[Vec<Number<Integer, Float, Complex>>],
+) -> bool {
+    if a.is_empty() || !is_hermitian(a) {
+        return false;
+    }
+    let prec = a[0][0].number.prec();
+    let n = a.len();
+    let mut l = vec![vec![Complex::new(prec); n]; n];
+    for j in 0..n {
+        let d = (0..j).fold(a[j][j].number.real().clone(), |s, k| {
+            s - sqr(l[j][k].clone().abs()).real()
+        });
+        if d.is_sign_negative() || negligible(&d) {
+            return false;
         }
-        if vec.is_empty() {
-            vec![Number::from(Complex::with_val(prec, Constant::Nan), None)]
-        } else {
-            vec
+        l[j][j] = Complex::with_val(prec, d.sqrt());
+        for i in j + 1..n {
+            let s = (0..j).fold(a[i][j].number.clone(), |s, k| {
+                s - l[i][k].clone() * l[j][k].clone().conj()
+            });
+            l[i][j] = s / l[j][j].clone();
         }
-    } else {
-        vec![
-            Number::from(z1, units),
-            Number::from(z2, units),
-            Number::from(z3, units),
-        ]
     }
+    true
 }
-pub fn quartic<
+// arithmetic for the simplex tableau, None once an exact value outgrows its cap
+trait Field<
+    I: crate::types::Integer<F, C>,
+    F: crate::types::Float<I, C>,
+    C: crate::types::Complex<I, F>,
+>: Clone
+{
+    fn add(&self, b: &Self) -> Option<Self>;
+    fn sub(&self, b: &Self) -> Option<Self>;
+    fn mul(&self, b: &Self) -> Option<Self>;
+    fn div(&self, b: &Self) -> Option<Self>;
+    fn sign(&self) -> Ordering;
+    fn from_number(n: &Number<I, F, C>) -> Option<Self>;
+    fn float(&self, prec: u32) -> F;
+}
+#[derive(Clone)]
+struct Approx<F>(F);
+impl<
     Integer: crate::types::Integer<Float, Complex>,
     Float: crate::types::Float<Integer, Complex>,
     Complex: crate::types::Complex<Integer, Float>,
->(
-    div: Number<Integer, Float, Complex>,
-    b: Number<Integer, Float, Complex>,
-    c: Number<Integer, Float, Complex>,
-    d: Number<Integer, Float, Complex>,
-    e: Number<Integer, Float, Complex>,
-    real: bool,
-) -> Vec<Number<Integer, Float, Complex>> {
-    let units = div_units(e.units, div.units).map(|a| a.root(4.0));
-    if e.number.is_zero() {
-        let mut vec = cubic(div, b, c, d, real);
-        vec.push(Number::from(Complex::new(e.number.prec()), vec[0].units));
-        return vec;
+> Field<Integer, Float, Complex> for Approx<Float>
+{
+    fn add(&self, b: &Self) -> Option<Self> {
+        Some(Approx(self.0.clone() + &b.0))
     }
-    let div = div.number;
-    if div.is_zero() {
-        return cubic(b, c, d, e, real);
+    fn sub(&self, b: &Self) -> Option<Self> {
+        Some(Approx(self.0.clone() - &b.0))
     }
-    let b = b.number;
-    let c = c.number;
-    let d = d.number;
-    let e = e.number;
-    let prec = div.prec();
-    let threerecip = Float::with_val(prec, 3).recip();
-    let a = b / div.clone();
-    let b = c / div.clone();
-    let c = d / div.clone();
-    let d = e / div;
-    if a.is_zero() && b.is_zero() && c.is_zero() {
-        return if d.is_zero() {
-            vec![Number::from(Complex::new(prec), None)]
+    fn mul(&self, b: &Self) -> Option<Self> {
+        Some(Approx(self.0.clone() * &b.0))
+    }
+    fn div(&self, b: &Self) -> Option<Self> {
+        Some(Approx(self.0.clone() / &b.0))
+    }
+    fn sign(&self) -> Ordering {
+        if negligible(&self.0) {
+            Ordering::Equal
+        } else if self.0.is_sign_negative() {
+            Ordering::Less
         } else {
-            let a = Complex::with_val(prec, 1) / 4;
-            let mut a: Complex = (-d).pow(a);
-            let mut v = vec![a.clone()];
-            for _ in 0..3 {
-                a = a.mul_i(false);
-                v.push(a.clone());
-            }
-            v.into_iter().map(|a| Number::from(a, None)).collect()
-        };
+            Ordering::Greater
+        }
     }
-    // https://upload.wikimedia.org/wikipedia/commons/9/99/Quartic_Formula.svg
-    let alpha: Complex = sqr(b.clone()) - a.clone() * c.clone() * 3 + d.clone() * 12;
-    let phi: Complex = cube(b.clone()) * 2 - a.clone() * b.clone() * c.clone() * 9
-        + sqr(c.clone()) * 27
-        + sqr(a.clone()) * d.clone() * 27
-        - b.clone() * d.clone() * 72;
-    let omega: Complex = cube(alpha.clone()) * -4 + sqr(phi.clone());
-    let omega: Complex = phi + omega.sqrt();
-    let alpha: Complex = if alpha.is_zero() {
-        Complex::new(prec)
-    } else {
-        Float::with_val(prec, 2).pow(threerecip.clone()) * alpha
-            / (pow_nth(omega.clone(), threerecip.clone().into()) * 3)
-    };
-    let beta: Complex = omega / 54;
-    let beta: Complex = pow_nth(beta, threerecip.clone().into());
-    let infirst: Complex = sqr(a.clone()) / 4 - b.clone() / 3 * 2 + alpha.clone() + beta.clone();
-    let first: Complex = infirst.clone().sqrt() / 2;
-    let third: Complex = -cube(a.clone()) + a.clone() * b.clone() * 4 - c.clone() * 8;
-    let third: Complex = if third.is_zero() {
-        Complex::new(prec)
-    } else {
-        third / (first.clone() * 8)
-    };
-    let a4: Complex = -a.clone() / 4;
-    let second: Complex = sqr(a.clone()) / 2 - b.clone() / 3 * 4 - alpha.clone() - beta.clone();
-    let secondn: Complex = second.clone() - third.clone();
-    let secondn: Complex = secondn.sqrt() / 2;
-    let secondp: Complex = second + third.clone();
-    let secondp: Complex = secondp.sqrt() / 2;
-    let mut r1 = a4.clone() - first.clone() - secondn.clone();
-    let mut r2 = a4.clone() - first.clone() + secondn.clone();
-    let mut r3 = a4.clone() + first.clone() - secondp.clone();
-    let mut r4 = a4.clone() + first.clone() + secondp.clone();
-    if -r1.imag().clone().abs().log10() > a.prec() / 8 {
-        r1 = r1.real().clone().into();
+    fn from_number(n: &Number<Integer, Float, Complex>) -> Option<Self> {
+        Some(Approx(n.number.real().clone()))
     }
-    if -r2.imag().clone().abs().log10() > a.prec() / 8 {
-        r2 = r2.real().clone().into();
+    fn float(&self, _: u32) -> Float {
+        self.0.clone()
     }
-    if -r3.imag().clone().abs().log10() > a.prec() / 8 {
-        r3 = r3.real().clone().into();
+}
+// an exact fraction in lowest terms kept by reduce_rational, with the precision capping it
+#[derive(Clone)]
+struct Exact<I>(I, I, u32);
+impl<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+> Field<Integer, Float, Complex> for Exact<Integer>
+{
+    fn add(&self, b: &Self) -> Option<Self> {
+        let (p, q) = reduce_rational::<Integer, Float, Complex>(
+            self.0.clone() * b.1.clone() + b.0.clone() * self.1.clone(),
+            self.1.clone() * b.1.clone(),
+            self.2,
+        )?;
+        Some(Exact(p, q, self.2))
     }
-    if -r4.imag().clone().abs().log10() > a.prec() / 8 {
-        r4 = r4.real().clone().into();
+    fn sub(&self, b: &Self) -> Option<Self> {
+        Field::<Integer, Float, Complex>::add(self, &Exact(-b.0.clone(), b.1.clone(), b.2))
     }
-    if real {
-        let mut vec = Vec::new();
-        if r1.imag().is_zero() {
-            vec.push(Number::from(r1, units))
-        }
-        if r2.imag().is_zero() {
-            vec.push(Number::from(r2, units))
-        }
-        if r3.imag().is_zero() {
-            vec.push(Number::from(r3, units))
-        }
-        if r4.imag().is_zero() {
-            vec.push(Number::from(r4, units))
-        }
-        if vec.is_empty() {
-            vec![Number::from(Complex::with_val(prec, Constant::Nan), None)]
-        } else {
-            vec
-        }
-    } else {
-        vec![
-            Number::from(r1, units),
-            Number::from(r2, units),
-            Number::from(r3, units),
-            Number::from(r4, units),
-        ]
+    fn mul(&self, b: &Self) -> Option<Self> {
+        let (p, q) = reduce_rational::<Integer, Float, Complex>(
+            self.0.clone() * b.0.clone(),
+            self.1.clone() * b.1.clone(),
+            self.2,
+        )?;
+        Some(Exact(p, q, self.2))
+    }
+    fn div(&self, b: &Self) -> Option<Self> {
+        Field::<Integer, Float, Complex>::mul(self, &Exact(b.1.clone(), b.0.clone(), b.2))
+    }
+    fn sign(&self) -> Ordering {
+        self.0.cmp0()
+    }
+    fn from_number(n: &Number<Integer, Float, Complex>) -> Option<Self> {
+        let (p, q) = n.rational()?;
+        Some(Exact(p, q, n.number.prec()))
+    }
+    fn float(&self, prec: u32) -> Float {
+        Float::with_val(prec, &self.0) / Float::with_val(prec, &self.1)
     }
 }
-pub fn variance<
+fn pivot<
     Integer: crate::types::Integer<Float, Complex>,
     Float: crate::types::Float<Integer, Complex>,
     Complex: crate::types::Complex<Integer, Float>,
+    T: Field<Integer, Float, Complex>,
 >(
-    a: &[Number<Integer, Float, Complex>],
-    mean: Option<Complex>,
-    prec: u32,
-) -> Number<Integer, Float, Complex> {
-    let mean = if let Some(n) = mean {
-        n
-    } else {
-        a.iter()
-            .fold(Complex::new(prec), |sum, val| sum + val.number.clone())
-            / a.len()
-    };
-    let mut variance = Complex::new(prec);
-    for a in a {
-        variance += sqr(a.number.clone() - mean.clone())
+    t: &mut [Vec<T>],
+    p: usize,
+    j: usize,
+) -> Option<()> {
+    let v = t[p][j].clone();
+    for x in t[p].iter_mut() {
+        *x = x.div(&v)?
+    }
+    let row = t[p].clone();
+    for (i, r) in t.iter_mut().enumerate() {
+        let f = r[j].clone();
+        if i != p && f.sign() != Ordering::Equal {
+            for (x, y) in r.iter_mut().zip(row.iter()) {
+                *x = x.sub(&f.mul(y)?)?
+            }
+        }
     }
-    Number::from(
-        variance / (a.len().saturating_sub(1)),
-        a[0].units.map(|a| a.pow(2.0)),
-    )
+    Some(())
 }
-#[allow(clippy::type_complexity)]
-pub fn recursion<
+// minimizes cost over the first cols columns of the tableau using bland's rule, false if unbounded
+// and None if the arithmetic gave out
+fn simplex<
     Integer: crate::types::Integer<Float, Complex>,
     Float: crate::types::Float<Integer, Complex>,
     Complex: crate::types::Complex<Integer, Float>,
+    T: Field<Integer, Float, Complex>,
 >(
-    mut func_vars: Vec<(String, Vec<NumStr<Integer, Float, Complex>>)>,
-    mut func: Vec<NumStr<Integer, Float, Complex>>,
-    options: Options,
-) -> Result<NumStr<Integer, Float, Complex>, &'static str> {
-    for fv in func_vars.clone() {
-        if fv.0.ends_with(')') {
-            let mut cont = false;
-            let mut bracket = 0;
-            let mut pw = Vec::new();
-            for f in &func {
-                match f {
-                    LeftBracket | LeftCurlyBracket => bracket += 1,
-                    RightBracket | RightCurlyBracket => {
-                        bracket -= 1;
-                        if !pw.is_empty() && bracket == pw[0] {
-                            pw.remove(0);
-                        }
-                    }
-                    Func(s) if s == "pw" => pw.insert(0, bracket),
-                    _ => {
-                        if !pw.is_empty() && f.str_is(&fv.0) {
-                            cont = true
-                        }
-                    }
-                }
+    t: &mut [Vec<T>],
+    basis: &mut [usize],
+    cost: &[T],
+    cols: usize,
+) -> Option<bool> {
+    loop {
+        let mut entering = None;
+        for j in (0..cols).filter(|j| !basis.contains(j)) {
+            let mut r = cost[j].clone();
+            for (row, b) in t.iter().zip(basis.iter()) {
+                r = r.sub(&cost[*b].mul(&row[j])?)?
             }
-            if cont {
-                continue;
+            if r.sign() == Ordering::Less {
+                entering = Some(j);
+                break;
             }
-            if fv.0.contains(',') {
-                let mut vars = fv.0.split(',').collect::<Vec<&str>>();
-                vars[0] = vars[0].split('(').next_back().unwrap();
-                {
-                    let vl = vars.len().saturating_sub(1);
-                    vars[vl] = &vars[vl][0..vars[vl].len().saturating_sub(1)];
-                }
-                let mut x = func.len();
-                while x > 0 {
-                    x -= 1;
-                    if func[x].str_is(&fv.0) {
-                        let mut fv = fv.clone();
-                        let mut bracket = 0;
-                        let mut k = 0;
-                        let mut processed = Vec::new();
-                        let mut last = 0;
-                        for (i, n) in func[x + 2..].iter().enumerate() {
-                            match n {
-                                LeftBracket | LeftCurlyBracket => bracket += 1,
-                                RightBracket | RightCurlyBracket => {
-                                    if bracket == 0 {
-                                        if let Ok(n) = do_math(
-                                            func[x + 2 + last..x + 2 + i].to_vec(),
-                                            options,
-                                            func_vars.clone(),
-                                        ) {
-                                            processed.push(vec![n]);
-                                        } else {
-                                            let iden = format!(
-                                                "@{}{}@",
-                                                func_vars.len(),
-                                                vars[processed.len()]
-                                            );
-                                            func_vars.push((
-                                                iden.clone(),
-                                                func[x + 2 + last..x + 2 + i].to_vec(),
-                                            ));
-                                            processed.push(vec![Func(iden)]);
-                                        }
-                                        k = i;
-                                        break;
-                                    }
-                                    bracket -= 1;
-                                }
-                                Comma if bracket == 0 => {
-                                    if let Ok(n) = do_math(
-                                        func[x + 2 + last..x + 2 + i].to_vec(),
-                                        options,
-                                        func_vars.clone(),
-                                    ) {
-                                        processed.push(vec![n]);
-                                    } else {
-                                        let iden = format!(
-                                            "@{}{}@",
-                                            func_vars.len(),
-                                            vars[processed.len()]
-                                        );
-                                        func_vars.push((
-                                            iden.clone(),
-                                            func[x + 2 + last..x + 2 + i].to_vec(),
-                                        ));
-                                        processed.push(vec![Func(iden)]);
-                                    }
-                                    last = i + 1;
-                                }
-                                _ => {}
-                            }
-                        }
-                        let mut i = 0;
-                        while i < fv.1.len() {
-                            if let Func(s) = &fv.1[i] {
-                                for v in processed.iter().zip(vars.iter()) {
-                                    if s == v.1 {
-                                        fv.1.remove(i);
-                                        fv.1.splice(i..i, v.0.clone());
-                                        break;
-                                    }
-                                }
-                            }
-                            i += 1;
-                        }
-                        func.drain(x..=k + x + 2);
-                        func.splice(x..x, fv.1.clone());
-                    }
-                }
-            } else {
-                let var = fv.0.split('(').next_back().unwrap();
-                let var = &var[0..var.len().saturating_sub(1)];
-                let mut x = func.len();
-                while x > 0 {
-                    x -= 1;
-                    if func[x].str_is(&fv.0) {
-                        let mut fv = fv.clone();
-                        let mut bracket = 0;
-                        let mut k = 0;
-                        for (i, n) in func[x + 2..].iter().enumerate() {
-                            match n {
-                                LeftBracket | LeftCurlyBracket => bracket += 1,
-                                RightBracket | RightCurlyBracket => {
-                                    if bracket == 0 {
-                                        k = i;
-                                    }
-                                    bracket -= 1;
-                                }
-                                _ => {}
-                            }
-                        }
-                        let iden = format!("@{}{}@", func_vars.len(), var);
-                        let mut i = 0;
-                        while i < fv.1.len() {
-                            if let Func(s) = &fv.1[i] {
-                                if *s == var {
-                                    fv.1[i] = Func(iden.clone());
-                                }
-                            }
-                            i += 1;
-                        }
-                        func_vars.push((iden.clone(), func[x + 1..=k + x + 2].to_vec()));
-                        func.drain(x..=k + x + 2);
-                        func.splice(x..x, fv.1.clone());
-                    }
+        }
+        let Some(j) = entering else {
+            return Some(true);
+        };
+        let mut best: Option<(usize, T)> = None;
+        for (i, row) in t.iter().enumerate() {
+            if row[j].sign() == Ordering::Greater {
+                let q = row[row.len() - 1].div(&row[j])?;
+                if match &best {
+                    None => true,
+                    Some((k, v)) => match q.sub(v)?.sign() {
+                        Ordering::Less => true,
+                        Ordering::Equal => basis[i] < basis[*k],
+                        Ordering::Greater => false,
+                    },
+                } {
+                    best = Some((i, q))
                 }
             }
         }
+        let Some((p, _)) = best else {
+            return Some(false);
+        };
+        pivot(t, p, j)?;
+        basis[p] = j;
     }
-    do_math(func, options, func_vars)
 }
-pub fn gcd<
+// two phase simplex for min c.y subject to rows (a.y <= b or a.y = b) and y >= 0,
+// Err(1) if infeasible and Err(2) if unbounded, None if the arithmetic gave out
+#[allow(clippy::type_complexity)]
+fn simplex_solve<
     Integer: crate::types::Integer<Float, Complex>,
     Float: crate::types::Float<Integer, Complex>,
     Complex: crate::types::Complex<Integer, Float>,
+    T: Field<Integer, Float, Complex>,
 >(
-    mut x: Integer,
-    mut y: Integer,
-) -> Integer {
-    while x != y {
-        if x > y {
-            x -= y.clone()
-        } else {
-            y -= x.clone()
+    c: &[T],
+    rows: Vec<(Vec<T>, T, bool)>,
+    zero: &T,
+    one: &T,
+) -> Option<Result<Vec<T>, usize>> {
+    let n = c.len();
+    let s = rows.iter().filter(|(_, _, eq)| !eq).count();
+    let m = rows.len();
+    let mut t = Vec::with_capacity(m);
+    let mut basis = Vec::with_capacity(m);
+    let mut k = n;
+    for (i, (a, b, eq)) in rows.into_iter().enumerate() {
+        let negate = b.sign() == Ordering::Less;
+        let flip = |x: &T| if negate { zero.sub(x) } else { Some(x.clone()) };
+        let mut row = a.iter().map(flip).collect::<Option<Vec<T>>>()?;
+        row.extend(vec![zero.clone(); s + m + 1]);
+        if !eq {
+            row[k] = flip(one)?;
+        }
+        row[n + s + i] = one.clone();
+        row[n + s + m] = flip(&b)?;
+        basis.push(if !eq && !negate { k } else { n + s + i });
+        if !eq {
+            k += 1
         }
+        t.push(row);
+    }
+    let mut cost = vec![zero.clone(); n + s];
+    cost.extend(vec![one.clone(); m]);
+    simplex(&mut t, &mut basis, &cost, n + s + m)?;
+    if t.iter()
+        .zip(basis.iter())
+        .any(|(row, b)| *b >= n + s && row[n + s + m].sign() == Ordering::Greater)
+    {
+        return Some(Err(1));
     }
-    x
-}
-pub fn incomplete_beta<
-    I: crate::types::Integer<F, C>,
-    F: crate::types::Float<I, C>,
-    C: crate::types::Complex<I, F>,
->(
-    x: C,
-    a: C,
-    b: C,
-) -> C {
-    if x.real() > &((a.real().clone() + 1) / (a.real().clone() + b.real() + 2)) {
-        (gamma(a.clone()) * gamma(b.clone()) / gamma(a.clone() + b.clone()))
-            - incomplete_beta(-x + 1, b, a)
-    } else {
-        let f: C = -x.clone() + 1;
-        pow_nth(x.clone(), a.clone()) * pow_nth(f, b.clone())
-            / (a.clone() * (incomplete_beta_recursion(x, a, b, 1, 10) + 1))
+    for i in (0..t.len()).rev() {
+        if basis[i] >= n + s {
+            if let Some(j) = (0..n + s).find(|j| t[i][*j].sign() != Ordering::Equal) {
+                pivot(&mut t, i, j)?;
+                basis[i] = j;
+            } else {
+                t.remove(i);
+                basis.remove(i);
+            }
+        }
     }
-}
-fn incomplete_beta_recursion<
-    I: crate::types::Integer<F, C>,
-    F: crate::types::Float<I, C>,
-    C: crate::types::Complex<I, F>,
->(
-    x: C,
-    a: C,
-    b: C,
-    iter: usize,
-    max: usize,
-) -> C {
-    if iter == max {
-        C::new(x.prec())
-    } else if iter % 2 == 1 {
-        let m = (iter - 1) / 2;
-        (-x.clone() * (a.clone() + m) * (a.clone() + b.clone() + m)
-            / ((a.clone() + (2 * m)) * (a.clone() + (2 * m) + 1)))
-            / (incomplete_beta_recursion(x, a, b, iter + 1, max) + 1)
-    } else {
-        let m = iter / 2;
-        (x.clone() * m * (b.clone() - m) / ((a.clone() + (2 * m)) * (a.clone() + (2 * m) - 1)))
-            / (incomplete_beta_recursion(x, a, b, iter + 1, max) + 1)
+    let mut cost = c.to_vec();
+    cost.extend(vec![zero.clone(); s + m]);
+    if !simplex(&mut t, &mut basis, &cost, n + s)? {
+        return Some(Err(2));
+    }
+    let mut y = vec![zero.clone(); n];
+    for (row, b) in t.iter().zip(basis.iter()) {
+        if *b < n {
+            y[*b] = row[n + s + m].clone()
+        }
     }
+    Some(Ok(y))
 }
-pub fn erf<
+// a constraint row a.x <= b, or a.x = b when the flag is set
+type LpRow<I, F, C> = (Vec<Number<I, F, C>>, Number<I, F, C>, bool);
+// substitutes the bounds lo <= x <= hi into y >= 0 form, None if an input is not representable
+// in T or the arithmetic gave out
+#[allow(clippy::type_complexity)]
+fn lp_with<
     Integer: crate::types::Integer<Float, Complex>,
     Float: crate::types::Float<Integer, Complex>,
     Complex: crate::types::Complex<Integer, Float>,
+    T: Field<Integer, Float, Complex>,
 >(
-    z: Complex,
-) -> Complex {
-    -erfc(z) + 1
+    c: &[Number<Integer, Float, Complex>],
+    rows: &[LpRow<Integer, Float, Complex>],
+    bounds: &[(
+        Number<Integer, Float, Complex>,
+        Number<Integer, Float, Complex>,
+    )],
+    options: Options,
+) -> Option<Result<Vec<Float>, usize>> {
+    let conv = |n: &Number<Integer, Float, Complex>| T::from_number(n);
+    let zero = conv(&Number::from(Complex::new(options.prec), None))?;
+    let one = conv(&Number::from(Complex::with_val(options.prec, 1), None))?;
+    let mut map: Vec<(T, Vec<(usize, bool)>)> = Vec::with_capacity(c.len());
+    let mut extra = Vec::new();
+    let mut k = 0;
+    for (lo, hi) in bounds {
+        if lo.number.real().is_finite() {
+            if hi.number.real().is_finite() {
+                extra.push((k, conv(hi)?.sub(&conv(lo)?)?))
+            }
+            map.push((conv(lo)?, vec![(k, false)]));
+            k += 1
+        } else if hi.number.real().is_finite() {
+            map.push((conv(hi)?, vec![(k, true)]));
+            k += 1
+        } else {
+            map.push((zero.clone(), vec![(k, false), (k + 1, true)]));
+            k += 2
+        }
+    }
+    let substitute = |a: &[Number<Integer, Float, Complex>]| -> Option<(Vec<T>, T)> {
+        let mut row = vec![zero.clone(); k];
+        let mut offset = zero.clone();
+        for (a, (o, terms)) in a.iter().zip(map.iter()) {
+            let a = conv(a)?;
+            offset = offset.add(&a.mul(o)?)?;
+            for (j, negate) in terms {
+                row[*j] = if *negate {
+                    row[*j].sub(&a)?
+                } else {
+                    row[*j].add(&a)?
+                }
+            }
+        }
+        Some((row, offset))
+    };
+    let mut new = Vec::with_capacity(rows.len() + extra.len());
+    for (a, b, eq) in rows {
+        let (row, offset) = substitute(a)?;
+        new.push((row, conv(b)?.sub(&offset)?, *eq))
+    }
+    for (j, b) in extra {
+        let mut row = vec![zero.clone(); k];
+        row[j] = one.clone();
+        new.push((row, b, false))
+    }
+    let (cost, _) = substitute(c)?;
+    Some(match simplex_solve(&cost, new, &zero, &one)? {
+        Ok(y) => Ok(map
+            .iter()
+            .map(|(o, terms)| {
+                let mut x = o.clone();
+                for (j, negate) in terms {
+                    x = if *negate {
+                        x.sub(&y[*j])?
+                    } else {
+                        x.add(&y[*j])?
+                    }
+                }
+                Some(x.float(options.prec))
+            })
+            .collect::<Option<Vec<Float>>>()?),
+        Err(status) => Err(status),
+    })
 }
-pub fn erfc<
+#[allow(clippy::type_complexity)]
+fn lp_args<
     Integer: crate::types::Integer<Float, Complex>,
     Float: crate::types::Float<Integer, Complex>,
     Complex: crate::types::Complex<Integer, Float>,
 >(
-    z: Complex,
-) -> Complex {
-    let p2: Complex = sqr(z.clone());
-    erfc_recursion(z.clone(), 0, z.prec() as usize / 4)
-        / Complex::with_val(z.prec(), Constant::Pi).sqrt()
-        * z
-        / p2.exp()
+    args: &[NumStr<Integer, Float, Complex>],
+    n: usize,
+) -> Result<Vec<LpRow<Integer, Float, Complex>>, &'static str> {
+    let real = |v: &[Number<Integer, Float, Complex>]| {
+        if v.iter().all(|n| n.number.imag().is_zero()) {
+            Ok(v.to_vec())
+        } else {
+            Err("complex input")
+        }
+    };
+    let mut rows = Vec::new();
+    for (k, eq) in [(0, false), (2, true)] {
+        if k + 1 < args.len() {
+            let a = match &args[k] {
+                Matrix(m) => m.clone(),
+                Vector(v) if v.is_empty() => Vec::new(),
+                Vector(v) => vec![v.clone()],
+                Num(n) => vec![vec![*n.clone()]],
+                _ => return Err("bad input"),
+            };
+            let b = match &args[k + 1] {
+                Vector(v) => v.clone(),
+                Num(n) => vec![*n.clone()],
+                _ => return Err("bad input"),
+            };
+            if a.len() != b.len() || a.iter().any(|r| r.len() != n) {
+                return Err("dimension mismatch");
+            }
+            for (a, b) in a.iter().zip(real(&b)?) {
+                rows.push((real(a)?, b, eq))
+            }
+        } else if k < args.len() {
+            return Err("not enough args");
+        }
+    }
+    Ok(rows)
 }
-fn erfc_recursion<
+// minimizes c.x subject to A.x <= b, Aeq.x = beq and the bounds (default x >= 0),
+// returning the flat vector {status, objective, x1, x2, ...} where status is 0 optimal,
+// 1 infeasible (objective and x are nan) or 2 unbounded (objective is -inf, x is nan)
+pub fn lp<
     Integer: crate::types::Integer<Float, Complex>,
     Float: crate::types::Float<Integer, Complex>,
     Complex: crate::types::Complex<Integer, Float>,
 >(
-    z: Complex,
-    iter: usize,
-    max: usize,
-) -> Complex {
-    if iter == max {
-        Complex::with_val(z.prec(), 1)
-    } else if iter == 0 {
-        erfc_recursion(z, 1, max).recip()
-    } else if iter % 2 == 1 {
-        sqr(z.clone()) + Float::with_val(z.prec(), iter) / (erfc_recursion(z, iter + 1, max) * 2)
-    } else {
-        Float::with_val(z.prec(), iter) / (erfc_recursion(z, iter + 1, max) * 2) + 1
-    }
-}
-fn gamma0<
-    I: crate::types::Integer<F, C>,
-    F: crate::types::Float<I, C>,
-    C: crate::types::Complex<I, F>,
->(
-    z: C,
-) -> C {
-    let p = z.prec() as usize / 4;
-    gamma0_recursion_first(z.clone(), 0, p) + gamma0_recursion_second(z, 0, p)
-}
-fn gamma0_recursion_first<
-    I: crate::types::Integer<F, C>,
-    F: crate::types::Float<I, C>,
-    C: crate::types::Complex<I, F>,
->(
-    z: C,
-    iter: usize,
-    max: usize,
-) -> C {
-    if iter == max {
-        C::with_val(z.prec(), 1)
-    } else if iter == 0 {
-        F::with_val(z.prec(), -1).exp() / gamma0_recursion_first(z, 1, max)
-    } else {
-        -z.clone() + 2 * iter + (z.clone() - iter) * iter / gamma0_recursion_first(z, iter + 1, max)
-    }
-}
-fn gamma0_recursion_second<
-    I: crate::types::Integer<F, C>,
-    F: crate::types::Float<I, C>,
-    C: crate::types::Complex<I, F>,
->(
-    z: C,
-    iter: usize,
-    max: usize,
-) -> C {
-    if iter == max {
-        C::with_val(z.prec(), 1)
-    } else if iter == 0 {
-        F::with_val(z.prec(), -1).exp() / gamma0_recursion_second(z, 1, max)
-    } else if iter % 2 == 1 {
-        z.clone() + (iter - 1) - (z.clone() + iter / 2) / gamma0_recursion_second(z, iter + 1, max)
-    } else {
-        z.clone()
-            + (iter - 1)
-            + C::with_val(z.prec(), iter / 2) / gamma0_recursion_second(z, iter + 1, max)
-    }
-}
-pub fn incomplete_gamma<
-    Integer: crate::types::Integer<Float, Complex>,
-    Float: crate::types::Float<Integer, Complex>,
-    Complex: crate::types::Complex<Integer, Float>,
->(
-    s: Complex,
-    z: Complex,
-) -> Complex {
-    if z.is_zero() {
-        gamma(s)
-    } else if s.real().is_sign_positive()
-        && !s.real().clone().fract().is_zero()
-        && *z.real() <= 0.25
-    {
-        let p = z.prec() as usize / 4;
-        gamma(s.clone()) - lower_incomplete_gamma_recursion(s, z, 0, p)
-    } else {
-        let p = z.prec() as usize / 4;
-        incomplete_gamma_recursion(s, z, 0, p)
-    }
+    args: &[NumStr<Integer, Float, Complex>],
+    options: Options,
+) -> Result<NumStr<Integer, Float, Complex>, &'static str> {
+    let c = match &args[0] {
+        Vector(v) => v.clone(),
+        Num(n) => vec![*n.clone()],
+        _ => return Err("bad input"),
+    };
+    let n = c.len();
+    if args.len() < 3 {
+        return Err("not enough args");
+    }
+    let rows = lp_args(&args[1..5.min(args.len())], n)?;
+    let num = |f: f64| Number::from(Complex::with_val(options.prec, f), None);
+    let bounds = match args.get(5) {
+        None => vec![(num(0.0), num(f64::INFINITY)); n],
+        Some(Vector(v)) if v.len() == 2 => vec![(v[0].clone(), v[1].clone()); n],
+        Some(Matrix(m)) if m.len() == n && m.iter().all(|r| r.len() == 2) => {
+            m.iter().map(|r| (r[0].clone(), r[1].clone())).collect()
+        }
+        _ => return Err("bad bounds"),
+    };
+    // exact while every input has a fraction and the fractions stay small, floats otherwise
+    let result =
+        match lp_with::<Integer, Float, Complex, Exact<Integer>>(&c, &rows, &bounds, options) {
+            Some(r) => r,
+            None => lp_with::<Integer, Float, Complex, Approx<Float>>(&c, &rows, &bounds, options)
+                .unwrap_or(Err(1)),
+        };
+    let zero = Float::new(options.prec);
+    Ok(lp_result(
+        result,
+        |x| {
+            c.iter()
+                .zip(x.iter())
+                .fold(zero.clone(), |s, (c, x)| s + c.number.real().clone() * x)
+        },
+        n,
+        options,
+    ))
 }
-pub fn lower_incomplete_gamma<
+fn lp_result<
     Integer: crate::types::Integer<Float, Complex>,
     Float: crate::types::Float<Integer, Complex>,
     Complex: crate::types::Complex<Integer, Float>,
 >(
-    s: Complex,
-    z: Complex,
-) -> Complex {
-    if s.real().is_sign_positive() && !s.real().clone().fract().is_zero() && *z.real() <= 1 {
-        let p = z.prec() as usize / 4;
-        lower_incomplete_gamma_recursion(s, z, 0, p)
-    } else {
-        gamma(s.clone()) - incomplete_gamma(s, z)
-    }
+    result: Result<Vec<Float>, usize>,
+    objective: impl Fn(&[Float]) -> Float,
+    n: usize,
+    options: Options,
+) -> NumStr<Integer, Float, Complex> {
+    let num = |f: Float| Number::from(Complex::with_val(options.prec, f), None);
+    let (status, value, x) = match result {
+        Ok(x) => (0, objective(&x), x),
+        Err(status) => (
+            status,
+            Float::with_val(
+                options.prec,
+                if status == 2 {
+                    f64::NEG_INFINITY
+                } else {
+                    f64::NAN
+                },
+            ),
+            vec![Float::with_val(options.prec, f64::NAN); n],
+        ),
+    };
+    let mut vec = vec![num(Float::with_val(options.prec, status)), num(value)];
+    vec.extend(x.into_iter().map(num));
+    Vector(vec)
 }
-pub fn eta<
+fn gauss<
     Integer: crate::types::Integer<Float, Complex>,
     Float: crate::types::Float<Integer, Complex>,
     Complex: crate::types::Complex<Integer, Float>,
 >(
-    s: Complex,
-) -> Complex {
-    let prec = s.prec();
-    let mut sum = Complex::new(prec);
-    let two = Float::with_val(prec, 2);
-    for n in 0..=(prec / 16).max(16) {
-        let mut innersum = Complex::new(prec);
-        let nb = Integer::from(n);
-        for k in 0..=n {
-            let num = nb.clone().binomial(k) * pow_nth(Complex::with_val(prec, 1 + k), -s.clone());
-            if k % 2 == 0 {
-                innersum += num;
-            } else {
-                innersum -= num;
+    mut a: Vec<Vec<Float>>,
+    mut b: Vec<Float>,
+) -> Option<Vec<Float>> {
+    let n = b.len();
+    for k in 0..n {
+        let p = (k..n).max_by(|i, j| {
+            a[*i][k]
+                .clone()
+                .abs()
+                .partial_cmp(&a[*j][k].clone().abs())
+                .unwrap_or(Ordering::Equal)
+        })?;
+        if Field::<Integer, Float, Complex>::sign(&Approx(a[p][k].clone())) == Ordering::Equal {
+            return None;
+        }
+        a.swap(k, p);
+        b.swap(k, p);
+        let (top, bottom) = a.split_at_mut(k + 1);
+        for (i, r) in bottom.iter_mut().enumerate() {
+            let f = r[k].clone() / top[k][k].clone();
+            for (x, y) in r[k..].iter_mut().zip(top[k][k..].iter()) {
+                *x -= y.clone() * f.clone()
             }
+            let v = b[k].clone() * f;
+            b[k + 1 + i] -= v
         }
-        sum += innersum / two.clone().pow(n + 1)
     }
-    sum
-}
-pub fn zeta<
-    Integer: crate::types::Integer<Float, Complex>,
-    Float: crate::types::Float<Integer, Complex>,
-    Complex: crate::types::Complex<Integer, Float>,
->(
-    s: Complex,
-) -> Complex {
-    eta(s.clone()) / (-pow_nth(Complex::with_val(s.prec(), 2), -s + 1) + 1)
+    let mut x = b.clone();
+    for k in (0..n).rev() {
+        let mut s = b[k].clone();
+        for j in k + 1..n {
+            s -= a[k][j].clone() * x[j].clone()
+        }
+        x[k] = s / a[k][k].clone()
+    }
+    Some(x)
 }
-pub fn euleriannumbers<
+// minimizes x.Q.x/2+c.x subject to A.x <= b and Aeq.x = beq for positive semidefinite Q
+// via the primal active set method, returning {status, objective, x} like lp
+pub fn qp<
     Integer: crate::types::Integer<Float, Complex>,
     Float: crate::types::Float<Integer, Complex>,
     Complex: crate::types::Complex<Integer, Float>,
 >(
-    n: Complex,
-    k: i32,
-) -> Complex {
-    if k < 0 {
-        Complex::with_val(n.prec(), Constant::Nan)
-    } else if n.real().clone().fract() != 0 && n.imag().is_zero() && n.real().is_sign_positive() {
-        let mut sum = Complex::new(n.prec());
-        for i in 0..=k {
-            let ic = Complex::with_val(n.prec(), i);
-            let num: Complex = -ic.clone() + k + 1;
-            let num = binomial(n.clone() + 1, ic) * pow_nth(num, n.clone());
-            if i % 2 == 0 { sum += num } else { sum -= num }
+    args: &[NumStr<Integer, Float, Complex>],
+    options: Options,
+) -> Result<NumStr<Integer, Float, Complex>, &'static str> {
+    if args.len() < 2 {
+        return Err("not enough args");
+    }
+    let real = |n: &Number<Integer, Float, Complex>| n.number.real().clone();
+    let q: Vec<Vec<Float>> = match &args[0] {
+        Matrix(m) => m.iter().map(|r| r.iter().map(real).collect()).collect(),
+        Num(n) => vec![vec![real(n)]],
+        _ => return Err("bad input"),
+    };
+    let c: Vec<Float> = match &args[1] {
+        Vector(v) => v.iter().map(real).collect(),
+        Num(n) => vec![real(n)],
+        _ => return Err("bad input"),
+    };
+    let n = c.len();
+    if q.len() != n || q.iter().any(|r| r.len() != n) {
+        return Err("dimension mismatch");
+    }
+    let constraints = lp_args(&args[2..6.min(args.len())], n)?;
+    let rows: Vec<(Vec<Float>, Float, bool)> = constraints
+        .iter()
+        .map(|(a, b, eq)| (a.iter().map(real).collect(), real(b), *eq))
+        .collect();
+    let zero = Float::new(options.prec);
+    let dot = |a: &[Float], b: &[Float]| {
+        a.iter()
+            .zip(b.iter())
+            .fold(zero.clone(), |s, (a, b)| s + a.clone() * b)
+    };
+    let objective = |x: &[Float]| {
+        q.iter()
+            .zip(x.iter())
+            .fold(dot(&c, x), |s, (r, xi)| s + dot(r, x) * xi / 2)
+    };
+    let num = |f: f64| Number::from(Complex::with_val(options.prec, f), None);
+    let free = vec![(num(f64::NEG_INFINITY), num(f64::INFINITY)); n];
+    let sign = |f: Float| Field::<Integer, Float, Complex>::sign(&Approx(f));
+    let mut x = match lp_with::<Integer, Float, Complex, Approx<Float>>(
+        &vec![num(0.0); n],
+        &constraints,
+        &free,
+        options,
+    ) {
+        Some(Ok(x)) => x,
+        _ => return Ok(lp_result(Err(1), objective, n, options)),
+    };
+    let mut work: Vec<usize> = (0..rows.len()).filter(|i| rows[*i].2).collect();
+    for _ in 0..1024 {
+        let size = n + work.len();
+        let mut kkt = vec![vec![zero.clone(); size]; size];
+        let mut rhs = vec![zero.clone(); size];
+        for i in 0..n {
+            kkt[i][..n].clone_from_slice(&q[i]);
+            rhs[i] = -(dot(&q[i], &x) + &c[i]);
+        }
+        for (k, w) in work.iter().enumerate() {
+            kkt[n + k][..n].clone_from_slice(&rows[*w].0);
+            for (r, a) in kkt.iter_mut().zip(rows[*w].0.iter()) {
+                r[n + k] = a.clone()
+            }
+        }
+        // positive semidefinite Q or dependent constraints make the kkt system singular,
+        // so shift it by a tiny multiple of the identity, repeated steps remove the bias
+        let (sol, regularized) = match gauss(kkt.clone(), rhs.clone()) {
+            Some(sol) => (sol, false),
+            None => {
+                let delta = Float::with_val(options.prec, 1) >> (zero.prec() / 4);
+                for (i, r) in kkt.iter_mut().enumerate() {
+                    if i < n {
+                        r[i] += &delta
+                    } else {
+                        r[i] -= &delta
+                    }
+                }
+                (gauss(kkt, rhs).ok_or("singular kkt matrix")?, true)
+            }
+        };
+        let p = &sol[..n];
+        if p.iter().all(|p| sign(p.clone()) == Ordering::Equal) {
+            match work
+                .iter()
+                .enumerate()
+                .filter(|(k, w)| !rows[**w].2 && sign(sol[n + k].clone()) == Ordering::Less)
+                .min_by(|(a, _), (b, _)| {
+                    sol[n + a]
+                        .partial_cmp(&sol[n + b])
+                        .unwrap_or(Ordering::Equal)
+                }) {
+                Some((k, _)) => {
+                    work.remove(k);
+                }
+                None => return Ok(lp_result(Ok(x), objective, n, options)),
+            }
+        } else {
+            let mut alpha = Float::with_val(options.prec, 1);
+            let mut block = None;
+            let mut bounded = false;
+            for (i, (a, b, eq)) in rows.iter().enumerate() {
+                let ap = dot(a, p);
+                if !eq && !work.contains(&i) && sign(ap.clone()) == Ordering::Greater {
+                    bounded = true;
+                    let step = (b.clone() - dot(a, &x)) / ap;
+                    if step < alpha {
+                        alpha = step;
+                        block = Some(i)
+                    }
+                }
+            }
+            // a flat direction of Q that lowers the objective and hits no constraint
+            if regularized
+                && !bounded
+                && q.iter().all(|r| sign(dot(r, p)) == Ordering::Equal)
+                && sign(
+                    q.iter()
+                        .zip(p.iter())
+                        .fold(dot(&c, p), |s, (r, pi)| s + dot(r, &x) * pi),
+                ) == Ordering::Less
+            {
+                return Ok(lp_result(Err(2), objective, n, options));
+            }
+            for (x, p) in x.iter_mut().zip(p.iter()) {
+                *x += p.clone() * &alpha
+            }
+            if let Some(i) = block {
+                work.push(i)
+            }
         }
-        sum
-    } else {
-        Complex::with_val(
-            n.prec(),
-            euleriannumbersint::<Integer, Float, Complex>(
-                n.real()
-                    .to_integer()
-                    .unwrap_or_default()
-                    .to_u32()
-                    .unwrap_or_default(),
-                k as u32,
-            ),
-        )
     }
+    Err("qp did not converge")
 }
-pub fn euleriannumbersint<
-    Integer: crate::types::Integer<Float, Complex>,
-    Float: crate::types::Float<Integer, Complex>,
-    Complex: crate::types::Complex<Integer, Float>,
->(
-    n: u32,
-    k: u32,
-) -> Integer {
-    let mut sum = Integer::new();
-    for i in 0..=k {
-        let num: Integer = -Integer::from(i) + k + 1;
-        let num = Integer::from(n + 1).binomial(i) * num.pow(n);
-        if i % 2 == 0 { sum += num } else { sum -= num }
+pub fn mul_units(a: Option<Units>, b: Option<Units>) -> Option<Units> {
+    match (a, b) {
+        (Some(a), None) => Some(a),
+        (None, Some(b)) => Some(b),
+        (Some(a), Some(b)) => Some(a.mul(&b)),
+        _ => None,
     }
-    sum
 }
-pub fn binomial<
-    I: crate::types::Integer<F, C>,
-    F: crate::types::Float<I, C>,
-    C: crate::types::Complex<I, F>,
->(
-    a: C,
-    b: C,
-) -> C {
-    if a.imag().is_zero()
-        && b.imag().is_zero()
-        && a.real().clone().fract().is_zero()
-        && b.real().clone().fract().is_zero()
-        && a.real().is_finite()
-        && a.real() >= &0
-        && b.real() >= &0
-    {
-        C::with_val(
-            a.prec(),
-            a.real().to_integer().unwrap_or_default().binomial(
-                b.real()
-                    .to_integer()
-                    .unwrap_or_default()
-                    .to_u32()
-                    .unwrap_or_default(),
-            ),
-        )
-    } else if a.real().is_sign_negative()
-        && a.real().clone().fract().is_zero()
-        && a.imag().is_zero()
-        && b.imag().is_zero()
-    {
-        let prec = a.prec();
-        let a = a + C::with_val(prec, (0, 1)) * F::with_val(prec, 0.5).pow(prec / 2);
-        let g: C = gamma(a.clone() + 1) / (gamma(b.clone() + 1) * gamma(a.clone() - b.clone() + 1));
-        g.real().clone().into()
-    } else {
-        gamma(a.clone() + 1) / (gamma(b.clone() + 1) * gamma(a.clone() - b.clone() + 1))
+pub fn div_units(a: Option<Units>, b: Option<Units>) -> Option<Units> {
+    match (a, b) {
+        (Some(a), None) => Some(a),
+        (None, Some(b)) => Some(b.pow(-1.0)),
+        (Some(a), Some(b)) => Some(a.div(&b)),
+        _ => None,
     }
 }
-fn incomplete_gamma_recursion<
+pub fn quadratic<
     Integer: crate::types::Integer<Float, Complex>,
     Float: crate::types::Float<Integer, Complex>,
     Complex: crate::types::Complex<Integer, Float>,
 >(
-    s: Complex,
-    z: Complex,
-    iter: usize,
-    max: usize,
-) -> Complex {
-    if iter == max {
-        Complex::with_val(s.prec(), 1)
-    } else if iter == 0 {
-        (pow_nth(z.clone(), s.clone()) / z.clone().exp()) / incomplete_gamma_recursion(s, z, 1, max)
-    } else if iter % 2 == 1 {
-        z.clone()
-            + ((-s.clone() + iter.div_ceil(2)) / incomplete_gamma_recursion(s, z, iter + 1, max))
+    a: Number<Integer, Float, Complex>,
+    b: Number<Integer, Float, Complex>,
+    c: Number<Integer, Float, Complex>,
+    real: bool,
+) -> Vec<Number<Integer, Float, Complex>> {
+    if a.number.is_zero() {
+        return if b.number.is_zero() {
+            vec![Number::from(Complex::new(a.number.prec()), None)]
+        } else {
+            let units = div_units(c.units, b.units);
+            let b = b.number;
+            let c = c.number;
+            let mut r = -c / b;
+            if -r.imag().clone().abs().log10() > a.number.prec() / 4 {
+                r = r.real().clone().into();
+            }
+            if real && !r.imag().is_zero() {
+                vec![Number::from(
+                    Complex::with_val(a.number.prec(), Constant::Nan),
+                    None,
+                )]
+            } else {
+                vec![Number::from(r, units)]
+            }
+        };
+    }
+    let units = div_units(c.units, a.units).map(|a| a.root(2.0));
+    let a = a.number;
+    let b = b.number;
+    let c = c.number;
+    let p: Complex = sqr(b.clone());
+    let p: Complex = p - (c * a.clone() * 4);
+    let p = p.sqrt();
+    let a: Complex = a * 2;
+    let mut z1 = (p.clone() - b.clone()) / a.clone();
+    let mut z2 = (-p - b) / a.clone();
+    if -z1.imag().clone().abs().log10() > a.prec() / 4 {
+        z1 = z1.real().clone().into();
+    }
+    if -z2.imag().clone().abs().log10() > a.prec() / 4 {
+        z2 = z2.real().clone().into();
+    }
+    if real {
+        let mut vec = Vec::new();
+        if z1.imag().is_zero() {
+            vec.push(Number::from(z1, units))
+        }
+        if z2.imag().is_zero() {
+            vec.push(Number::from(z2, units))
+        }
+        if vec.is_empty() {
+            vec![Number::from(
+                Complex::with_val(a.prec(), Constant::Nan),
+                None,
+            )]
+        } else {
+            vec
+        }
     } else {
-        (Float::with_val(z.prec(), iter.div_ceil(2))
-            / incomplete_gamma_recursion(s, z, iter + 1, max))
-            + 1
+        vec![Number::from(z1, units), Number::from(z2, units)]
     }
 }
-fn lower_incomplete_gamma_recursion<
+pub fn cubic<
     Integer: crate::types::Integer<Float, Complex>,
     Float: crate::types::Float<Integer, Complex>,
     Complex: crate::types::Complex<Integer, Float>,
 >(
-    s: Complex,
-    z: Complex,
-    iter: usize,
-    max: usize,
-) -> Complex {
-    if iter == max {
-        Complex::with_val(s.prec(), 1)
-    } else if iter == 0 {
-        (pow_nth(z.clone(), s.clone()) / z.clone().exp())
-            / lower_incomplete_gamma_recursion(s, z, 1, max)
-    } else if iter % 2 == 1 {
-        s.clone() + iter
-            - 1
-            - ((s.clone() + iter - 1) * z.clone())
-                / lower_incomplete_gamma_recursion(s, z, iter + 1, max)
+    a: Number<Integer, Float, Complex>,
+    b: Number<Integer, Float, Complex>,
+    c: Number<Integer, Float, Complex>,
+    d: Number<Integer, Float, Complex>,
+    real: bool,
+) -> Vec<Number<Integer, Float, Complex>> {
+    let units = div_units(d.units, a.units).map(|a| a.root(3.0));
+    if a.number.is_zero() {
+        return quadratic(b, c, d, real);
+    }
+    let d = d.number;
+    if d.is_zero() {
+        let mut vec = quadratic(a, b, c, real);
+        vec.push(Number::from(Complex::new(d.prec()), vec[0].units));
+        return vec;
+    }
+    let a = a.number;
+    let b = b.number;
+    let c = c.number;
+    let prec = a.prec();
+    let threerecip = Float::with_val(prec, 3).recip();
+    if b.is_zero() && c.is_zero() {
+        let reuse = pow_nth(d / a.clone(), threerecip.clone().into());
+        let mut z1 = -reuse.clone();
+        let mut z2 = reuse.clone() * Complex::with_val(prec, -1).pow(threerecip.clone());
+        let mut z3: Complex = -reuse * Complex::with_val(prec, -1).pow(threerecip * 2);
+        if -z1.imag().clone().abs().log10() > a.prec() / 4 {
+            z1 = z1.real().clone().into();
+        }
+        if -z2.imag().clone().abs().log10() > a.prec() / 4 {
+            z2 = z2.real().clone().into();
+        }
+        if -z3.imag().clone().abs().log10() > a.prec() / 4 {
+            z3 = z3.real().clone().into();
+        }
+        return if real {
+            let mut vec = Vec::new();
+            if z1.imag().is_zero() {
+                vec.push(Number::from(z1, units))
+            }
+            if z2.imag().is_zero() {
+                vec.push(Number::from(z2, units))
+            }
+            if z3.imag().is_zero() {
+                vec.push(Number::from(z3, units))
+            }
+            if vec.is_empty() {
+                vec![Number::from(Complex::with_val(prec, Constant::Nan), None)]
+            } else {
+                vec
+            }
+        } else {
+            vec![
+                Number::from(z1, units),
+                Number::from(z2, units),
+                Number::from(z3, units),
+            ]
+        };
+    }
+    let b = b / a.clone();
+    let c = c / a.clone();
+    let d = d / a.clone();
+    // https://en.wikipedia.org/wiki/Cubic_equation#General_cubic_formula
+    let d0: Complex = sqr(b.clone()) - c.clone() * 3;
+    let d1: Complex = cube(b.clone()) * 2 - b.clone() * c.clone() * 9 + d.clone() * 27;
+    let c: Complex = sqr(d1.clone()) - cube(d0.clone()) * 4;
+    let c: Complex = (d1 + c.sqrt()) / 2;
+    let c = pow_nth(c, threerecip.clone().into());
+    let omega: Complex = (Complex::with_val(prec, 3).sqrt().mul_i(false) - 1) / 2;
+    let mut z1: Complex = if d0.is_zero() {
+        -(b.clone() + c.clone()) / 3
     } else {
-        s.clone() + iter - 1
-            + (z.clone() / lower_incomplete_gamma_recursion(s, z, iter + 1, max) * iter / 2)
+        -(b.clone() + c.clone() + d0.clone() / c.clone()) / 3
+    };
+    let c0 = c.clone() * omega.clone();
+    let mut z2: Complex = if d0.is_zero() {
+        -(b.clone() + c0.clone()) / 3
+    } else {
+        -(b.clone() + c0.clone() + d0.clone() / c0) / 3
+    };
+    let c1 = c * omega.conj();
+    let mut z3: Complex = if d0.is_zero() {
+        -(b + c1.clone()) / 3
+    } else {
+        -(b + c1.clone() + d0 / c1) / 3
+    };
+    if -z1.imag().clone().abs().log10() > a.prec() / 4 {
+        z1 = z1.real().clone().into();
     }
-}
-pub fn subfactorial<
-    Integer: crate::types::Integer<Float, Complex>,
-    Float: crate::types::Float<Integer, Complex>,
-    Complex: crate::types::Complex<Integer, Float>,
->(
-    z: Complex,
-) -> Complex {
-    subfactorial_recursion(z.clone(), 0, (z.prec() as usize / 4).max(32))
-        + gamma(z.clone() + 1) / Float::with_val(z.prec(), 1).exp()
-}
-fn subfactorial_recursion<
-    Integer: crate::types::Integer<Float, Complex>,
-    Float: crate::types::Float<Integer, Complex>,
-    Complex: crate::types::Complex<Integer, Float>,
->(
-    z: Complex,
-    iter: usize,
-    max: usize,
-) -> Complex {
-    if iter == max {
-        Complex::with_val(z.prec(), 1)
-    } else if iter == 0 {
-        Complex::with_val(z.prec(), -1).pow(z.clone()) / subfactorial_recursion(z, 1, max)
+    if -z2.imag().clone().abs().log10() > a.prec() / 4 {
+        z2 = z2.real().clone().into();
+    }
+    if -z3.imag().clone().abs().log10() > a.prec() / 4 {
+        z3 = z3.real().clone().into();
+    }
+    if real {
+        let mut vec = Vec::new();
+        if z1.imag().is_zero() {
+            vec.push(Number::from(z1, units))
+        }
+        if z2.imag().is_zero() {
+            vec.push(Number::from(z2, units))
+        }
+        if z3.imag().is_zero() {
+            vec.push(Number::from(z3, units))
+        }
+        if vec.is_empty() {
+            vec![Number::from(Complex::with_val(prec, Constant::Nan), None)]
+        } else {
+            vec
+        }
     } else {
-        (z.clone() + iter + 1)
-            - Float::with_val(z.prec(), iter) / subfactorial_recursion(z, iter + 1, max)
+        vec![
+            Number::from(z1, units),
+            Number::from(z2, units),
+            Number::from(z3, units),
+        ]
     }
 }
-#[allow(clippy::too_many_arguments)]
-#[allow(clippy::type_complexity)]
-pub fn surface_area<
+pub fn quartic<
     Integer: crate::types::Integer<Float, Complex>,
     Float: crate::types::Float<Integer, Complex>,
     Complex: crate::types::Complex<Integer, Float>,
 >(
-    func: Vec<NumStr<Integer, Float, Complex>>,
-    func_vars: Vec<(String, Vec<NumStr<Integer, Float, Complex>>)>,
-    options: Options,
-    varx: String,
-    mut startx: Complex,
-    endx: Number<Integer, Float, Complex>,
-    vary: String,
-    starty_func: Vec<NumStr<Integer, Float, Complex>>,
-    endy_func: Vec<NumStr<Integer, Float, Complex>>,
-) -> Result<Number<Integer, Float, Complex>, &'static str> {
-    if starty_func.is_empty() || endy_func.is_empty() {
-        return Err("bad start/end");
-    }
-    let points = options.prec as usize / 16;
-    let unitsx = endx.units;
-    let endx = endx.number;
-    let deltax: Complex = (endx.clone() - startx.clone()) / (points - 1);
-    let mut area: Complex = Complex::new(options.prec);
-    if let (Ok(Num(start)), Ok(Num(end))) = (
+    div: Number<Integer, Float, Complex>,
+    b: Number<Integer, Float, Complex>,
+    c: Number<Integer, Float, Complex>,
+    d: Number<Integer, Float, Complex>,
+    e: Number<Integer, Float, Complex>,
+    real: bool,
+) -> Vec<Number<Integer, Float, Complex>> {
+    let units = div_units(e.units, div.units).map(|a| a.root(4.0));
+    if e.number.is_zero() {
+        let mut vec = cubic(div, b, c, d, real);
+        vec.push(Number::from(Complex::new(e.number.prec()), vec[0].units));
+        return vec;
+    }
+    let div = div.number;
+    if div.is_zero() {
+        return cubic(b, c, d, e, real);
+    }
+    let b = b.number;
+    let c = c.number;
+    let d = d.number;
+    let e = e.number;
+    let prec = div.prec();
+    let threerecip = Float::with_val(prec, 3).recip();
+    let a = b / div.clone();
+    let b = c / div.clone();
+    let c = d / div.clone();
+    let d = e / div;
+    if a.is_zero() && b.is_zero() && c.is_zero() {
+        return if d.is_zero() {
+            vec![Number::from(Complex::new(prec), None)]
+        } else {
+            let a = Complex::with_val(prec, 1) / 4;
+            let mut a: Complex = (-d).pow(a);
+            let mut v = vec![a.clone()];
+            for _ in 0..3 {
+                a = a.mul_i(false);
+                v.push(a.clone());
+            }
+            v.into_iter().map(|a| Number::from(a, None)).collect()
+        };
+    }
+    // https://upload.wikimedia.org/wikipedia/commons/9/99/Quartic_Formula.svg
+    let alpha: Complex = sqr(b.clone()) - a.clone() * c.clone() * 3 + d.clone() * 12;
+    let phi: Complex = cube(b.clone()) * 2 - a.clone() * b.clone() * c.clone() * 9
+        + sqr(c.clone()) * 27
+        + sqr(a.clone()) * d.clone() * 27
+        - b.clone() * d.clone() * 72;
+    let omega: Complex = cube(alpha.clone()) * -4 + sqr(phi.clone());
+    let omega: Complex = phi + omega.sqrt();
+    let alpha: Complex = if alpha.is_zero() {
+        Complex::new(prec)
+    } else {
+        Float::with_val(prec, 2).pow(threerecip.clone()) * alpha
+            / (pow_nth(omega.clone(), threerecip.clone().into()) * 3)
+    };
+    let beta: Complex = omega / 54;
+    let beta: Complex = pow_nth(beta, threerecip.clone().into());
+    let infirst: Complex = sqr(a.clone()) / 4 - b.clone() / 3 * 2 + alpha.clone() + beta.clone();
+    let first: Complex = infirst.clone().sqrt() / 2;
+    let third: Complex = -cube(a.clone()) + a.clone() * b.clone() * 4 - c.clone() * 8;
+    let third: Complex = if third.is_zero() {
+        Complex::new(prec)
+    } else {
+        third / (first.clone() * 8)
+    };
+    let a4: Complex = -a.clone() / 4;
+    let second: Complex = sqr(a.clone()) / 2 - b.clone() / 3 * 4 - alpha.clone() - beta.clone();
+    let secondn: Complex = second.clone() - third.clone();
+    let secondn: Complex = secondn.sqrt() / 2;
+    let secondp: Complex = second + third.clone();
+    let secondp: Complex = secondp.sqrt() / 2;
+    let mut r1 = a4.clone() - first.clone() - secondn.clone();
+    let mut r2 = a4.clone() - first.clone() + secondn.clone();
+    let mut r3 = a4.clone() + first.clone() - secondp.clone();
+    let mut r4 = a4.clone() + first.clone() + secondp.clone();
+    if -r1.imag().clone().abs().log10() > a.prec() / 8 {
+        r1 = r1.real().clone().into();
+    }
+    if -r2.imag().clone().abs().log10() > a.prec() / 8 {
+        r2 = r2.real().clone().into();
+    }
+    if -r3.imag().clone().abs().log10() > a.prec() / 8 {
+        r3 = r3.real().clone().into();
+    }
+    if -r4.imag().clone().abs().log10() > a.prec() / 8 {
+        r4 = r4.real().clone().into();
+    }
+    if real {
+        let mut vec = Vec::new();
+        if r1.imag().is_zero() {
+            vec.push(Number::from(r1, units))
+        }
+        if r2.imag().is_zero() {
+            vec.push(Number::from(r2, units))
+        }
+        if r3.imag().is_zero() {
+            vec.push(Number::from(r3, units))
+        }
+        if r4.imag().is_zero() {
+            vec.push(Number::from(r4, units))
+        }
+        if vec.is_empty() {
+            vec![Number::from(Complex::with_val(prec, Constant::Nan), None)]
+        } else {
+            vec
+        }
+    } else {
+        vec![
+            Number::from(r1, units),
+            Number::from(r2, units),
+            Number::from(r3, units),
+            Number::from(r4, units),
+        ]
+    }
+}
+pub fn variance<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    a: &[Number<Integer, Float, Complex>],
+    mean: Option<Complex>,
+    prec: u32,
+) -> Number<Integer, Float, Complex> {
+    let mean = if let Some(n) = mean {
+        n
+    } else {
+        a.iter()
+            .fold(Complex::new(prec), |sum, val| sum + val.number.clone())
+            / a.len()
+    };
+    let mut variance = Complex::new(prec);
+    for a in a {
+        variance += sqr(a.number.clone() - mean.clone())
+    }
+    Number::from(
+        variance / (a.len().saturating_sub(1)),
+        a[0].units.map(|a| a.pow(2.0)),
+    )
+}
+// average ranks, ties share the mean of the ranks they span
+fn ranks<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    a: &[Float],
+) -> Vec<Float> {
+    let mut idx = (0..a.len()).collect::<Vec<usize>>();
+    idx.sort_by(|x, y| a[*x].partial_cmp(&a[*y]).unwrap_or(Ordering::Equal));
+    let mut r = vec![Float::new(a[0].prec()); a.len()];
+    let mut i = 0;
+    while i < idx.len() {
+        let mut j = i;
+        while j + 1 < idx.len() && a[idx[j + 1]] == a[idx[i]] {
+            j += 1
+        }
+        let rank: Float = Float::with_val(a[0].prec(), i + j + 2) / 2;
+        for k in &idx[i..=j] {
+            r[*k] = rank.clone()
+        }
+        i = j + 1
+    }
+    r
+}
+fn pearson<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    a: &[Float],
+    b: &[Float],
+) -> Float {
+    let prec = a[0].prec();
+    let ma: Float = a.iter().fold(Float::new(prec), |s, x| s + x) / a.len();
+    let mb: Float = b.iter().fold(Float::new(prec), |s, x| s + x) / b.len();
+    let mut sab = Float::new(prec);
+    let mut saa = Float::new(prec);
+    let mut sbb = Float::new(prec);
+    for (a, b) in a.iter().zip(b.iter()) {
+        let da: Float = a.clone() - &ma;
+        let db: Float = b.clone() - &mb;
+        sab += da.clone() * db.clone();
+        saa += da.clone() * da;
+        sbb += db.clone() * db;
+    }
+    let d: Float = saa * sbb;
+    sab / d.sqrt()
+}
+// kendall's tau-b, which accounts for ties in either variable
+fn kendall<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    a: &[Float],
+    b: &[Float],
+) -> Float {
+    let (mut s, mut tx, mut ty) = (0i128, 0i128, 0i128);
+    for i in 0..a.len() {
+        for j in i + 1..a.len() {
+            let x = a[i].partial_cmp(&a[j]).unwrap_or(Ordering::Equal);
+            let y = b[i].partial_cmp(&b[j]).unwrap_or(Ordering::Equal);
+            match (x, y) {
+                (Ordering::Equal, Ordering::Equal) => {
+                    tx += 1;
+                    ty += 1
+                }
+                (Ordering::Equal, _) => tx += 1,
+                (_, Ordering::Equal) => ty += 1,
+                _ if x == y => s += 1,
+                _ => s -= 1,
+            }
+        }
+    }
+    let n = (a.len() * (a.len() - 1) / 2) as i128;
+    let d: Float = Float::with_val(a[0].prec(), n - tx) * (n - ty);
+    Float::with_val(a[0].prec(), s) / d.sqrt()
+}
+// cov, corr (pearson), spearman or kendall between two samples
+pub fn correlation<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    kind: &str,
+    a: &[Number<Integer, Float, Complex>],
+    b: &[Number<Integer, Float, Complex>],
+    prec: u32,
+) -> Result<Number<Integer, Float, Complex>, &'static str> {
+    if a.len() != b.len() {
+        return Err("different sized data sets");
+    }
+    if a.len() < 2 {
+        return Err("not enough data");
+    }
+    if matches!(kind, "cov" | "covariance") {
+        let ma = a
+            .iter()
+            .fold(Complex::new(prec), |sum, val| sum + val.number.clone())
+            / a.len();
+        let mb = b
+            .iter()
+            .fold(Complex::new(prec), |sum, val| sum + val.number.clone())
+            / b.len();
+        let mut sum = Complex::new(prec);
+        for (a, b) in a.iter().zip(b.iter()) {
+            sum += (a.number.clone() - ma.clone()) * (b.number.clone() - mb.clone());
+        }
+        return Ok(Number::from(
+            sum / (a.len() - 1),
+            mul_units(a[0].units, b[0].units),
+        ));
+    }
+    let x = a
+        .iter()
+        .map(|a| a.number.real().clone())
+        .collect::<Vec<Float>>();
+    let y = b
+        .iter()
+        .map(|b| b.number.real().clone())
+        .collect::<Vec<Float>>();
+    Ok(Number::from(
+        match kind {
+            "corr" => pearson(&x, &y),
+            "spearman" => pearson(&ranks(&x), &ranks(&y)),
+            "kendall" => kendall(&x, &y),
+            _ => return Err("unknown correlation"),
+        }
+        .into(),
+        None,
+    ))
+}
+// pairwise correlation matrix between the columns of a
+#[allow(clippy::type_complexity)]
+pub fn correlation_matrix<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    kind: &str,
+    a: &[Vec<Number<Integer, Float, Complex>>],
+    prec: u32,
+) -> Result<Vec<Vec<Number<Integer, Float, Complex>>>, &'static str> {
+    let cols = transpose(a);
+    let mut mat: Vec<Vec<Number<Integer, Float, Complex>>> =
+        vec![Vec::with_capacity(cols.len()); cols.len()];
+    for i in 0..cols.len() {
+        for j in 0..cols.len() {
+            let c = if j < i {
+                mat[j][i].clone()
+            } else {
+                correlation(kind, &cols[i], &cols[j], prec)?
+            };
+            mat[i].push(c)
+        }
+    }
+    Ok(mat)
+}
+pub fn zscore<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    a: &[Number<Integer, Float, Complex>],
+    prec: u32,
+) -> Vec<Number<Integer, Float, Complex>> {
+    let mean = a
+        .iter()
+        .fold(Complex::new(prec), |sum, val| sum + val.number.clone())
+        / a.len();
+    let sd = variance(a, Some(mean.clone()), prec).number.sqrt();
+    a.iter()
+        .map(|a| Number::from((a.number.clone() - mean.clone()) / sd.clone(), None))
+        .collect()
+}
+fn sorted_median<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    a: &[Number<Integer, Float, Complex>],
+) -> Complex {
+    if a.len() % 2 == 0 {
+        (a[a.len() / 2 - 1].number.clone() + a[a.len() / 2].number.clone()) / 2
+    } else {
+        a[a.len() / 2].number.clone()
+    }
+}
+// {count, mean, sd, min, q1, median, q3, max}
+pub fn describe<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    a: Vec<Number<Integer, Float, Complex>>,
+    prec: u32,
+) -> Result<Vec<Number<Integer, Float, Complex>>, &'static str> {
+    if a.len() < 2 {
+        return Err("not enough data");
+    }
+    let units = a[0].units;
+    let mean = a
+        .iter()
+        .fold(Complex::new(prec), |sum, val| sum + val.number.clone())
+        / a.len();
+    let sd = variance(&a, Some(mean.clone()), prec).number.sqrt();
+    let a = sort(a);
+    let mut out = vec![
+        Number::from(Complex::with_val(prec, a.len()), None),
+        Number::from(mean, units),
+        Number::from(sd, units),
+        a[0].clone(),
+    ];
+    out.extend(quartiles(a.clone())?);
+    out.push(a[a.len() - 1].clone());
+    Ok(out)
+}
+// {q1, median, q3}, the quartiles being the medians of the halves below and above the median
+pub fn quartiles<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    a: Vec<Number<Integer, Float, Complex>>,
+) -> Result<Vec<Number<Integer, Float, Complex>>, &'static str> {
+    if a.len() < 2 {
+        return Err("not enough data");
+    }
+    let units = a[0].units;
+    let a = sort(a);
+    let half1 = &a[0..a.len() / 2];
+    let half2 = &a[a.len().div_ceil(2)..a.len()];
+    Ok(vec![
+        Number::from(sorted_median(half1), units),
+        Number::from(sorted_median(&a), units),
+        Number::from(sorted_median(half2), units),
+    ])
+}
+// interquartile range, q3 - q1 from quartiles
+fn iqr<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    a: &[Float],
+) -> Float {
+    match quartiles::<Integer, Float, Complex>(
+        a.iter()
+            .map(|a| Number::from(a.clone().into(), None))
+            .collect(),
+    ) {
+        Ok(q) => q[2].number.real().clone() - q[0].number.real(),
+        Err(_) => Float::new(a.first().map(|a| a.prec()).unwrap_or(64)),
+    }
+}
+// number of bins from the sturges, freedman–diaconis or scott rule
+pub fn bin_count<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    a: &[Float],
+    rule: &str,
+) -> Result<usize, &'static str> {
+    if a.is_empty() {
+        return Err("not enough data");
+    }
+    let prec = a[0].prec();
+    let n = Float::with_val(prec, a.len());
+    let cbrt: Float = n.clone().ln() / 3;
+    let cbrt = cbrt.exp();
+    let width: Float = match rule {
+        "sturges" => {
+            let b: Float = n.log2().ceil() + 1;
+            return Ok(b.to_integer().unwrap_or_default().to_usize().unwrap_or(1));
+        }
+        "fd" => {
+            let iqr: Float = iqr(a) * 2;
+            iqr / cbrt
+        }
+        "scott" => {
+            let sd = mean_var(a).1.sqrt();
+            sd * Float::with_val(prec, 3.49) / cbrt
+        }
+        _ => return Err("unknown binning rule"),
+    };
+    let (min, max) = a
+        .iter()
+        .fold((a[0].clone(), a[0].clone()), |(min, max), x| {
+            (
+                if *x < min { x.clone() } else { min },
+                if *x > max { x.clone() } else { max },
+            )
+        });
+    if width.is_zero() || !width.is_finite() {
+        return Ok(1);
+    }
+    let b: Float = ((max - min) / width).ceil();
+    Ok(b.to_integer()
+        .unwrap_or_default()
+        .to_usize()
+        .unwrap_or(1)
+        .max(1))
+}
+// bins evenly spaced over the range of the data
+pub fn bin_edges<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    a: &[Float],
+    bins: usize,
+) -> Result<Vec<Float>, &'static str> {
+    if a.is_empty() || bins == 0 {
+        return Err("not enough data");
+    }
+    let (mut min, mut max) = a
+        .iter()
+        .fold((a[0].clone(), a[0].clone()), |(min, max), x| {
+            (
+                if *x < min { x.clone() } else { min },
+                if *x > max { x.clone() } else { max },
+            )
+        });
+    if min == max {
+        min -= 0.5;
+        max += 0.5;
+    }
+    let width: Float = (max - min.clone()) / bins;
+    Ok((0..=bins)
+        .map(|k| min.clone() + width.clone() * k)
+        .collect())
+}
+// silverman's rule of thumb bandwidth for a gaussian kernel, data without any spread has no
+// scale to derive one from so it needs an explicit bandwidth
+pub fn silverman<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    a: &[Float],
+) -> Result<Float, &'static str> {
+    if a.len() < 2 || a.iter().all(|x| x == &a[0]) {
+        return Err("constant data needs a bandwidth");
+    }
+    let prec = a[0].prec();
+    let sd = mean_var(a).1.sqrt();
+    let spread: Float = iqr(a) / Float::with_val(prec, 1.34);
+    let spread = if spread.is_zero() || sd < spread {
+        sd
+    } else {
+        spread
+    };
+    let n: Float = Float::with_val(prec, a.len()).ln() / 5;
+    Ok(spread * Float::with_val(prec, 0.9) / n.exp())
+}
+// gaussian kernel density estimate at x
+pub fn kde<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    a: &[Float],
+    x: &Float,
+    h: &Float,
+) -> Float {
+    let prec = a[0].prec();
+    let sum = a.iter().fold(Float::new(prec), |sum, xi| {
+        let u: Float = (x.clone() - xi) / h.clone();
+        let u: Float = -(u.clone() * u) / 2;
+        sum + u.exp()
+    });
+    let tau: Float = Float::with_val(prec, Constant::Pi) * 2;
+    sum / (tau.sqrt() * h.clone() * a.len())
+}
+// fraction of the sample at or below x
+pub fn ecdf<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    a: &[Float],
+    x: &Float,
+) -> Float {
+    Float::with_val(a[0].prec(), a.iter().filter(|a| *a <= x).count()) / a.len()
+}
+// rows of {left edge, right edge, count}, the last bin includes its right edge
+#[allow(clippy::type_complexity)]
+pub fn histogram<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    a: &[Number<Integer, Float, Complex>],
+    edges: &[Float],
+) -> Result<Vec<Vec<Number<Integer, Float, Complex>>>, &'static str> {
+    if edges.len() < 2 {
+        return Err("need at least 2 edges");
+    }
+    if edges.windows(2).any(|e| e[0] >= e[1]) {
+        return Err("edges must be increasing");
+    }
+    let mut counts = vec![0usize; edges.len() - 1];
+    for x in a {
+        let x = x.number.real();
+        if x < &edges[0] || x > &edges[edges.len() - 1] {
+            continue;
+        }
+        let k = edges.partition_point(|e| e <= x);
+        let last = counts.len() - 1;
+        counts[k.saturating_sub(1).min(last)] += 1
+    }
+    let units = a.first().and_then(|a| a.units);
+    Ok(counts
+        .into_iter()
+        .enumerate()
+        .map(|(k, c)| {
+            vec![
+                Number::from(edges[k].clone().into(), units),
+                Number::from(edges[k + 1].clone().into(), units),
+                Number::from(Complex::with_val(edges[0].prec(), c), None),
+            ]
+        })
+        .collect())
+}
+#[allow(clippy::type_complexity)]
+pub fn recursion<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    mut func_vars: Vec<(String, Vec<NumStr<Integer, Float, Complex>>)>,
+    mut func: Vec<NumStr<Integer, Float, Complex>>,
+    options: Options,
+) -> Result<NumStr<Integer, Float, Complex>, &'static str> {
+    for fv in func_vars.clone() {
+        if fv.0.ends_with(')') {
+            let mut cont = false;
+            let mut bracket = 0;
+            let mut pw = Vec::new();
+            for f in &func {
+                match f {
+                    LeftBracket | LeftCurlyBracket => bracket += 1,
+                    RightBracket | RightCurlyBracket => {
+                        bracket -= 1;
+                        if !pw.is_empty() && bracket == pw[0] {
+                            pw.remove(0);
+                        }
+                    }
+                    Func(s) if s == "pw" => pw.insert(0, bracket),
+                    _ => {
+                        if !pw.is_empty() && f.str_is(&fv.0) {
+                            cont = true
+                        }
+                    }
+                }
+            }
+            if cont {
+                continue;
+            }
+            if fv.0.contains(',') {
+                let mut vars = fv.0.split(',').collect::<Vec<&str>>();
+                vars[0] = vars[0].split('(').next_back().unwrap();
+                {
+                    let vl = vars.len().saturating_sub(1);
+                    vars[vl] = &vars[vl][0..vars[vl].len().saturating_sub(1)];
+                }
+                let mut x = func.len();
+                while x > 0 {
+                    x -= 1;
+                    if func[x].str_is(&fv.0) {
+                        let mut fv = fv.clone();
+                        let mut bracket = 0;
+                        let mut k = 0;
+                        let mut processed = Vec::new();
+                        let mut last = 0;
+                        for (i, n) in func[x + 2..].iter().enumerate() {
+                            match n {
+                                LeftBracket | LeftCurlyBracket => bracket += 1,
+                                RightBracket | RightCurlyBracket => {
+                                    if bracket == 0 {
+                                        if let Ok(n) = do_math(
+                                            func[x + 2 + last..x + 2 + i].to_vec(),
+                                            options,
+                                            func_vars.clone(),
+                                        ) {
+                                            processed.push(vec![n]);
+                                        } else {
+                                            let iden = format!(
+                                                "@{}{}@",
+                                                func_vars.len(),
+                                                vars[processed.len()]
+                                            );
+                                            func_vars.push((
+                                                iden.clone(),
+                                                func[x + 2 + last..x + 2 + i].to_vec(),
+                                            ));
+                                            processed.push(vec![Func(iden)]);
+                                        }
+                                        k = i;
+                                        break;
+                                    }
+                                    bracket -= 1;
+                                }
+                                Comma if bracket == 0 => {
+                                    if let Ok(n) = do_math(
+                                        func[x + 2 + last..x + 2 + i].to_vec(),
+                                        options,
+                                        func_vars.clone(),
+                                    ) {
+                                        processed.push(vec![n]);
+                                    } else {
+                                        let iden = format!(
+                                            "@{}{}@",
+                                            func_vars.len(),
+                                            vars[processed.len()]
+                                        );
+                                        func_vars.push((
+                                            iden.clone(),
+                                            func[x + 2 + last..x + 2 + i].to_vec(),
+                                        ));
+                                        processed.push(vec![Func(iden)]);
+                                    }
+                                    last = i + 1;
+                                }
+                                _ => {}
+                            }
+                        }
+                        let mut i = 0;
+                        while i < fv.1.len() {
+                            if let Func(s) = &fv.1[i] {
+                                for v in processed.iter().zip(vars.iter()) {
+                                    if s == v.1 {
+                                        fv.1.remove(i);
+                                        fv.1.splice(i..i, v.0.clone());
+                                        break;
+                                    }
+                                }
+                            }
+                            i += 1;
+                        }
+                        func.drain(x..=k + x + 2);
+                        func.splice(x..x, fv.1.clone());
+                    }
+                }
+            } else {
+                let var = fv.0.split('(').next_back().unwrap();
+                let var = &var[0..var.len().saturating_sub(1)];
+                let mut x = func.len();
+                while x > 0 {
+                    x -= 1;
+                    if func[x].str_is(&fv.0) {
+                        let mut fv = fv.clone();
+                        let mut bracket = 0;
+                        let mut k = 0;
+                        for (i, n) in func[x + 2..].iter().enumerate() {
+                            match n {
+                                LeftBracket | LeftCurlyBracket => bracket += 1,
+                                RightBracket | RightCurlyBracket => {
+                                    if bracket == 0 {
+                                        k = i;
+                                    }
+                                    bracket -= 1;
+                                }
+                                _ => {}
+                            }
+                        }
+                        let iden = format!("@{}{}@", func_vars.len(), var);
+                        let mut i = 0;
+                        while i < fv.1.len() {
+                            if let Func(s) = &fv.1[i] {
+                                if *s == var {
+                                    fv.1[i] = Func(iden.clone());
+                                }
+                            }
+                            i += 1;
+                        }
+                        func_vars.push((iden.clone(), func[x + 1..=k + x + 2].to_vec()));
+                        func.drain(x..=k + x + 2);
+                        func.splice(x..x, fv.1.clone());
+                    }
+                }
+            }
+        }
+    }
+    do_math(func, options, func_vars)
+}
+pub fn gcd<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    mut x: Integer,
+    mut y: Integer,
+) -> Integer {
+    while x != y {
+        if x > y {
+            x -= y.clone()
+        } else {
+            y -= x.clone()
+        }
+    }
+    x
+}
+pub fn incomplete_beta<
+    I: crate::types::Integer<F, C>,
+    F: crate::types::Float<I, C>,
+    C: crate::types::Complex<I, F>,
+>(
+    x: C,
+    a: C,
+    b: C,
+) -> C {
+    if x.real() > &((a.real().clone() + 1) / (a.real().clone() + b.real() + 2)) {
+        (gamma(a.clone()) * gamma(b.clone()) / gamma(a.clone() + b.clone()))
+            - incomplete_beta(-x + 1, b, a)
+    } else {
+        let f: C = -x.clone() + 1;
+        pow_nth(x.clone(), a.clone()) * pow_nth(f, b.clone())
+            / (a.clone() * (incomplete_beta_recursion(x, a, b, 1, 10) + 1))
+    }
+}
+fn incomplete_beta_recursion<
+    I: crate::types::Integer<F, C>,
+    F: crate::types::Float<I, C>,
+    C: crate::types::Complex<I, F>,
+>(
+    x: C,
+    a: C,
+    b: C,
+    iter: usize,
+    max: usize,
+) -> C {
+    if iter == max {
+        C::new(x.prec())
+    } else if iter % 2 == 1 {
+        let m = (iter - 1) / 2;
+        (-x.clone() * (a.clone() + m) * (a.clone() + b.clone() + m)
+            / ((a.clone() + (2 * m)) * (a.clone() + (2 * m) + 1)))
+            / (incomplete_beta_recursion(x, a, b, iter + 1, max) + 1)
+    } else {
+        let m = iter / 2;
+        (x.clone() * m * (b.clone() - m) / ((a.clone() + (2 * m)) * (a.clone() + (2 * m) - 1)))
+            / (incomplete_beta_recursion(x, a, b, iter + 1, max) + 1)
+    }
+}
+pub fn erf<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    z: Complex,
+) -> Complex {
+    -erfc(z) + 1
+}
+pub fn erfc<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    z: Complex,
+) -> Complex {
+    let p2: Complex = sqr(z.clone());
+    erfc_recursion(z.clone(), 0, z.prec() as usize / 4)
+        / Complex::with_val(z.prec(), Constant::Pi).sqrt()
+        * z
+        / p2.exp()
+}
+fn erfc_recursion<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    z: Complex,
+    iter: usize,
+    max: usize,
+) -> Complex {
+    if iter == max {
+        Complex::with_val(z.prec(), 1)
+    } else if iter == 0 {
+        erfc_recursion(z, 1, max).recip()
+    } else if iter % 2 == 1 {
+        sqr(z.clone()) + Float::with_val(z.prec(), iter) / (erfc_recursion(z, iter + 1, max) * 2)
+    } else {
+        Float::with_val(z.prec(), iter) / (erfc_recursion(z, iter + 1, max) * 2) + 1
+    }
+}
+fn gamma0<
+    I: crate::types::Integer<F, C>,
+    F: crate::types::Float<I, C>,
+    C: crate::types::Complex<I, F>,
+>(
+    z: C,
+) -> C {
+    let p = z.prec() as usize / 4;
+    gamma0_recursion_first(z.clone(), 0, p) + gamma0_recursion_second(z, 0, p)
+}
+fn gamma0_recursion_first<
+    I: crate::types::Integer<F, C>,
+    F: crate::types::Float<I, C>,
+    C: crate::types::Complex<I, F>,
+>(
+    z: C,
+    iter: usize,
+    max: usize,
+) -> C {
+    if iter == max {
+        C::with_val(z.prec(), 1)
+    } else if iter == 0 {
+        F::with_val(z.prec(), -1).exp() / gamma0_recursion_first(z, 1, max)
+    } else {
+        -z.clone() + 2 * iter + (z.clone() - iter) * iter / gamma0_recursion_first(z, iter + 1, max)
+    }
+}
+fn gamma0_recursion_second<
+    I: crate::types::Integer<F, C>,
+    F: crate::types::Float<I, C>,
+    C: crate::types::Complex<I, F>,
+>(
+    z: C,
+    iter: usize,
+    max: usize,
+) -> C {
+    if iter == max {
+        C::with_val(z.prec(), 1)
+    } else if iter == 0 {
+        F::with_val(z.prec(), -1).exp() / gamma0_recursion_second(z, 1, max)
+    } else if iter % 2 == 1 {
+        z.clone() + (iter - 1) - (z.clone() + iter / 2) / gamma0_recursion_second(z, iter + 1, max)
+    } else {
+        z.clone()
+            + (iter - 1)
+            + C::with_val(z.prec(), iter / 2) / gamma0_recursion_second(z, iter + 1, max)
+    }
+}
+pub fn incomplete_gamma<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    s: Complex,
+    z: Complex,
+) -> Complex {
+    if z.is_zero() {
+        gamma(s)
+    } else if s.real().is_sign_positive()
+        && !s.real().clone().fract().is_zero()
+        && *z.real() <= 0.25
+    {
+        let p = z.prec() as usize / 4;
+        gamma(s.clone()) - lower_incomplete_gamma_recursion(s, z, 0, p)
+    } else {
+        let p = z.prec() as usize / 4;
+        incomplete_gamma_recursion(s, z, 0, p)
+    }
+}
+pub fn lower_incomplete_gamma<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    s: Complex,
+    z: Complex,
+) -> Complex {
+    if s.real().is_sign_positive() && !s.real().clone().fract().is_zero() && *z.real() <= 1 {
+        let p = z.prec() as usize / 4;
+        lower_incomplete_gamma_recursion(s, z, 0, p)
+    } else {
+        gamma(s.clone()) - incomplete_gamma(s, z)
+    }
+}
+pub fn eta<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    s: Complex,
+) -> Complex {
+    let prec = s.prec();
+    let mut sum = Complex::new(prec);
+    let two = Float::with_val(prec, 2);
+    for n in 0..=(prec / 16).max(16) {
+        let mut innersum = Complex::new(prec);
+        let nb = Integer::from(n);
+        for k in 0..=n {
+            let num = nb.clone().binomial(k) * pow_nth(Complex::with_val(prec, 1 + k), -s.clone());
+            if k % 2 == 0 {
+                innersum += num;
+            } else {
+                innersum -= num;
+            }
+        }
+        sum += innersum / two.clone().pow(n + 1)
+    }
+    sum
+}
+pub fn zeta<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    s: Complex,
+) -> Complex {
+    eta(s.clone()) / (-pow_nth(Complex::with_val(s.prec(), 2), -s + 1) + 1)
+}
+pub fn euleriannumbers<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    n: Complex,
+    k: i32,
+) -> Complex {
+    if k < 0 {
+        Complex::with_val(n.prec(), Constant::Nan)
+    } else if n.real().clone().fract() != 0 && n.imag().is_zero() && n.real().is_sign_positive() {
+        let mut sum = Complex::new(n.prec());
+        for i in 0..=k {
+            let ic = Complex::with_val(n.prec(), i);
+            let num: Complex = -ic.clone() + k + 1;
+            let num = binomial(n.clone() + 1, ic) * pow_nth(num, n.clone());
+            if i % 2 == 0 { sum += num } else { sum -= num }
+        }
+        sum
+    } else {
+        Complex::with_val(
+            n.prec(),
+            euleriannumbersint::<Integer, Float, Complex>(
+                n.real()
+                    .to_integer()
+                    .unwrap_or_default()
+                    .to_u32()
+                    .unwrap_or_default(),
+                k as u32,
+            ),
+        )
+    }
+}
+pub fn euleriannumbersint<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    n: u32,
+    k: u32,
+) -> Integer {
+    let mut sum = Integer::new();
+    for i in 0..=k {
+        let num: Integer = -Integer::from(i) + k + 1;
+        let num = Integer::from(n + 1).binomial(i) * num.pow(n);
+        if i % 2 == 0 { sum += num } else { sum -= num }
+    }
+    sum
+}
+// lo (lo + step) ... hi, split in halves so the big multiplications stay balanced
+pub fn range_product<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    lo: u64,
+    hi: u64,
+    step: u64,
+) -> Integer {
+    if lo > hi {
+        return Integer::from(1);
+    }
+    let n = (hi - lo) / step;
+    if n < 16 {
+        let mut r = Integer::from(1);
+        for i in 0..=n {
+            r *= lo + i * step
+        }
+        r
+    } else {
+        let mid = lo + n / 2 * step;
+        range_product::<Integer, Float, Complex>(lo, mid, step)
+            * range_product::<Integer, Float, Complex>(mid + step, hi, step)
+    }
+}
+// (F(n), F(n+1)) by fast doubling over the bits of n
+pub fn fibonacci<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    n: &Integer,
+) -> (Integer, Integer) {
+    let neg = n.cmp0() == Ordering::Less;
+    let mut a = Integer::new();
+    let mut b = Integer::from(1);
+    for bit in n.clone().abs().to_string_radix(2).chars() {
+        let c: Integer = a.clone() * (b.clone() * 2 - a.clone());
+        let d: Integer = a.clone() * a.clone() + b.clone() * b.clone();
+        (a, b) = if bit == '1' {
+            (d.clone(), c + d)
+        } else {
+            (c, d)
+        }
+    }
+    if neg {
+        // F(-n) = (-1)^(n+1) F(n) and F(-n+1) = F(-(n-1))
+        let odd = md(n.clone(), &Integer::from(2)) == 1;
+        let prev = b - a.clone();
+        (
+            if odd { a.clone() } else { -a.clone() },
+            if odd { -prev } else { prev },
+        )
+    } else {
+        (a, b)
+    }
+}
+pub fn lucas<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    n: &Integer,
+) -> Integer {
+    let (a, b) = fibonacci(n);
+    b * 2 - a
+}
+// bernoulli numbers as a reduced fraction via the akiyama tanigawa algorithm, with B1 = -1/2
+pub fn bernoulli<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    n: usize,
+) -> (Integer, Integer) {
+    if n == 1 {
+        return (Integer::from(-1), Integer::from(2));
+    } else if n % 2 == 1 {
+        return (Integer::new(), Integer::from(1));
+    }
+    let reduce = |p: Integer, q: Integer| {
+        let g = p.clone().gcd(&q);
+        if g == 0 {
+            (p, q)
+        } else {
+            (p / g.clone(), q / g)
+        }
+    };
+    let mut a: Vec<(Integer, Integer)> = Vec::with_capacity(n + 1);
+    for m in 0..=n {
+        a.push((Integer::from(1), Integer::from(m + 1)));
+        for j in (1..=m).rev() {
+            let (p0, q0) = a[j - 1].clone();
+            let (p1, q1) = a[j].clone();
+            a[j - 1] = reduce((p0 * q1.clone() - p1 * q0.clone()) * j, q0 * q1);
+        }
+    }
+    a.swap_remove(0)
+}
+// rough count of the machine word operations the exact recurrences below take, past
+// RECURRENCE_WORK callers fall back to a closed form, an asymptotic or an error
+pub const RECURRENCE_WORK: f64 = (1u64 << 30) as f64;
+pub fn recurrence_work(s: &str, n: u64, k: u64) -> f64 {
+    let (n, k) = (n as f64, k.min(n) as f64);
+    // words of an n log n bit number, about the size of the entries of the triangles
+    let words = n * n.max(2.0).log2() / 64.0;
+    match s {
+        // n^2/2 additions, or reductions whose gcds are quadratic in the words
+        "bell" => n * n / 2.0 * words,
+        "bernoulli" => n * n / 2.0 * words * words,
+        // a row of k + 1 entries per step, or k + 1 powers for the explicit sum
+        "stirling1" => n * (k + 1.0) * words,
+        "stirling2" if stirling2_by_sum(n as usize, k as usize) => {
+            (k + 1.0) * (n * (k + 2.0).log2() / 64.0).powf(1.6)
+        }
+        "stirling2" => n * (k + 1.0) * words,
+        // n^1.5 additions of 4 sqrt(n) bit numbers
+        "partitions" => n * n / 16.0,
+        "harmonic" => n * (n / 44.0).powi(2),
+        "catalan" => n * n / 32.0,
+        _ => 0.0,
+    }
+}
+// signed stirling numbers of the first kind
+pub fn stirling1<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    n: usize,
+    k: usize,
+) -> Integer {
+    if k > n {
+        return Integer::new();
+    }
+    // only the first k + 1 entries of each row feed entry k
+    let mut row = vec![Integer::from(1)];
+    for m in 0..n {
+        let mut next = vec![Integer::new(); (m + 2).min(k + 1)];
+        for (j, c) in row.iter().enumerate() {
+            next[j] -= c.clone() * m;
+            if j < k {
+                next[j + 1] += c;
+            }
+        }
+        row = next
+    }
+    row.swap_remove(k)
+}
+// the explicit sum is cheaper than the triangle once n outgrows k^2
+fn stirling2_by_sum(n: usize, k: usize) -> bool {
+    (k + 1).saturating_mul(k + 1) < n
+}
+pub fn stirling2<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    n: usize,
+    k: usize,
+) -> Integer {
+    if k > n {
+        return Integer::new();
+    }
+    if stirling2_by_sum(n, k) {
+        // k! S(n, k) = sum (-1)^(k-j) C(k, j) j^n
+        let mut sum = Integer::new();
+        for j in 0..=k {
+            let term = Integer::from(k).binomial(j as u32) * Integer::from(j).pow(n as u32);
+            if (k - j) % 2 == 0 {
+                sum += term
+            } else {
+                sum -= term
+            }
+        }
+        return sum / range_product::<Integer, Float, Complex>(1, k as u64, 1);
+    }
+    // only the first k + 1 entries of each row feed entry k
+    let mut row = vec![Integer::from(1)];
+    for m in 0..n {
+        let mut next = vec![Integer::new(); (m + 2).min(k + 1)];
+        for (j, c) in row.iter().enumerate() {
+            next[j] += c.clone() * j;
+            if j < k {
+                next[j + 1] += c;
+            }
+        }
+        row = next
+    }
+    row.swap_remove(k)
+}
+// via the bell triangle
+pub fn bell<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    n: usize,
+) -> Integer {
+    let mut row = vec![Integer::from(1)];
+    for _ in 0..n {
+        let mut next = vec![row.last().unwrap().clone()];
+        for c in &row {
+            let v = next.last().unwrap().clone() + c;
+            next.push(v)
+        }
+        row = next
+    }
+    row.swap_remove(0)
+}
+pub fn catalan<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    n: u32,
+) -> Integer {
+    let mut c = Integer::from(1);
+    for k in 0..n {
+        c = c * (4 * k + 2) / Integer::from(k + 2)
+    }
+    c
+}
+// number of integer partitions by euler's pentagonal number recurrence
+pub fn partitions<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    n: usize,
+) -> Integer {
+    let mut p = vec![Integer::from(1)];
+    for m in 1..=n {
+        let mut sum = Integer::new();
+        for k in 1.. {
+            let g1 = k * (3 * k - 1) / 2;
+            if g1 > m {
+                break;
+            }
+            let g2 = k * (3 * k + 1) / 2;
+            let mut term = p[m - g1].clone();
+            if g2 <= m {
+                term += &p[m - g2]
+            }
+            if k % 2 == 1 { sum += term } else { sum -= term }
+        }
+        p.push(sum)
+    }
+    p.swap_remove(n)
+}
+// first term of rademacher's series for p(n), which refines hardy and ramanujan's asymptotic
+// and is within a fraction of a unit of p(n) well before the exact recurrence gives out
+pub fn partitions_asymptotic<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    n: Float,
+) -> Float {
+    let prec = n.prec();
+    let pi = Float::with_val(prec, crate::types::Constant::Pi);
+    let l = (n - Float::with_val(prec, 24).recip()).sqrt();
+    let two_thirds: Float = Float::with_val(prec, 2) / 3;
+    let c = pi.clone() * two_thirds.sqrt();
+    let mu: Float = c.clone() * l.clone();
+    // c l cosh(mu) - sinh(mu), written so a huge mu overflows to inf rather than inf - inf
+    let cl: Float = c * l.clone();
+    let grow: Float = (cl.clone() - 1) * mu.clone().exp();
+    let decay: Float = (cl + 1) * (-mu).exp();
+    (grow + decay) / (Float::with_val(prec, 32).sqrt() * pi * l.clone() * l.clone() * l)
+}
+// n-th harmonic number as a reduced fraction
+pub fn harmonic<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    n: usize,
+) -> (Integer, Integer) {
+    let (mut p, mut q) = (Integer::new(), Integer::from(1));
+    for k in 1..=n {
+        p = p * k + &q;
+        q *= k;
+        let g = p.clone().gcd(&q);
+        p /= g.clone();
+        q /= g
+    }
+    (p, q)
+}
+// index vectors of every k-permutation of 0..n in lexicographic order
+pub fn permutations(n: usize, k: usize) -> Result<Vec<Vec<usize>>, &'static str> {
+    enumeration_size(n, k, false)?;
+    let mut out = Vec::new();
+    let mut cur = Vec::with_capacity(k);
+    let mut used = vec![false; n];
+    fn go(n: usize, k: usize, cur: &mut Vec<usize>, used: &mut [bool], out: &mut Vec<Vec<usize>>) {
+        if cur.len() == k {
+            out.push(cur.clone());
+            return;
+        }
+        for i in 0..n {
+            if !used[i] {
+                used[i] = true;
+                cur.push(i);
+                go(n, k, cur, used, out);
+                cur.pop();
+                used[i] = false
+            }
+        }
+    }
+    if k <= n {
+        go(n, k, &mut cur, &mut used, &mut out)
+    }
+    Ok(out)
+}
+// index vectors of every k-combination of 0..n in lexicographic order
+pub fn combinations(n: usize, k: usize) -> Result<Vec<Vec<usize>>, &'static str> {
+    enumeration_size(n, k, true)?;
+    let mut out = Vec::new();
+    if k > n {
+        return Ok(out);
+    }
+    let mut cur = (0..k).collect::<Vec<usize>>();
+    loop {
+        out.push(cur.clone());
+        let Some(i) = (0..k).rev().find(|i| cur[*i] != i + n - k) else {
+            return Ok(out);
+        };
+        cur[i] += 1;
+        for j in i + 1..k {
+            cur[j] = cur[j - 1] + 1
+        }
+    }
+}
+// n!/(n-k)! or C(n,k) rows of k entries each, capped so enumerating cant exhaust memory
+fn enumeration_size(n: usize, k: usize, choose: bool) -> Result<(), &'static str> {
+    if k > n {
+        return Ok(());
+    }
+    let count = (0..k).try_fold(1u128, |c, j| {
+        let c = c.checked_mul((n - j) as u128)?;
+        Some(if choose { c / (j as u128 + 1) } else { c })
+    });
+    if count.is_none_or(|c| c > 1 << 20 || c * k as u128 > 1 << 24) {
+        Err("too many to enumerate")
+    } else {
+        Ok(())
+    }
+}
+// lexicographic rank of a permutation of 0..n from its lehmer code
+pub fn perm_rank<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    perm: &[usize],
+) -> Integer {
+    let mut rank = Integer::new();
+    for (i, p) in perm.iter().enumerate() {
+        let smaller = perm[i + 1..].iter().filter(|q| *q < p).count();
+        rank = rank * (perm.len() - i) + smaller
+    }
+    rank
+}
+// permutation of 0..n with the given lexicographic rank, none if rank is out of range
+pub fn perm_unrank<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    n: usize,
+    mut rank: Integer,
+) -> Option<Vec<usize>> {
+    if rank.cmp0() == Ordering::Less {
+        return None;
+    }
+    let mut code = vec![0; n];
+    for (i, c) in code.iter_mut().enumerate().rev() {
+        let (q, r) = rank.div_rem(Integer::from(n - i));
+        *c = r.to_usize().unwrap_or_default();
+        rank = q
+    }
+    if rank != 0 {
+        return None;
+    }
+    let mut left = (0..n).collect::<Vec<usize>>();
+    Some(code.into_iter().map(|c| left.remove(c)).collect())
+}
+pub fn binomial<
+    I: crate::types::Integer<F, C>,
+    F: crate::types::Float<I, C>,
+    C: crate::types::Complex<I, F>,
+>(
+    a: C,
+    b: C,
+) -> C {
+    if a.imag().is_zero()
+        && b.imag().is_zero()
+        && a.real().clone().fract().is_zero()
+        && b.real().clone().fract().is_zero()
+        && a.real().is_finite()
+        && a.real() >= &0
+        && b.real() >= &0
+    {
+        C::with_val(
+            a.prec(),
+            a.real().to_integer().unwrap_or_default().binomial(
+                b.real()
+                    .to_integer()
+                    .unwrap_or_default()
+                    .to_u32()
+                    .unwrap_or_default(),
+            ),
+        )
+    } else if a.real().is_sign_negative()
+        && a.real().clone().fract().is_zero()
+        && a.imag().is_zero()
+        && b.imag().is_zero()
+    {
+        let prec = a.prec();
+        let a = a + C::with_val(prec, (0, 1)) * F::with_val(prec, 0.5).pow(prec / 2);
+        let g: C = gamma(a.clone() + 1) / (gamma(b.clone() + 1) * gamma(a.clone() - b.clone() + 1));
+        g.real().clone().into()
+    } else {
+        gamma(a.clone() + 1) / (gamma(b.clone() + 1) * gamma(a.clone() - b.clone() + 1))
+    }
+}
+fn incomplete_gamma_recursion<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    s: Complex,
+    z: Complex,
+    iter: usize,
+    max: usize,
+) -> Complex {
+    if iter == max {
+        Complex::with_val(s.prec(), 1)
+    } else if iter == 0 {
+        (pow_nth(z.clone(), s.clone()) / z.clone().exp()) / incomplete_gamma_recursion(s, z, 1, max)
+    } else if iter % 2 == 1 {
+        z.clone()
+            + ((-s.clone() + iter.div_ceil(2)) / incomplete_gamma_recursion(s, z, iter + 1, max))
+    } else {
+        (Float::with_val(z.prec(), iter.div_ceil(2))
+            / incomplete_gamma_recursion(s, z, iter + 1, max))
+            + 1
+    }
+}
+fn lower_incomplete_gamma_recursion<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    s: Complex,
+    z: Complex,
+    iter: usize,
+    max: usize,
+) -> Complex {
+    if iter == max {
+        Complex::with_val(s.prec(), 1)
+    } else if iter == 0 {
+        (pow_nth(z.clone(), s.clone()) / z.clone().exp())
+            / lower_incomplete_gamma_recursion(s, z, 1, max)
+    } else if iter % 2 == 1 {
+        s.clone() + iter
+            - 1
+            - ((s.clone() + iter - 1) * z.clone())
+                / lower_incomplete_gamma_recursion(s, z, iter + 1, max)
+    } else {
+        s.clone() + iter - 1
+            + (z.clone() / lower_incomplete_gamma_recursion(s, z, iter + 1, max) * iter / 2)
+    }
+}
+pub fn subfactorial<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    z: Complex,
+) -> Complex {
+    subfactorial_recursion(z.clone(), 0, (z.prec() as usize / 4).max(32))
+        + gamma(z.clone() + 1) / Float::with_val(z.prec(), 1).exp()
+}
+fn subfactorial_recursion<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    z: Complex,
+    iter: usize,
+    max: usize,
+) -> Complex {
+    if iter == max {
+        Complex::with_val(z.prec(), 1)
+    } else if iter == 0 {
+        Complex::with_val(z.prec(), -1).pow(z.clone()) / subfactorial_recursion(z, 1, max)
+    } else {
+        (z.clone() + iter + 1)
+            - Float::with_val(z.prec(), iter) / subfactorial_recursion(z, iter + 1, max)
+    }
+}
+#[allow(clippy::too_many_arguments)]
+#[allow(clippy::type_complexity)]
+pub fn surface_area<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    func: Vec<NumStr<Integer, Float, Complex>>,
+    func_vars: Vec<(String, Vec<NumStr<Integer, Float, Complex>>)>,
+    options: Options,
+    varx: String,
+    mut startx: Complex,
+    endx: Number<Integer, Float, Complex>,
+    vary: String,
+    starty_func: Vec<NumStr<Integer, Float, Complex>>,
+    endy_func: Vec<NumStr<Integer, Float, Complex>>,
+) -> Result<Number<Integer, Float, Complex>, &'static str> {
+    if starty_func.is_empty() || endy_func.is_empty() {
+        return Err("bad start/end");
+    }
+    let points = options.prec as usize / 16;
+    let unitsx = endx.units;
+    let endx = endx.number;
+    let deltax: Complex = (endx.clone() - startx.clone()) / (points - 1);
+    let mut area: Complex = Complex::new(options.prec);
+    if let (Ok(Num(start)), Ok(Num(end))) = (
         do_math(starty_func.clone(), options, func_vars.clone()),
         do_math(endy_func.clone(), options, func_vars.clone()),
     ) {
@@ -3929,6 +7756,229 @@ fn check_bounds<
     }
     Ok(())
 }
+// a seed is an integer from 0 to 2^64-1, anything else is rejected instead of wrapped
+pub fn to_seed<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    n: &Float,
+) -> Result<u64, &'static str> {
+    const ERR: &str = "seed must be an integer from 0 to 2^64-1";
+    if !n.is_finite() || (n.is_sign_negative() && !n.is_zero()) || !n.clone().fract().is_zero() {
+        return Err(ERR);
+    }
+    n.to_integer()
+        .and_then(|n| n.to_u128())
+        .and_then(|n| u64::try_from(n).ok())
+        .ok_or(ERR)
+}
+// seed for the k-th of many draws from one seed, mixed with splitmix64 so nearby seeds
+// and indices give unrelated streams
+pub fn index_seed(seed: u64, k: usize) -> u64 {
+    let mut z =
+        (seed ^ (k as u64).wrapping_mul(0x9e3779b97f4a7c15)).wrapping_add(0x9e3779b97f4a7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+    z ^ (z >> 31)
+}
+// evaluates function n times, keeping only a running count, mean and sum of squared
+// deviations per worker which are merged with chan's formula, returns {mean, var, lo, hi}
+// with a 95% confidence interval for the mean, a seeded run gives the same result every time
+#[allow(clippy::type_complexity)]
+pub fn montecarlo<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    function: Vec<NumStr<Integer, Float, Complex>>,
+    func_vars: Vec<(String, Vec<NumStr<Integer, Float, Complex>>)>,
+    options: Options,
+    n: usize,
+    seed: Option<u64>,
+) -> Result<NumStr<Integer, Float, Complex>, &'static str> {
+    if n < 2 {
+        return Err("need at least 2 samples");
+    }
+    let prec = options.prec;
+    #[cfg(feature = "fastrand")]
+    let seed = seed.or(options.seed.map(|_| fastrand::u64(..)));
+    #[cfg(feature = "fastrand")]
+    let state = fastrand::get_seed();
+    let sample = |k: usize| -> Result<Float, &'static str> {
+        #[cfg(feature = "fastrand")]
+        if let Some(seed) = seed {
+            fastrand::seed(index_seed(seed, k))
+        }
+        #[cfg(not(feature = "fastrand"))]
+        let _ = (k, seed);
+        Ok(do_math(function.clone(), options, func_vars.clone())?
+            .num()?
+            .number
+            .real()
+            .clone())
+    };
+    let empty = || (0usize, Float::new(prec), Float::new(prec));
+    let push = |(n, mean, m2): (usize, Float, Float), x: Float| {
+        let d: Float = x.clone() - &mean;
+        let mean: Float = mean + d.clone() / (n + 1);
+        let d2: Float = x - &mean;
+        (n + 1, mean, m2 + d * d2)
+    };
+    let merge = |a: (usize, Float, Float), b: (usize, Float, Float)| {
+        if a.0 == 0 {
+            return b;
+        } else if b.0 == 0 {
+            return a;
+        }
+        let n = a.0 + b.0;
+        let d: Float = b.1 - &a.1;
+        let mean: Float = a.1 + d.clone() * b.0 / n;
+        let m2: Float = d.clone() * d * a.0 * b.0 / n;
+        (n, mean, a.2 + b.2 + m2)
+    };
+    // fixed index chunks are streamed on their own and merged in order, so the result does
+    // not depend on how the work is split between threads
+    let size = (n / 4096).max(1024);
+    let chunk = |j: usize| {
+        (j * size..((j + 1) * size).min(n)).try_fold(empty(), |acc, k| Ok(push(acc, sample(k)?)))
+    };
+    let chunks: Result<Vec<(usize, Float, Float)>, &'static str> = if options.graphing {
+        (0..n.div_ceil(size)).map(chunk).collect()
+    } else {
+        #[cfg(feature = "rayon")]
+        let r = (0..n.div_ceil(size)).into_par_iter().map(chunk).collect();
+        #[cfg(not(feature = "rayon"))]
+        let r = (0..n.div_ceil(size)).map(chunk).collect();
+        r
+    };
+    let stats = chunks.map(|c| c.into_iter().fold(empty(), merge));
+    #[cfg(feature = "fastrand")]
+    if seed.is_some() {
+        fastrand::seed(state)
+    }
+    let (_, mean, m2) = stats?;
+    let var: Float = m2 / (n - 1);
+    let se: Float = var.clone() / n;
+    let half = quantile("norm", Float::with_val(prec, 0.975), &[])? * se.sqrt();
+    Ok(Vector(vec![
+        Number::from(mean.clone().into(), None),
+        Number::from(var.into(), None),
+        Number::from((mean.clone() - half.clone()).into(), None),
+        Number::from((mean + half).into(), None),
+    ]))
+}
+// resamples data with replacement n times and returns {{lo, hi}, {lo, hi}} holding the
+// percentile and bias corrected and accelerated intervals for the named vector statistic
+#[cfg(feature = "fastrand")]
+#[allow(clippy::type_complexity)]
+pub fn bootstrap<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    data: Vec<Number<Integer, Float, Complex>>,
+    stat: &str,
+    n: usize,
+    level: Float,
+    options: Options,
+) -> Result<NumStr<Integer, Float, Complex>, &'static str> {
+    if data.len() < 2 || n < 2 {
+        return Err("not enough data");
+    }
+    if !(level.cmp0() == Some(Ordering::Greater) && level < 1) {
+        return Err("level must be between 0 and 1");
+    }
+    let prec = options.prec;
+    let statistic = |v: Vec<Number<Integer, Float, Complex>>| {
+        do_math(vec![Func(stat.to_string()), Vector(v)], options, Vec::new())?.num()
+    };
+    let estimate = statistic(data.clone())?;
+    let units = estimate.units;
+    let estimate = estimate.number.real().clone();
+    let seed = options.seed.map(|_| fastrand::u64(..));
+    let state = fastrand::get_seed();
+    let resample = |k: usize| -> Result<Float, &'static str> {
+        if let Some(seed) = seed {
+            fastrand::seed(index_seed(seed, k))
+        }
+        let v = (0..data.len())
+            .map(|_| data[fastrand::usize(..data.len())].clone())
+            .collect();
+        Ok(statistic(v)?.number.real().clone())
+    };
+    let stats: Result<Vec<Float>, &'static str> = if options.graphing {
+        (0..n).map(resample).collect()
+    } else {
+        #[cfg(feature = "rayon")]
+        let r = (0..n).into_par_iter().map(resample).collect();
+        #[cfg(not(feature = "rayon"))]
+        let r = (0..n).map(resample).collect();
+        r
+    };
+    if seed.is_some() {
+        fastrand::seed(state)
+    }
+    let mut stats = stats?;
+    stats.sort_by(|x, y| x.partial_cmp(y).unwrap_or(Ordering::Equal));
+    let at = |p: &Float| -> Float {
+        let r: Float = p.clone() * (n - 1);
+        let r = r
+            .round()
+            .to_integer()
+            .unwrap_or_default()
+            .to_usize()
+            .unwrap_or_default();
+        stats[r.min(n - 1)].clone()
+    };
+    let alpha: Float = (-level + 1) / 2;
+    let upper: Float = -alpha.clone() + 1;
+    // bias correction from the share of resamples below the estimate
+    let below = stats.iter().filter(|s| **s < estimate).count();
+    let z0 = quantile("norm", Float::with_val(prec, below) / n, &[])?;
+    // acceleration from the skewness of the jackknife estimates
+    let mut jack = Vec::with_capacity(data.len());
+    for i in 0..data.len() {
+        let mut v = data.clone();
+        v.remove(i);
+        jack.push(statistic(v)?.number.real().clone())
+    }
+    let mean: Float = jack.iter().fold(Float::new(prec), |s, j| s + j) / jack.len();
+    let (mut num, mut den) = (Float::new(prec), Float::new(prec));
+    for j in jack {
+        let d: Float = mean.clone() - j;
+        let d2: Float = d.clone() * d.clone();
+        num += d2.clone() * d;
+        den += d2;
+    }
+    let den: Float = den.clone() * den.sqrt() * 6;
+    let a = if den.is_zero() {
+        Float::new(prec)
+    } else {
+        num / den
+    };
+    let adjust = |p: &Float| -> Result<Float, &'static str> {
+        let z = quantile("norm", p.clone(), &[])?;
+        let s: Float = z0.clone() + z;
+        let d: Float = -a.clone() * s.clone() + 1;
+        cdf("norm", z0.clone() + s / d, &[])
+    };
+    let row = |lo: Float, hi: Float| {
+        vec![
+            Number::from(lo.into(), units),
+            Number::from(hi.into(), units),
+        ]
+    };
+    Ok(Matrix(vec![
+        row(at(&alpha), at(&upper)),
+        if z0.is_finite() {
+            row(at(&adjust(&alpha)?), at(&adjust(&upper)?))
+        } else {
+            let nan = Float::with_val(prec, f64::NAN);
+            row(nan.clone(), nan)
+        },
+    ]))
+}
 #[allow(clippy::too_many_arguments)]
 #[allow(clippy::type_complexity)]
 pub fn area<
@@ -4616,7 +8666,23 @@ pub fn area<
     > = if options.graphing {
         (0..points).map(body).collect()
     } else {
-        #[cfg(feature = "rayon")]
+        #[cfg(all(feature = "rayon", feature = "fastrand"))]
+        let r = if options.seed.is_some() {
+            let base = fastrand::u64(..);
+            let state = fastrand::get_seed();
+            let r = (0..points)
+                .into_par_iter()
+                .map(|i| {
+                    fastrand::seed(index_seed(base, i));
+                    body(i)
+                })
+                .collect();
+            fastrand::seed(state);
+            r
+        } else {
+            (0..points).into_par_iter().map(body).collect()
+        };
+        #[cfg(all(feature = "rayon", not(feature = "fastrand")))]
         let r = (0..points).into_par_iter().map(body).collect();
         #[cfg(not(feature = "rayon"))]
         let r = (0..points).map(body).collect();
@@ -5522,9 +9588,177 @@ pub fn slopesided<
                         .number;
                 }
             }
-            if right || nth % 2 == 0 {
+            if right || nth % 2 == 0 {
+                let mut n = NumStr::new(Number::from(
+                    sum * Float::with_val(options.prec, 2).pow(nth * prec),
+                    match (yunits, units) {
+                        (Some(a), Some(b)) => Some(a.div(&b)),
+                        (Some(a), None) => Some(a),
+                        (None, Some(b)) => Some(Units::default().div(&b)),
+                        (None, None) => None,
+                    },
+                ));
+                if oop != 0 {
+                    n.set_prec(oop);
+                }
+                Ok(n)
+            } else {
+                let mut n = NumStr::new(Number::from(
+                    -sum * Float::with_val(options.prec, 2).pow(nth * prec),
+                    match (yunits, units) {
+                        (Some(a), Some(b)) => Some(a.div(&b)),
+                        (Some(a), None) => Some(a),
+                        (None, Some(b)) => Some(Units::default().div(&b)),
+                        (None, None) => None,
+                    },
+                ));
+                if oop != 0 {
+                    n.set_prec(oop);
+                }
+                Ok(n)
+            }
+        }
+        Vector(mut sum) if !combine => {
+            let yunits = sum[0].units;
+            if nth % 2 == 1 {
+                for n in sum.iter_mut() {
+                    n.number *= -1;
+                }
+            }
+            for k in 0..nth {
+                let b = num.clone().binomial(k);
+                let vec = do_math_with_var(
+                    func.clone(),
+                    options,
+                    func_vars.clone(),
+                    &var,
+                    NumStr::new(Number::from(point.clone() + h.clone() * (nth - k), units)),
+                )?
+                .vec()?;
+                if k % 2 == 0 {
+                    for (n, a) in sum.iter_mut().zip(vec) {
+                        n.number += a.number * b.clone()
+                    }
+                } else {
+                    for (n, a) in sum.iter_mut().zip(vec) {
+                        n.number -= a.number * b.clone()
+                    }
+                }
+            }
+            let mut v = Vector(
+                sum.iter()
+                    .map(|n| {
+                        Number::from(
+                            if right || nth % 2 == 0 {
+                                n.number.clone() * Float::with_val(options.prec, 2).pow(nth * prec)
+                            } else {
+                                -n.number.clone() * Float::with_val(options.prec, 2).pow(nth * prec)
+                            },
+                            match (yunits, units) {
+                                (Some(a), Some(b)) => Some(a.div(&b)),
+                                (Some(a), None) => Some(a),
+                                (None, Some(b)) => Some(Units::default().div(&b)),
+                                (None, None) => None,
+                            },
+                        )
+                    })
+                    .collect::<Vec<Number<Integer, Float, Complex>>>(),
+            );
+            if oop != 0 {
+                v.set_prec(oop);
+            }
+            Ok(v)
+        }
+        Vector(mut sum) if sum.len() == 1 => {
+            let yunits = sum[0].units;
+            if nth % 2 == 1 {
+                sum[0].number *= -1;
+            }
+            for k in 0..nth {
+                if k % 2 == 0 {
+                    sum[0].number += num.clone().binomial(k)
+                        * do_math_with_var(
+                            func.clone(),
+                            options,
+                            func_vars.clone(),
+                            &var,
+                            NumStr::new(Number::from(point.clone() + h.clone() * (nth - k), units)),
+                        )?
+                        .num()?
+                        .number;
+                } else {
+                    sum[0].number -= num.clone().binomial(k)
+                        * do_math_with_var(
+                            func.clone(),
+                            options,
+                            func_vars.clone(),
+                            &var,
+                            NumStr::new(Number::from(point.clone() + h.clone() * (nth - k), units)),
+                        )?
+                        .num()?
+                        .number;
+                }
+            }
+            if right || nth % 2 == 0 {
+                let mut n = NumStr::new(Number::from(
+                    sum[0].number.clone() * Float::with_val(options.prec, 2).pow(nth * prec),
+                    match (yunits, units) {
+                        (Some(a), Some(b)) => Some(a.div(&b)),
+                        (Some(a), None) => Some(a),
+                        (None, Some(b)) => Some(Units::default().div(&b)),
+                        (None, None) => None,
+                    },
+                ));
+                if oop != 0 {
+                    n.set_prec(oop)
+                }
+                Ok(n)
+            } else {
+                let mut n = NumStr::new(Number::from(
+                    -sum[0].number.clone() * Float::with_val(options.prec, 2).pow(nth * prec),
+                    match (yunits, units) {
+                        (Some(a), Some(b)) => Some(a.div(&b)),
+                        (Some(a), None) => Some(a),
+                        (None, Some(b)) => Some(Units::default().div(&b)),
+                        (None, None) => None,
+                    },
+                ));
+                if oop != 0 {
+                    n.set_prec(oop)
+                }
+                Ok(n)
+            }
+        }
+        Vector(mut sum) => {
+            let yunits = sum[0].units;
+            if nth % 2 == 1 {
+                for n in sum.iter_mut() {
+                    n.number *= -1;
+                }
+            }
+            for k in 0..nth {
+                let b = num.clone().binomial(k);
+                let vec = do_math_with_var(
+                    func.clone(),
+                    options,
+                    func_vars.clone(),
+                    &var,
+                    NumStr::new(Number::from(point.clone() + h.clone() * (nth - k), units)),
+                )?
+                .vec()?;
+                if k % 2 == 0 {
+                    for (n, a) in sum.iter_mut().zip(vec) {
+                        n.number += a.number * b.clone()
+                    }
+                } else {
+                    for (n, a) in sum.iter_mut().zip(vec) {
+                        n.number -= a.number * b.clone()
+                    }
+                }
+            }
+            if sum.len() == 2 {
                 let mut n = NumStr::new(Number::from(
-                    sum * Float::with_val(options.prec, 2).pow(nth * prec),
+                    sum[1].number.clone() / sum[0].number.clone(),
                     match (yunits, units) {
                         (Some(a), Some(b)) => Some(a.div(&b)),
                         (Some(a), None) => Some(a),
@@ -5533,275 +9767,632 @@ pub fn slopesided<
                     },
                 ));
                 if oop != 0 {
-                    n.set_prec(oop);
+                    n.set_prec(oop)
                 }
                 Ok(n)
             } else {
-                let mut n = NumStr::new(Number::from(
-                    -sum * Float::with_val(options.prec, 2).pow(nth * prec),
-                    match (yunits, units) {
-                        (Some(a), Some(b)) => Some(a.div(&b)),
-                        (Some(a), None) => Some(a),
-                        (None, Some(b)) => Some(Units::default().div(&b)),
-                        (None, None) => None,
-                    },
-                ));
+                let nf = &sum.last().unwrap().number;
+                let mut v = Vector(
+                    sum[0..sum.len().saturating_sub(1)]
+                        .iter()
+                        .map(|n| {
+                            Number::from(
+                                nf.clone() / n.number.clone(),
+                                match (yunits, units) {
+                                    (Some(a), Some(b)) => Some(a.div(&b)),
+                                    (Some(a), None) => Some(a),
+                                    (None, Some(b)) => Some(Units::default().div(&b)),
+                                    (None, None) => None,
+                                },
+                            )
+                        })
+                        .collect::<Vec<Number<Integer, Float, Complex>>>(),
+                );
                 if oop != 0 {
-                    n.set_prec(oop);
+                    v.set_prec(oop)
                 }
+                Ok(v)
+            }
+        }
+        _ => Err("not supported slope data"),
+    }
+}
+#[derive(Copy, Clone, PartialEq)]
+pub enum LimSide {
+    Left,
+    Right,
+    Both,
+}
+#[allow(clippy::type_complexity)]
+pub fn limit<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    mut func: Vec<NumStr<Integer, Float, Complex>>,
+    mut func_vars: Vec<(String, Vec<NumStr<Integer, Float, Complex>>)>,
+    mut options: Options,
+    var: String,
+    point: Number<Integer, Float, Complex>,
+    side: LimSide,
+) -> Result<NumStr<Integer, Float, Complex>, &'static str> {
+    let xunits = point.units;
+    let mut point = point.number;
+    let oop = options.prec;
+    options.prec = options.prec.clamp(256, 1024);
+    if oop != options.prec {
+        point.set_prec(options.prec);
+        set_prec(&mut func, &mut func_vars, options.prec);
+    }
+    if point.clone().real().is_infinite() || point.clone().imag().is_infinite() {
+        let (h1, h2);
+        let positive = point.real().is_sign_positive();
+        if positive {
+            h1 = Complex::with_val(options.prec, 2).pow(options.prec / 4);
+            h2 = Complex::with_val(options.prec, 2).pow((options.prec / 3) as f64 + 7.0 / 0.94) - 3;
+        } else {
+            h1 = -Complex::with_val(options.prec, 2).pow(options.prec / 4);
+            h2 =
+                -Complex::with_val(options.prec, 2).pow((options.prec / 3) as f64 + 7.0 / 0.94) + 3;
+        }
+        let n1 = do_math_with_var(
+            func.clone(),
+            options,
+            func_vars.clone(),
+            &var,
+            NumStr::new(Number::from(h1, xunits)),
+        )?;
+        let n2 = do_math_with_var(
+            func.clone(),
+            options,
+            func_vars.clone(),
+            &var,
+            NumStr::new(Number::from(h2, xunits)),
+        )?;
+        match (n1, n2) {
+            (Num(n1), Num(n2)) => {
+                let units = n1.units;
+                let n1 = n1.number;
+                let n2 = n2.number;
+                let mut n = if (n1.clone() - n2.clone()).abs().real().clone().log2()
+                    < options.prec as i32 / -16
+                {
+                    NumStr::new(Number::from(n2, units))
+                } else if n1.real().is_sign_positive() != n2.real().is_sign_positive()
+                    || n1.imag().is_sign_positive() != n2.imag().is_sign_positive()
+                {
+                    NumStr::new(Number::from(
+                        Complex::with_val(options.prec, Constant::Nan),
+                        None,
+                    ))
+                } else if n2.real().is_infinite() || n2.imag().is_infinite() {
+                    NumStr::new(Number::from(
+                        match (n2.real().is_infinite(), n2.imag().is_infinite()) {
+                            (true, true) => {
+                                match (n1.real().is_sign_positive(), n1.imag().is_sign_positive()) {
+                                    (true, true) => Complex::with_val(
+                                        options.prec,
+                                        (Constant::Infinity, Constant::Infinity),
+                                    ),
+                                    (true, false) => Complex::with_val(
+                                        options.prec,
+                                        (Constant::Infinity, Constant::NegInfinity),
+                                    ),
+                                    (false, true) => Complex::with_val(
+                                        options.prec,
+                                        (Constant::NegInfinity, Constant::Infinity),
+                                    ),
+                                    (false, false) => -Complex::with_val(
+                                        options.prec,
+                                        (Constant::Infinity, Constant::Infinity),
+                                    ),
+                                }
+                            }
+                            (true, false) => {
+                                Complex::with_val(
+                                    options.prec,
+                                    if n1.real().is_sign_positive() {
+                                        Constant::Infinity
+                                    } else {
+                                        Constant::NegInfinity
+                                    },
+                                ) + Complex::with_val_imag(
+                                    options.prec,
+                                    if (n1.imag().clone() - n2.imag()).abs().log2()
+                                        < options.prec as i32 / -16
+                                    {
+                                        n2.imag().clone()
+                                    } else {
+                                        Float::new(options.prec)
+                                    },
+                                )
+                            }
+                            (false, true) => {
+                                Complex::with_val(
+                                    options.prec,
+                                    if (n1.real().clone() - n2.real()).abs().log2()
+                                        < options.prec as i32 / -16
+                                    {
+                                        n2.real().clone()
+                                    } else {
+                                        Float::new(options.prec)
+                                    },
+                                ) + Complex::with_val_imag(
+                                    options.prec,
+                                    if n1.imag().is_sign_positive() {
+                                        Constant::Infinity
+                                    } else {
+                                        Constant::NegInfinity
+                                    },
+                                )
+                            }
+                            (false, false) => Complex::with_val(options.prec, Constant::Nan),
+                        },
+                        units,
+                    ))
+                } else {
+                    let n3 = do_math_with_var(
+                        func.clone(),
+                        options,
+                        func_vars.clone(),
+                        &var,
+                        NumStr::new(Number::from(
+                            if positive {
+                                Complex::with_val(options.prec, 2)
+                                    .pow((options.prec / 2) as f64 + 13.0 / 0.7)
+                                    - 7
+                            } else {
+                                -Complex::with_val(options.prec, 2)
+                                    .pow((options.prec / 2) as f64 + 13.0 / 0.7)
+                                    + 7
+                            },
+                            xunits,
+                        )),
+                    )?
+                    .num()?
+                    .number;
+                    let sign = n2.real().is_sign_positive() == n3.real().is_sign_positive()
+                        && n2.imag().is_sign_positive() == n3.imag().is_sign_positive();
+                    let n1r = n1.real().clone().abs();
+                    let n2r = n2.real().clone().abs();
+                    let n3r = n3.real().clone().abs();
+                    let n1i = n1.imag().clone().abs();
+                    let n2i = n2.imag().clone().abs();
+                    let n3i = n3.imag().clone().abs();
+                    NumStr::new(Number::from(
+                        if !sign {
+                            Complex::with_val(options.prec, Constant::Nan)
+                        } else {
+                            match (n3r > n2r && n2r > n1r, n3i > n2i && n2i > n1i) {
+                                (true, true) => {
+                                    match (
+                                        n1.real().is_sign_positive(),
+                                        n1.imag().is_sign_positive(),
+                                    ) {
+                                        (true, true) => Complex::with_val(
+                                            options.prec,
+                                            (Constant::Infinity, Constant::Infinity),
+                                        ),
+                                        (true, false) => Complex::with_val(
+                                            options.prec,
+                                            (Constant::Infinity, Constant::NegInfinity),
+                                        ),
+                                        (false, true) => Complex::with_val(
+                                            options.prec,
+                                            (Constant::NegInfinity, Constant::Infinity),
+                                        ),
+                                        (false, false) => -Complex::with_val(
+                                            options.prec,
+                                            (Constant::Infinity, Constant::Infinity),
+                                        ),
+                                    }
+                                }
+                                (true, false) => {
+                                    Complex::with_val(
+                                        options.prec,
+                                        if n1.real().is_sign_positive() {
+                                            Constant::Infinity
+                                        } else {
+                                            Constant::NegInfinity
+                                        },
+                                    ) + Complex::with_val_imag(
+                                        options.prec,
+                                        if (n2i - n3i.clone()).abs().log2()
+                                            < options.prec as i32 / -16
+                                        {
+                                            n3i
+                                        } else {
+                                            Float::new(options.prec)
+                                        },
+                                    )
+                                }
+                                (false, true) => {
+                                    Complex::with_val(
+                                        options.prec,
+                                        if (n2r - n3r.clone()).abs().log2()
+                                            < options.prec as i32 / -16
+                                        {
+                                            n3r
+                                        } else {
+                                            Float::new(options.prec)
+                                        },
+                                    ) + Complex::with_val_imag(
+                                        options.prec,
+                                        if n1.imag().is_sign_positive() {
+                                            Constant::Infinity
+                                        } else {
+                                            Constant::NegInfinity
+                                        },
+                                    )
+                                }
+                                (false, false) => Complex::with_val(options.prec, Constant::Nan),
+                            }
+                        },
+                        units,
+                    ))
+                };
+                n.set_prec(oop);
                 Ok(n)
             }
-        }
-        Vector(mut sum) if !combine => {
-            let yunits = sum[0].units;
-            if nth % 2 == 1 {
-                for n in sum.iter_mut() {
-                    n.number *= -1;
-                }
-            }
-            for k in 0..nth {
-                let b = num.clone().binomial(k);
-                let vec = do_math_with_var(
-                    func.clone(),
-                    options,
-                    func_vars.clone(),
-                    &var,
-                    NumStr::new(Number::from(point.clone() + h.clone() * (nth - k), units)),
-                )?
-                .vec()?;
-                if k % 2 == 0 {
-                    for (n, a) in sum.iter_mut().zip(vec) {
-                        n.number += a.number * b.clone()
-                    }
-                } else {
-                    for (n, a) in sum.iter_mut().zip(vec) {
-                        n.number -= a.number * b.clone()
-                    }
-                }
-            }
-            let mut v = Vector(
-                sum.iter()
-                    .map(|n| {
-                        Number::from(
-                            if right || nth % 2 == 0 {
-                                n.number.clone() * Float::with_val(options.prec, 2).pow(nth * prec)
+            (Vector(v1), Vector(v2)) => {
+                let mut v3: Vec<Number<Integer, Float, Complex>> = Vec::new();
+                let mut vec = Vec::with_capacity(v1.len().max(1));
+                for (i, (n1, n2)) in v1.iter().zip(v2).enumerate() {
+                    let units = n1.units;
+                    let n1 = n1.number.clone();
+                    let n2 = n2.number.clone();
+                    vec.push(Number::from(
+                        if (n1.clone() - n2.clone()).abs().real().clone().log2()
+                            < options.prec as i32 / -16
+                        {
+                            n2
+                        } else if n1.real().is_sign_positive() != n2.real().is_sign_positive()
+                            || n1.imag().is_sign_positive() != n2.imag().is_sign_positive()
+                        {
+                            Complex::with_val(options.prec, Constant::Nan)
+                        } else if n2.real().is_infinite() || n2.imag().is_infinite() {
+                            match (n2.real().is_infinite(), n2.imag().is_infinite()) {
+                                (true, true) => {
+                                    match (
+                                        n1.real().is_sign_positive(),
+                                        n1.imag().is_sign_positive(),
+                                    ) {
+                                        (true, true) => Complex::with_val(
+                                            options.prec,
+                                            (Constant::Infinity, Constant::Infinity),
+                                        ),
+                                        (true, false) => Complex::with_val(
+                                            options.prec,
+                                            (Constant::Infinity, Constant::NegInfinity),
+                                        ),
+                                        (false, true) => Complex::with_val(
+                                            options.prec,
+                                            (Constant::NegInfinity, Constant::Infinity),
+                                        ),
+                                        (false, false) => -Complex::with_val(
+                                            options.prec,
+                                            (Constant::Infinity, Constant::Infinity),
+                                        ),
+                                    }
+                                }
+                                (true, false) => {
+                                    Complex::with_val(
+                                        options.prec,
+                                        if n1.real().is_sign_positive() {
+                                            Constant::Infinity
+                                        } else {
+                                            Constant::NegInfinity
+                                        },
+                                    ) + Complex::with_val_imag(
+                                        options.prec,
+                                        if (n1.imag().clone() - n2.imag()).abs().log10() <= -10 {
+                                            n2.imag().clone()
+                                        } else {
+                                            Float::new(options.prec)
+                                        },
+                                    )
+                                }
+                                (false, true) => {
+                                    Complex::with_val(
+                                        options.prec,
+                                        if (n1.real().clone() - n2.real()).abs().log10() <= -10 {
+                                            n2.real().clone()
+                                        } else {
+                                            Float::new(options.prec)
+                                        },
+                                    ) + Complex::with_val_imag(
+                                        options.prec,
+                                        if n1.imag().is_sign_positive() {
+                                            Constant::Infinity
+                                        } else {
+                                            Constant::NegInfinity
+                                        },
+                                    )
+                                }
+                                (false, false) => Complex::with_val(options.prec, Constant::Nan),
+                            }
+                        } else {
+                            if v3.is_empty() {
+                                v3 = do_math_with_var(
+                                    func.clone(),
+                                    options,
+                                    func_vars.clone(),
+                                    &var,
+                                    NumStr::new(Number::from(
+                                        if positive {
+                                            Complex::with_val(options.prec, 2)
+                                                .pow((options.prec / 2) as f64 + 13.0 / 0.7)
+                                                - 7
+                                        } else {
+                                            -Complex::with_val(options.prec, 2)
+                                                .pow((options.prec / 2) as f64 + 13.0 / 0.7)
+                                                + 7
+                                        },
+                                        xunits,
+                                    )),
+                                )?
+                                .vec()?;
+                            }
+                            let v3 = &v3[i].number;
+                            let sign = n2.real().is_sign_positive() == v3.real().is_sign_positive()
+                                && n2.imag().is_sign_positive() == v3.imag().is_sign_positive();
+                            let n1r = n1.real().clone().abs();
+                            let n2r = n2.real().clone().abs();
+                            let n3r = v3.real().clone().abs();
+                            let n1i = n1.imag().clone().abs();
+                            let n2i = n2.imag().clone().abs();
+                            let n3i = v3.imag().clone().abs();
+                            if !sign {
+                                Complex::with_val(options.prec, Constant::Nan)
                             } else {
-                                -n.number.clone() * Float::with_val(options.prec, 2).pow(nth * prec)
-                            },
-                            match (yunits, units) {
-                                (Some(a), Some(b)) => Some(a.div(&b)),
-                                (Some(a), None) => Some(a),
-                                (None, Some(b)) => Some(Units::default().div(&b)),
-                                (None, None) => None,
-                            },
-                        )
-                    })
-                    .collect::<Vec<Number<Integer, Float, Complex>>>(),
-            );
-            if oop != 0 {
-                v.set_prec(oop);
-            }
-            Ok(v)
-        }
-        Vector(mut sum) if sum.len() == 1 => {
-            let yunits = sum[0].units;
-            if nth % 2 == 1 {
-                sum[0].number *= -1;
-            }
-            for k in 0..nth {
-                if k % 2 == 0 {
-                    sum[0].number += num.clone().binomial(k)
-                        * do_math_with_var(
-                            func.clone(),
-                            options,
-                            func_vars.clone(),
-                            &var,
-                            NumStr::new(Number::from(point.clone() + h.clone() * (nth - k), units)),
-                        )?
-                        .num()?
-                        .number;
-                } else {
-                    sum[0].number -= num.clone().binomial(k)
-                        * do_math_with_var(
-                            func.clone(),
-                            options,
-                            func_vars.clone(),
-                            &var,
-                            NumStr::new(Number::from(point.clone() + h.clone() * (nth - k), units)),
-                        )?
-                        .num()?
-                        .number;
-                }
-            }
-            if right || nth % 2 == 0 {
-                let mut n = NumStr::new(Number::from(
-                    sum[0].number.clone() * Float::with_val(options.prec, 2).pow(nth * prec),
-                    match (yunits, units) {
-                        (Some(a), Some(b)) => Some(a.div(&b)),
-                        (Some(a), None) => Some(a),
-                        (None, Some(b)) => Some(Units::default().div(&b)),
-                        (None, None) => None,
-                    },
-                ));
-                if oop != 0 {
-                    n.set_prec(oop)
-                }
-                Ok(n)
-            } else {
-                let mut n = NumStr::new(Number::from(
-                    -sum[0].number.clone() * Float::with_val(options.prec, 2).pow(nth * prec),
-                    match (yunits, units) {
-                        (Some(a), Some(b)) => Some(a.div(&b)),
-                        (Some(a), None) => Some(a),
-                        (None, Some(b)) => Some(Units::default().div(&b)),
-                        (None, None) => None,
-                    },
-                ));
-                if oop != 0 {
-                    n.set_prec(oop)
+                                match (n3r > n2r && n2r > n1r, n3i > n2i && n2i > n1i) {
+                                    (true, true) => {
+                                        match (
+                                            n1.real().is_sign_positive(),
+                                            n1.imag().is_sign_positive(),
+                                        ) {
+                                            (true, true) => Complex::with_val(
+                                                options.prec,
+                                                (Constant::Infinity, Constant::Infinity),
+                                            ),
+                                            (true, false) => Complex::with_val(
+                                                options.prec,
+                                                (Constant::Infinity, Constant::NegInfinity),
+                                            ),
+                                            (false, true) => Complex::with_val(
+                                                options.prec,
+                                                (Constant::NegInfinity, Constant::Infinity),
+                                            ),
+                                            (false, false) => -Complex::with_val(
+                                                options.prec,
+                                                (Constant::Infinity, Constant::Infinity),
+                                            ),
+                                        }
+                                    }
+                                    (true, false) => {
+                                        Complex::with_val(
+                                            options.prec,
+                                            if n1.real().is_sign_positive() {
+                                                Constant::Infinity
+                                            } else {
+                                                Constant::NegInfinity
+                                            },
+                                        ) + Complex::with_val_imag(
+                                            options.prec,
+                                            if (n2i - n3i.clone()).abs().log2()
+                                                < options.prec as i32 / -16
+                                            {
+                                                n3i
+                                            } else {
+                                                Float::new(options.prec)
+                                            },
+                                        )
+                                    }
+                                    (false, true) => {
+                                        Complex::with_val(
+                                            options.prec,
+                                            if (n2r - n3r.clone()).abs().log2()
+                                                < options.prec as i32 / -16
+                                            {
+                                                n3r
+                                            } else {
+                                                Float::new(options.prec)
+                                            },
+                                        ) + Complex::with_val_imag(
+                                            options.prec,
+                                            if n1.imag().is_sign_positive() {
+                                                Constant::Infinity
+                                            } else {
+                                                Constant::NegInfinity
+                                            },
+                                        )
+                                    }
+                                    (false, false) => {
+                                        Complex::with_val(options.prec, Constant::Nan)
+                                    }
+                                }
+                            }
+                        },
+                        units,
+                    ))
                 }
-                Ok(n)
+                let mut v = Vector(vec);
+                v.set_prec(oop);
+                Ok(v)
             }
+            (_, _) => Err("unsupported lim data"),
         }
-        Vector(mut sum) => {
-            let yunits = sum[0].units;
-            if nth % 2 == 1 {
-                for n in sum.iter_mut() {
-                    n.number *= -1;
-                }
-            }
-            for k in 0..nth {
-                let b = num.clone().binomial(k);
-                let vec = do_math_with_var(
+    } else {
+        let point = Number::from(point, xunits);
+        match side {
+            LimSide::Left => limsided(func, func_vars, options, var, point, false),
+            LimSide::Right => limsided(func, func_vars, options, var, point, true),
+            LimSide::Both => {
+                let left = limsided(
                     func.clone(),
-                    options,
                     func_vars.clone(),
-                    &var,
-                    NumStr::new(Number::from(point.clone() + h.clone() * (nth - k), units)),
-                )?
-                .vec()?;
-                if k % 2 == 0 {
-                    for (n, a) in sum.iter_mut().zip(vec) {
-                        n.number += a.number * b.clone()
-                    }
-                } else {
-                    for (n, a) in sum.iter_mut().zip(vec) {
-                        n.number -= a.number * b.clone()
+                    options,
+                    var.clone(),
+                    point.clone(),
+                    false,
+                )?;
+                let right = limsided(func, func_vars, options, var, point, true)?;
+                match (left, right) {
+                    (Num(left), Num(right)) => {
+                        let units = left.units;
+                        let left = left.number;
+                        let right = right.number;
+                        if (((left.real().is_infinite()
+                            && right.real().is_infinite()
+                            && (left.imag().clone() - right.imag().clone())
+                                .abs()
+                                .clone()
+                                .log2()
+                                < options.prec as i32 / -16)
+                            || (left.imag().is_infinite()
+                                && right.imag().is_infinite()
+                                && (left.real().clone() - right.real().clone())
+                                    .abs()
+                                    .clone()
+                                    .log2()
+                                    < options.prec as i32 / -16))
+                            && left.real().is_sign_positive() == right.real().is_sign_positive()
+                            && left.imag().is_sign_positive() == right.imag().is_sign_positive())
+                            || (left.clone() - right.clone()).abs().real().clone().log2()
+                                < options.prec as i32 / -16
+                        {
+                            let mut n = NumStr::new(Number::from((left + right) / 2, units));
+                            n.set_prec(oop);
+                            Ok(n)
+                        } else {
+                            Ok(NumStr::new(Number::from(
+                                Complex::with_val(options.prec, Constant::Nan),
+                                None,
+                            )))
+                        }
                     }
-                }
-            }
-            if sum.len() == 2 {
-                let mut n = NumStr::new(Number::from(
-                    sum[1].number.clone() / sum[0].number.clone(),
-                    match (yunits, units) {
-                        (Some(a), Some(b)) => Some(a.div(&b)),
-                        (Some(a), None) => Some(a),
-                        (None, Some(b)) => Some(Units::default().div(&b)),
-                        (None, None) => None,
-                    },
-                ));
-                if oop != 0 {
-                    n.set_prec(oop)
-                }
-                Ok(n)
-            } else {
-                let nf = &sum.last().unwrap().number;
-                let mut v = Vector(
-                    sum[0..sum.len().saturating_sub(1)]
-                        .iter()
-                        .map(|n| {
-                            Number::from(
-                                nf.clone() / n.number.clone(),
-                                match (yunits, units) {
-                                    (Some(a), Some(b)) => Some(a.div(&b)),
-                                    (Some(a), None) => Some(a),
-                                    (None, Some(b)) => Some(Units::default().div(&b)),
-                                    (None, None) => None,
-                                },
-                            )
-                        })
-                        .collect::<Vec<Number<Integer, Float, Complex>>>(),
-                );
-                if oop != 0 {
-                    v.set_prec(oop)
-                }
-                Ok(v)
-            }
-        }
-        _ => Err("not supported slope data"),
-    }
-}
-#[derive(Copy, Clone, PartialEq)]
-pub enum LimSide {
-    Left,
-    Right,
-    Both,
-}
-#[allow(clippy::type_complexity)]
-pub fn limit<
-    Integer: crate::types::Integer<Float, Complex>,
-    Float: crate::types::Float<Integer, Complex>,
-    Complex: crate::types::Complex<Integer, Float>,
->(
-    mut func: Vec<NumStr<Integer, Float, Complex>>,
-    mut func_vars: Vec<(String, Vec<NumStr<Integer, Float, Complex>>)>,
-    mut options: Options,
-    var: String,
-    point: Number<Integer, Float, Complex>,
-    side: LimSide,
-) -> Result<NumStr<Integer, Float, Complex>, &'static str> {
-    let xunits = point.units;
-    let mut point = point.number;
-    let oop = options.prec;
-    options.prec = options.prec.clamp(256, 1024);
-    if oop != options.prec {
-        point.set_prec(options.prec);
-        set_prec(&mut func, &mut func_vars, options.prec);
-    }
-    if point.clone().real().is_infinite() || point.clone().imag().is_infinite() {
-        let (h1, h2);
-        let positive = point.real().is_sign_positive();
-        if positive {
-            h1 = Complex::with_val(options.prec, 2).pow(options.prec / 4);
-            h2 = Complex::with_val(options.prec, 2).pow((options.prec / 3) as f64 + 7.0 / 0.94) - 3;
-        } else {
-            h1 = -Complex::with_val(options.prec, 2).pow(options.prec / 4);
-            h2 =
-                -Complex::with_val(options.prec, 2).pow((options.prec / 3) as f64 + 7.0 / 0.94) + 3;
-        }
-        let n1 = do_math_with_var(
-            func.clone(),
-            options,
-            func_vars.clone(),
-            &var,
-            NumStr::new(Number::from(h1, xunits)),
-        )?;
-        let n2 = do_math_with_var(
-            func.clone(),
-            options,
-            func_vars.clone(),
-            &var,
-            NumStr::new(Number::from(h2, xunits)),
-        )?;
-        match (n1, n2) {
-            (Num(n1), Num(n2)) => {
-                let units = n1.units;
-                let n1 = n1.number;
-                let n2 = n2.number;
-                let mut n = if (n1.clone() - n2.clone()).abs().real().clone().log2()
-                    < options.prec as i32 / -16
-                {
-                    NumStr::new(Number::from(n2, units))
-                } else if n1.real().is_sign_positive() != n2.real().is_sign_positive()
-                    || n1.imag().is_sign_positive() != n2.imag().is_sign_positive()
+                    (Vector(left), Vector(right)) => {
+                        let mut vec = Vec::with_capacity(left.len().max(1));
+                        for (left, right) in left.iter().zip(right) {
+                            let units = left.units;
+                            let left = &left.number;
+                            let right = &right.number;
+                            vec.push(
+                                if (((left.real().is_infinite()
+                                    && right.real().is_infinite()
+                                    && (left.imag().clone() - right.imag().clone())
+                                        .abs()
+                                        .clone()
+                                        .log2()
+                                        < options.prec as i32 / -16)
+                                    || (left.imag().is_infinite()
+                                        && right.imag().is_infinite()
+                                        && (left.real().clone() - right.real().clone())
+                                            .abs()
+                                            .clone()
+                                            .log2()
+                                            < options.prec as i32 / -16))
+                                    && left.real().is_sign_positive()
+                                        == right.real().is_sign_positive()
+                                    && left.imag().is_sign_positive()
+                                        == right.imag().is_sign_positive())
+                                    || (left.clone() - right.clone()).abs().real().clone().log2()
+                                        < options.prec as i32 / -16
+                                {
+                                    Number::from((left.clone() + right) / 2, units)
+                                } else {
+                                    Number::from(
+                                        Complex::with_val(options.prec, Constant::Nan),
+                                        None,
+                                    )
+                                },
+                            )
+                        }
+                        let mut v = Vector(vec);
+                        v.set_prec(options.prec);
+                        Ok(v)
+                    }
+                    (_, _) => Err("lim err"),
+                }
+            }
+        }
+    }
+}
+#[allow(clippy::type_complexity)]
+fn limsided<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    func: Vec<NumStr<Integer, Float, Complex>>,
+    func_vars: Vec<(String, Vec<NumStr<Integer, Float, Complex>>)>,
+    options: Options,
+    var: String,
+    point: Number<Integer, Float, Complex>,
+    right: bool,
+) -> Result<NumStr<Integer, Float, Complex>, &'static str> {
+    let xunits = point.units;
+    let point = point.number;
+    let h1 = Float::with_val(options.prec, 0.5).pow(options.prec / 4);
+    let h2 = Float::with_val(options.prec, 0.5).pow((options.prec / 3) as f64 + 7.0 / 0.94);
+    let n1 = do_math_with_var(
+        func.clone(),
+        options,
+        func_vars.clone(),
+        &var,
+        NumStr::new(Number::from(
+            point.clone() + if right { h1 } else { -h1 },
+            xunits,
+        )),
+    )?;
+    let n2 = do_math_with_var(
+        func.clone(),
+        options,
+        func_vars.clone(),
+        &var,
+        NumStr::new(Number::from(
+            point.clone() + if right { h2 } else { -h2 },
+            xunits,
+        )),
+    )?;
+    match (n1, n2) {
+        (Num(n1), Num(n2)) => {
+            let units = n1.units;
+            let n1 = n1.number;
+            let n2 = n2.number;
+            Ok(NumStr::new(Number::from(
+                if (n2.clone() - n1.clone()).abs().real().clone().log2() < options.prec as i32 / -16
                 {
-                    NumStr::new(Number::from(
-                        Complex::with_val(options.prec, Constant::Nan),
-                        None,
-                    ))
-                } else if n2.real().is_infinite() || n2.imag().is_infinite() {
-                    NumStr::new(Number::from(
-                        match (n2.real().is_infinite(), n2.imag().is_infinite()) {
+                    n1
+                } else {
+                    let h3 = Complex::with_val(options.prec, 0.5)
+                        .pow((options.prec / 2) as f64 + 13.0 / 0.7);
+                    let n3 = do_math_with_var(
+                        func.clone(),
+                        options,
+                        func_vars.clone(),
+                        &var,
+                        NumStr::new(Number::from(
+                            point.clone() + if right { h3 } else { -h3 },
+                            xunits,
+                        )),
+                    )?
+                    .num()?
+                    .number;
+                    let sign = n1.real().is_sign_positive() == n2.real().is_sign_positive()
+                        && n2.real().is_sign_positive() == n3.real().is_sign_positive()
+                        && n1.imag().is_sign_positive() == n2.imag().is_sign_positive()
+                        && n2.imag().is_sign_positive() == n3.imag().is_sign_positive();
+                    let n1r = n1.real().clone().abs();
+                    let n2r = n2.real().clone().abs();
+                    let n3r = n3.real().clone().abs();
+                    let n1i = n1.imag().clone().abs();
+                    let n2i = n2.imag().clone().abs();
+                    let n3i = n3.imag().clone().abs();
+                    if !sign {
+                        Complex::with_val(options.prec, Constant::Nan)
+                    } else {
+                        match (n3r > n2r && n2r > n1r, n3i > n2i && n2i > n1i) {
                             (true, true) => {
                                 match (n1.real().is_sign_positive(), n1.imag().is_sign_positive()) {
                                     (true, true) => Complex::with_val(
@@ -5861,131 +10452,52 @@ pub fn limit<
                                 )
                             }
                             (false, false) => Complex::with_val(options.prec, Constant::Nan),
-                        },
-                        units,
-                    ))
-                } else {
-                    let n3 = do_math_with_var(
-                        func.clone(),
-                        options,
-                        func_vars.clone(),
-                        &var,
-                        NumStr::new(Number::from(
-                            if positive {
-                                Complex::with_val(options.prec, 2)
-                                    .pow((options.prec / 2) as f64 + 13.0 / 0.7)
-                                    - 7
-                            } else {
-                                -Complex::with_val(options.prec, 2)
-                                    .pow((options.prec / 2) as f64 + 13.0 / 0.7)
-                                    + 7
-                            },
-                            xunits,
-                        )),
-                    )?
-                    .num()?
-                    .number;
-                    let sign = n2.real().is_sign_positive() == n3.real().is_sign_positive()
-                        && n2.imag().is_sign_positive() == n3.imag().is_sign_positive();
-                    let n1r = n1.real().clone().abs();
-                    let n2r = n2.real().clone().abs();
-                    let n3r = n3.real().clone().abs();
-                    let n1i = n1.imag().clone().abs();
-                    let n2i = n2.imag().clone().abs();
-                    let n3i = n3.imag().clone().abs();
-                    NumStr::new(Number::from(
-                        if !sign {
-                            Complex::with_val(options.prec, Constant::Nan)
-                        } else {
-                            match (n3r > n2r && n2r > n1r, n3i > n2i && n2i > n1i) {
-                                (true, true) => {
-                                    match (
-                                        n1.real().is_sign_positive(),
-                                        n1.imag().is_sign_positive(),
-                                    ) {
-                                        (true, true) => Complex::with_val(
-                                            options.prec,
-                                            (Constant::Infinity, Constant::Infinity),
-                                        ),
-                                        (true, false) => Complex::with_val(
-                                            options.prec,
-                                            (Constant::Infinity, Constant::NegInfinity),
-                                        ),
-                                        (false, true) => Complex::with_val(
-                                            options.prec,
-                                            (Constant::NegInfinity, Constant::Infinity),
-                                        ),
-                                        (false, false) => -Complex::with_val(
-                                            options.prec,
-                                            (Constant::Infinity, Constant::Infinity),
-                                        ),
-                                    }
-                                }
-                                (true, false) => {
-                                    Complex::with_val(
-                                        options.prec,
-                                        if n1.real().is_sign_positive() {
-                                            Constant::Infinity
-                                        } else {
-                                            Constant::NegInfinity
-                                        },
-                                    ) + Complex::with_val_imag(
-                                        options.prec,
-                                        if (n2i - n3i.clone()).abs().log2()
-                                            < options.prec as i32 / -16
-                                        {
-                                            n3i
-                                        } else {
-                                            Float::new(options.prec)
-                                        },
-                                    )
-                                }
-                                (false, true) => {
-                                    Complex::with_val(
-                                        options.prec,
-                                        if (n2r - n3r.clone()).abs().log2()
-                                            < options.prec as i32 / -16
-                                        {
-                                            n3r
-                                        } else {
-                                            Float::new(options.prec)
-                                        },
-                                    ) + Complex::with_val_imag(
-                                        options.prec,
-                                        if n1.imag().is_sign_positive() {
-                                            Constant::Infinity
-                                        } else {
-                                            Constant::NegInfinity
-                                        },
-                                    )
-                                }
-                                (false, false) => Complex::with_val(options.prec, Constant::Nan),
-                            }
-                        },
-                        units,
-                    ))
-                };
-                n.set_prec(oop);
-                Ok(n)
-            }
-            (Vector(v1), Vector(v2)) => {
-                let mut v3: Vec<Number<Integer, Float, Complex>> = Vec::new();
-                let mut vec = Vec::with_capacity(v1.len().max(1));
-                for (i, (n1, n2)) in v1.iter().zip(v2).enumerate() {
-                    let units = n1.units;
-                    let n1 = n1.number.clone();
-                    let n2 = n2.number.clone();
-                    vec.push(Number::from(
-                        if (n1.clone() - n2.clone()).abs().real().clone().log2()
-                            < options.prec as i32 / -16
-                        {
-                            n2
-                        } else if n1.real().is_sign_positive() != n2.real().is_sign_positive()
-                            || n1.imag().is_sign_positive() != n2.imag().is_sign_positive()
-                        {
+                        }
+                    }
+                },
+                units,
+            )))
+        }
+        (Vector(n1), Vector(n2)) => {
+            let mut n3: Vec<Number<Integer, Float, Complex>> = Vec::new();
+            let mut vec = Vec::with_capacity(n1.len().max(1));
+            for (i, (n1, n2)) in n1.iter().zip(n2).enumerate() {
+                let units = n1.units;
+                let n1 = &n1.number;
+                let n2 = &n2.number;
+                vec.push(Number::from(
+                    if (n2.clone() - n1.clone()).abs().real().clone().log2()
+                        < options.prec as i32 / -16
+                    {
+                        n1.clone()
+                    } else {
+                        if n3.is_empty() {
+                            let h3 = Complex::with_val(options.prec, 0.5)
+                                .pow((options.prec / 2) as f64 + 13.0 / 0.7);
+                            n3 = do_math_with_var(
+                                func.clone(),
+                                options,
+                                func_vars.clone(),
+                                &var,
+                                NumStr::new(Number::from(point.clone() - h3.clone(), xunits)),
+                            )?
+                            .vec()?;
+                        }
+                        let n3 = &n3[i].number;
+                        let sign = n1.real().is_sign_positive() == n2.real().is_sign_positive()
+                            && n2.real().is_sign_positive() == n3.real().is_sign_positive()
+                            && n1.imag().is_sign_positive() == n2.imag().is_sign_positive()
+                            && n2.imag().is_sign_positive() == n3.imag().is_sign_positive();
+                        let n1r = n1.real().clone().abs();
+                        let n2r = n2.real().clone().abs();
+                        let n3r = n3.real().clone().abs();
+                        let n1i = n1.imag().clone().abs();
+                        let n2i = n2.imag().clone().abs();
+                        let n3i = n3.imag().clone().abs();
+                        if !sign {
                             Complex::with_val(options.prec, Constant::Nan)
-                        } else if n2.real().is_infinite() || n2.imag().is_infinite() {
-                            match (n2.real().is_infinite(), n2.imag().is_infinite()) {
+                        } else {
+                            match (n3r > n2r && n2r > n1r, n3i > n2i && n2i > n1i) {
                                 (true, true) => {
                                     match (
                                         n1.real().is_sign_positive(),
@@ -6019,8 +10531,10 @@ pub fn limit<
                                         },
                                     ) + Complex::with_val_imag(
                                         options.prec,
-                                        if (n1.imag().clone() - n2.imag()).abs().log10() <= -10 {
-                                            n2.imag().clone()
+                                        if (n2i - n3i.clone()).abs().log2()
+                                            < options.prec as i32 / -16
+                                        {
+                                            n3i
                                         } else {
                                             Float::new(options.prec)
                                         },
@@ -6029,8 +10543,10 @@ pub fn limit<
                                 (false, true) => {
                                     Complex::with_val(
                                         options.prec,
-                                        if (n1.real().clone() - n2.real()).abs().log10() <= -10 {
-                                            n2.real().clone()
+                                        if (n2r - n3r.clone()).abs().log2()
+                                            < options.prec as i32 / -16
+                                        {
+                                            n3r
                                         } else {
                                             Float::new(options.prec)
                                         },
@@ -6045,606 +10561,1204 @@ pub fn limit<
                                 }
                                 (false, false) => Complex::with_val(options.prec, Constant::Nan),
                             }
-                        } else {
-                            if v3.is_empty() {
-                                v3 = do_math_with_var(
-                                    func.clone(),
-                                    options,
-                                    func_vars.clone(),
-                                    &var,
-                                    NumStr::new(Number::from(
-                                        if positive {
-                                            Complex::with_val(options.prec, 2)
-                                                .pow((options.prec / 2) as f64 + 13.0 / 0.7)
-                                                - 7
-                                        } else {
-                                            -Complex::with_val(options.prec, 2)
-                                                .pow((options.prec / 2) as f64 + 13.0 / 0.7)
-                                                + 7
-                                        },
-                                        xunits,
-                                    )),
-                                )?
-                                .vec()?;
-                            }
-                            let v3 = &v3[i].number;
-                            let sign = n2.real().is_sign_positive() == v3.real().is_sign_positive()
-                                && n2.imag().is_sign_positive() == v3.imag().is_sign_positive();
-                            let n1r = n1.real().clone().abs();
-                            let n2r = n2.real().clone().abs();
-                            let n3r = v3.real().clone().abs();
-                            let n1i = n1.imag().clone().abs();
-                            let n2i = n2.imag().clone().abs();
-                            let n3i = v3.imag().clone().abs();
-                            if !sign {
-                                Complex::with_val(options.prec, Constant::Nan)
-                            } else {
-                                match (n3r > n2r && n2r > n1r, n3i > n2i && n2i > n1i) {
-                                    (true, true) => {
-                                        match (
-                                            n1.real().is_sign_positive(),
-                                            n1.imag().is_sign_positive(),
-                                        ) {
-                                            (true, true) => Complex::with_val(
-                                                options.prec,
-                                                (Constant::Infinity, Constant::Infinity),
-                                            ),
-                                            (true, false) => Complex::with_val(
-                                                options.prec,
-                                                (Constant::Infinity, Constant::NegInfinity),
-                                            ),
-                                            (false, true) => Complex::with_val(
-                                                options.prec,
-                                                (Constant::NegInfinity, Constant::Infinity),
-                                            ),
-                                            (false, false) => -Complex::with_val(
-                                                options.prec,
-                                                (Constant::Infinity, Constant::Infinity),
-                                            ),
-                                        }
-                                    }
-                                    (true, false) => {
-                                        Complex::with_val(
-                                            options.prec,
-                                            if n1.real().is_sign_positive() {
-                                                Constant::Infinity
-                                            } else {
-                                                Constant::NegInfinity
-                                            },
-                                        ) + Complex::with_val_imag(
-                                            options.prec,
-                                            if (n2i - n3i.clone()).abs().log2()
-                                                < options.prec as i32 / -16
-                                            {
-                                                n3i
-                                            } else {
-                                                Float::new(options.prec)
-                                            },
-                                        )
-                                    }
-                                    (false, true) => {
-                                        Complex::with_val(
-                                            options.prec,
-                                            if (n2r - n3r.clone()).abs().log2()
-                                                < options.prec as i32 / -16
-                                            {
-                                                n3r
-                                            } else {
-                                                Float::new(options.prec)
-                                            },
-                                        ) + Complex::with_val_imag(
-                                            options.prec,
-                                            if n1.imag().is_sign_positive() {
-                                                Constant::Infinity
-                                            } else {
-                                                Constant::NegInfinity
-                                            },
-                                        )
-                                    }
-                                    (false, false) => {
-                                        Complex::with_val(options.prec, Constant::Nan)
-                                    }
-                                }
-                            }
-                        },
-                        units,
-                    ))
-                }
-                let mut v = Vector(vec);
-                v.set_prec(oop);
-                Ok(v)
+                        }
+                    },
+                    units,
+                ))
+            }
+            Ok(Vector(vec))
+        }
+        (_, _) => Err("unsupported lim data"),
+    }
+}
+//https://github.com/IstvanMezo/LambertW-function/blob/master/complex%20Lambert.cpp
+pub fn lambertw<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    z: Complex,
+    k: Integer,
+) -> Complex {
+    if z.is_zero() {
+        return if k == 0 {
+            Complex::new(z.prec())
+        } else {
+            -Complex::with_val(z.prec(), Constant::Infinity)
+        };
+    }
+    if z.imag().is_zero() && (k == 0 || k == -1) {
+        let e: Float = -Float::with_val(z.prec(), -1).exp();
+        if z.real() == &e {
+            return Complex::with_val(z.prec(), -1);
+        }
+    }
+    let mut w = initpoint(z.clone(), k);
+    for _ in 0..(z.prec() / 64).max(8) {
+        let zexp = w.clone().exp();
+        let zexpz = zexp.clone() * &w;
+        let zexpz_d = zexpz.clone() + &zexp;
+        let zexpz_dd = (zexp * 2) + &zexpz;
+        w -=
+            ((zexpz.clone() - &z) * &zexpz_d) / ((sqr(zexpz_d) * 2) - ((zexpz - &z) * zexpz_dd)) * 2
+    }
+    w
+}
+fn initpoint<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    z: Complex,
+    k: Integer,
+) -> Complex {
+    let prec = z.prec();
+    {
+        let e = Float::with_val(prec, -1).exp();
+        let test: Complex = z.clone() + &e;
+        if test.abs().real() <= &1.005 {
+            if k == 0 && z.real() > &-0.5 {
+                let p1: Complex = z / e * 2 + 2;
+                let p = p1.clone().sqrt();
+                return p.clone() - (p1 / 3) + ((cube(p) * 11) / 72) - 1;
+            } else if k == -1 && z.real().is_sign_negative() && z.imag().is_zero() {
+                let p1: Complex = z / e * 2 + 2;
+                let p = p1.clone().sqrt();
+                return -p.clone() - (p1 / 3) - ((cube(p) * 11) / 72) - 1;
+            }
+        }
+    }
+    {
+        let test: Complex = z.clone() - 0.5;
+        if test.abs().real() <= &0.6 {
+            if k == 0 {
+                return ((z.clone() * 7.061302897 + 0.1237166) * 0.35173371)
+                    / ((z * 2 + 1) * 0.827184 + 2);
+            } else if k == -1 {
+                return (Complex::with_val(prec, (-2.2591588985, -4.22096))
+                    * (Complex::with_val(prec, (-14.073271, -33.767687754)) * &z
+                        + Complex::with_val(prec, (-12.7127, 19.071643)) * (z.clone() * 2 + 1)))
+                    / (Complex::with_val(prec, (-17.23103, 10.629721)) * (z * 2 + 1) + 2);
+            }
+        }
+    }
+    let zln = z.ln() + Complex::with_val_imag(prec, Float::with_val(prec, Constant::Pi) * k * 2);
+    zln.clone() - zln.ln()
+}
+#[cfg(feature = "fastrand")]
+pub fn rand_gamma<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    k: Float,
+    t: Float,
+) -> Float {
+    let prec = k.prec();
+    let mut sum = Float::new(prec);
+    for _ in 1..=k
+        .clone()
+        .floor()
+        .to_integer()
+        .unwrap_or_default()
+        .to_usize()
+        .unwrap_or_default()
+    {
+        let u: Float = Float::with_val(prec, fastrand::u128(1..)) / u128::MAX;
+        sum += u.ln();
+    }
+    let s = k.clone().fract();
+    let e = Float::with_val(prec, 1).exp();
+    let check = e.clone() / (e + s.clone());
+    let mut eta: Float;
+    loop {
+        let u: Float = Float::with_val(prec, fastrand::u128(1..)) / u128::MAX;
+        let v: Float = Float::with_val(prec, fastrand::u128(1..)) / u128::MAX;
+        let w: Float = Float::with_val(prec, fastrand::u128(1..)) / u128::MAX;
+        let n;
+        if u <= check {
+            eta = v.pow(s.clone().recip());
+            n = w * eta.clone().pow(s.clone() - 1);
+        } else {
+            eta = -v.ln() + 1;
+            n = w * (-eta.clone()).exp();
+        };
+        if n <= eta.clone().pow(s.clone() - 1) * (-eta.clone()).exp() {
+            break;
+        }
+    }
+    let f: Float = eta - sum;
+    t * f
+}
+#[cfg(feature = "fastrand")]
+pub fn rand_norm<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    m: Complex,
+    s: Complex,
+) -> Complex {
+    let prec = s.prec();
+    let mut u: Float = Float::with_val(prec, fastrand::i128(i128::MIN + 2..i128::MAX)) / i128::MAX;
+    let mut v: Float = Float::with_val(prec, fastrand::i128(i128::MIN + 2..i128::MAX)) / i128::MAX;
+    let mut g: Float = u.clone().pow(2) + v.pow(2);
+    while g >= 1 {
+        u = Float::with_val(prec, fastrand::i128(i128::MIN + 2..i128::MAX)) / i128::MAX;
+        v = Float::with_val(prec, fastrand::i128(i128::MIN + 2..i128::MAX)) / i128::MAX;
+        g = u.clone().pow(2) + v.pow(2);
+    }
+    let d: Float = g.clone().ln() / g * -2i32;
+    let z: Float = u * d.sqrt();
+    m + z * s
+}
+// samples the named distribution by inversion or from its relation to the gamma and normal
+#[cfg(feature = "fastrand")]
+pub fn rand_dist<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    dist: &str,
+    args: &[Float],
+) -> Result<Float, &'static str> {
+    let prec = args[0].prec();
+    let chi2 = |k: &Float| rand_gamma(k.clone() / 2, Float::with_val(prec, 2));
+    Ok(match dist {
+        "t" => {
+            let z = rand_norm(Complex::new(prec), Complex::with_val(prec, 1))
+                .real()
+                .clone();
+            z / (chi2(&args[0]) / &args[0]).sqrt()
+        }
+        "chi2" => chi2(&args[0]),
+        "f" if args.len() > 1 => (chi2(&args[0]) / &args[0]) / (chi2(&args[1]) / &args[1]),
+        _ => {
+            let u: Float = Float::with_val(prec, fastrand::u128(1..u128::MAX)) / u128::MAX;
+            quantile(dist, u, args)?
+        }
+    })
+}
+// probability vector normalized to sum to one
+fn normalized<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    p: &[Float],
+) -> Result<Vec<Float>, &'static str> {
+    if p.is_empty() || p.iter().any(|p| p.is_sign_negative() && !p.is_zero()) {
+        return Err("invalid probabilities");
+    }
+    let sum: Float = p.iter().fold(Float::new(p[0].prec()), |s, p| s + p);
+    if sum.is_zero() {
+        return Err("invalid probabilities");
+    }
+    Ok(p.iter().map(|p| p.clone() / &sum).collect())
+}
+// (k1+k2+...)!/(k1! k2! ...) through the gamma function
+pub fn multinomial<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    k: &[Complex],
+) -> Complex {
+    let prec = k.first().map(|k| k.prec()).unwrap_or(512);
+    let mut numerator = Complex::with_val(prec, 1);
+    let mut divisor = Complex::with_val(prec, 1);
+    for k in k {
+        numerator += k;
+        divisor *= gamma(k.clone() + 1);
+    }
+    gamma(numerator) / divisor
+}
+// pmf and pdf of the multinomial, categorical and dirichlet distributions
+pub fn multivariate_pdf<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    dist: &str,
+    x: &[Float],
+    p: &[Float],
+) -> Result<Float, &'static str> {
+    let prec = p.first().ok_or("invalid probabilities")?.prec();
+    let c = |f: &Float| Complex::with_val(prec, f);
+    let zero = Float::new(prec);
+    Ok(match dist {
+        "multinomial" => {
+            if x.len() != p.len() {
+                return Err("dimension mismatch");
             }
-            (_, _) => Err("unsupported lim data"),
+            let p = normalized(p)?;
+            if x.iter()
+                .any(|x| !x.clone().fract().is_zero() || x.is_sign_negative() && !x.is_zero())
+            {
+                return Ok(zero);
+            }
+            let coefficient = multinomial(&x.iter().map(c).collect::<Vec<Complex>>());
+            x.iter()
+                .zip(p.iter())
+                .fold(coefficient.real().clone(), |s, (x, p)| {
+                    s * p.clone().pow(x.clone())
+                })
         }
-    } else {
-        let point = Number::from(point, xunits);
-        match side {
-            LimSide::Left => limsided(func, func_vars, options, var, point, false),
-            LimSide::Right => limsided(func, func_vars, options, var, point, true),
-            LimSide::Both => {
-                let left = limsided(
-                    func.clone(),
-                    func_vars.clone(),
-                    options,
-                    var.clone(),
-                    point.clone(),
-                    false,
-                )?;
-                let right = limsided(func, func_vars, options, var, point, true)?;
-                match (left, right) {
-                    (Num(left), Num(right)) => {
-                        let units = left.units;
-                        let left = left.number;
-                        let right = right.number;
-                        if (((left.real().is_infinite()
-                            && right.real().is_infinite()
-                            && (left.imag().clone() - right.imag().clone())
-                                .abs()
-                                .clone()
-                                .log2()
-                                < options.prec as i32 / -16)
-                            || (left.imag().is_infinite()
-                                && right.imag().is_infinite()
-                                && (left.real().clone() - right.real().clone())
-                                    .abs()
-                                    .clone()
-                                    .log2()
-                                    < options.prec as i32 / -16))
-                            && left.real().is_sign_positive() == right.real().is_sign_positive()
-                            && left.imag().is_sign_positive() == right.imag().is_sign_positive())
-                            || (left.clone() - right.clone()).abs().real().clone().log2()
-                                < options.prec as i32 / -16
-                        {
-                            let mut n = NumStr::new(Number::from((left + right) / 2, units));
-                            n.set_prec(oop);
-                            Ok(n)
-                        } else {
-                            Ok(NumStr::new(Number::from(
-                                Complex::with_val(options.prec, Constant::Nan),
-                                None,
-                            )))
-                        }
-                    }
-                    (Vector(left), Vector(right)) => {
-                        let mut vec = Vec::with_capacity(left.len().max(1));
-                        for (left, right) in left.iter().zip(right) {
-                            let units = left.units;
-                            let left = &left.number;
-                            let right = &right.number;
-                            vec.push(
-                                if (((left.real().is_infinite()
-                                    && right.real().is_infinite()
-                                    && (left.imag().clone() - right.imag().clone())
-                                        .abs()
-                                        .clone()
-                                        .log2()
-                                        < options.prec as i32 / -16)
-                                    || (left.imag().is_infinite()
-                                        && right.imag().is_infinite()
-                                        && (left.real().clone() - right.real().clone())
-                                            .abs()
-                                            .clone()
-                                            .log2()
-                                            < options.prec as i32 / -16))
-                                    && left.real().is_sign_positive()
-                                        == right.real().is_sign_positive()
-                                    && left.imag().is_sign_positive()
-                                        == right.imag().is_sign_positive())
-                                    || (left.clone() - right.clone()).abs().real().clone().log2()
-                                        < options.prec as i32 / -16
-                                {
-                                    Number::from((left.clone() + right) / 2, units)
-                                } else {
-                                    Number::from(
-                                        Complex::with_val(options.prec, Constant::Nan),
-                                        None,
-                                    )
-                                },
-                            )
-                        }
-                        let mut v = Vector(vec);
-                        v.set_prec(options.prec);
-                        Ok(v)
-                    }
-                    (_, _) => Err("lim err"),
-                }
+        "categorical" => {
+            let p = normalized(p)?;
+            let k = x.first().ok_or("not enough args")?;
+            match k.to_integer().and_then(|k| k.to_usize()) {
+                Some(k) if k < p.len() && x[0].clone().fract().is_zero() => p[k].clone(),
+                _ => zero,
+            }
+        }
+        "dirichlet" => {
+            if x.len() != p.len() {
+                return Err("dimension mismatch");
+            }
+            if x.iter().any(|x| x.is_sign_negative() || x > &1) {
+                return Ok(zero);
+            }
+            // the density lives on the simplex, off it there is nothing
+            let sum: Float = x.iter().fold(zero.clone(), |s, x| s + x);
+            if !negligible(&(sum - 1)) {
+                return Ok(zero);
+            }
+            let a: Float = p.iter().fold(zero.clone(), |s, a| s + a);
+            x.iter()
+                .zip(p.iter())
+                .fold(gamma(c(&a)).real().clone(), |s, (x, a)| {
+                    s * x.clone().pow(a.clone() - 1) / gamma(c(a)).real()
+                })
+        }
+        _ => return Err("unsupported distribution"),
+    })
+}
+// draws of the multinomial (counts of n trials), categorical (index) and dirichlet distributions
+#[cfg(feature = "fastrand")]
+pub fn rand_multivariate<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    dist: &str,
+    p: &[Float],
+    n: usize,
+) -> Result<Vec<Float>, &'static str> {
+    let prec = p.first().ok_or("invalid probabilities")?.prec();
+    let categorical = |p: &[Float]| {
+        let mut u: Float = Float::with_val(prec, fastrand::u128(..)) / u128::MAX;
+        for (i, p) in p.iter().enumerate() {
+            if u < *p {
+                return i;
+            }
+            u -= p
+        }
+        p.len() - 1
+    };
+    Ok(match dist {
+        "multinomial" => {
+            // each count is binomial in the trials the earlier counts left over, with the
+            // probability of its category among the ones not yet drawn
+            let p = normalized(p)?;
+            let mut left = Float::with_val(prec, n);
+            let mut mass = Float::with_val(prec, 1);
+            let mut counts = Vec::with_capacity(p.len());
+            for (i, q) in p.iter().enumerate() {
+                let last = i + 1 == p.len();
+                let q: Float = q.clone() / &mass;
+                let count = if last || q >= 1 {
+                    left.clone()
+                } else if left.is_zero() || !q.is_sign_positive() || q.is_zero() {
+                    Float::new(prec)
+                } else {
+                    rand_dist("binomial", &[left.clone(), q])?
+                };
+                left -= &count;
+                mass -= &p[i];
+                counts.push(count)
+            }
+            counts
+        }
+        "categorical" => vec![Float::with_val(prec, categorical(&normalized(p)?))],
+        "dirichlet" => {
+            let g: Vec<Float> = p
+                .iter()
+                .map(|a| rand_gamma(a.clone(), Float::with_val(prec, 1)))
+                .collect();
+            normalized(&g)?
+        }
+        _ => return Err("unsupported distribution"),
+    })
+}
+// inverts an increasing cdf on [lo, hi] by newton steps, bisecting whenever a step leaves the bracket
+fn inverse_cdf<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    p: &Float,
+    mut lo: Float,
+    mut hi: Float,
+    cdf: impl Fn(&Float) -> Float,
+    pdf: impl Fn(&Float) -> Float,
+) -> Float {
+    let prec = p.prec();
+    let mut x: Float = (lo.clone() + &hi) / 2;
+    let mut last = Float::with_val(prec, f64::INFINITY);
+    for _ in 0..4 * prec {
+        let f = cdf(&x) - p;
+        if f.is_zero() {
+            break;
+        } else if f.is_sign_negative() {
+            lo.clone_from(&x)
+        } else {
+            hi.clone_from(&x)
+        }
+        let newton = x.clone() - f / pdf(&x);
+        let next = if newton.is_finite() && newton > lo && newton < hi {
+            newton
+        } else {
+            (lo.clone() + &hi) / 2
+        };
+        // newton stalls at the rounding error, so stop once steps are tiny and not shrinking
+        let step = (next.clone() - &x).abs();
+        let done = step.is_zero()
+            || (step >= last
+                && step <= (x.clone().abs() + 1) * (Float::with_val(prec, 1) >> (prec / 2)));
+        last = step;
+        x = next;
+        if done {
+            break;
+        }
+    }
+    x
+}
+// smallest k >= lo with cdf(k) >= p, found by doubling then bisecting over the integers
+fn discrete_quantile<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    p: &Float,
+    lo: Float,
+    max: Option<Float>,
+    cdf: impl Fn(&Float) -> Float,
+) -> Float {
+    let prec = p.prec();
+    if *p == 1 {
+        return max.unwrap_or(Float::with_val(prec, f64::INFINITY));
+    } else if cdf(&lo) >= *p {
+        return lo;
+    }
+    let mut step = Float::with_val(prec, 1);
+    let mut hi = lo.clone() + &step;
+    let mut lo = lo;
+    while cdf(&hi) < *p {
+        if max.as_ref().is_some_and(|m| hi >= *m) {
+            return max.unwrap_or(hi);
+        } else if step.clone().log2() > prec {
+            return Float::with_val(prec, f64::INFINITY);
+        }
+        lo.clone_from(&hi);
+        step *= 2;
+        hi += &step;
+    }
+    while hi.clone() - &lo > 1 {
+        let mid: Float = (lo.clone() + &hi) / 2;
+        let mid = mid.floor();
+        if cdf(&mid) >= *p { hi = mid } else { lo = mid }
+    }
+    hi
+}
+// quantile function of the named distribution, args being the parameters after x in its cdf
+pub fn quantile<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    dist: &str,
+    p: Float,
+    args: &[Float],
+) -> Result<Float, &'static str> {
+    let prec = p.prec();
+    if p.is_nan() || p.cmp0() == Some(Ordering::Less) || p > 1 {
+        return Err("probability out of range");
+    }
+    let arg = |n: usize| args.get(n).cloned().ok_or("not enough args");
+    let c = |f: &Float| Complex::with_val(prec, f);
+    let zero = Float::new(prec);
+    let one = Float::with_val(prec, 1);
+    let inf = Float::with_val(prec, f64::INFINITY);
+    let std_norm = |p: &Float| {
+        if p.is_zero() {
+            -inf.clone()
+        } else if *p == 1 {
+            inf.clone()
+        } else {
+            let cdf = |z: &Float| (-z.clone() / Float::with_val(prec, 2).sqrt()).erfc() / 2;
+            let mut lo = -one.clone();
+            while cdf(&lo) > *p && lo.is_finite() {
+                lo *= 2
+            }
+            let mut hi = one.clone();
+            while cdf(&hi) < *p && hi.is_finite() {
+                hi *= 2
+            }
+            let tau = Float::with_val(prec, Constant::Tau).sqrt();
+            inverse_cdf(p, lo, hi, cdf, |z: &Float| {
+                let e: Float = -z.clone() * z / 2;
+                e.exp() / tau.clone()
+            })
+        }
+    };
+    // standard gamma with shape k and unit scale
+    let std_gamma = |p: &Float, k: &Float| {
+        if p.is_zero() {
+            zero.clone()
+        } else if *p == 1 {
+            inf.clone()
+        } else {
+            let g = gamma(c(k));
+            let cdf = |x: &Float| {
+                let q: Complex = -incomplete_gamma(c(k), c(x)) / g.clone() + 1;
+                q.real().clone()
+            };
+            let mut hi = if *k > 1 { k.clone() } else { one.clone() };
+            while cdf(&hi) < *p && hi.is_finite() {
+                hi *= 2
+            }
+            inverse_cdf(p, zero.clone(), hi, cdf, |x: &Float| {
+                (pow_nth(c(x), c(&(k.clone() - 1))) * c(&(-x.clone()).exp()) / g.clone())
+                    .real()
+                    .clone()
+            })
+        }
+    };
+    let std_beta = |p: &Float, a: &Float, b: &Float| {
+        if p.is_zero() {
+            zero.clone()
+        } else if *p == 1 {
+            one.clone()
+        } else {
+            let norm = gamma(c(a)) * gamma(c(b)) / gamma(c(&(a.clone() + b)));
+            inverse_cdf(
+                p,
+                zero.clone(),
+                one.clone(),
+                |x: &Float| regularized_incomplete_beta(c(x), c(a), c(b)).real().clone(),
+                |x: &Float| {
+                    (pow_nth(c(x), c(&(a.clone() - 1)))
+                        * pow_nth(c(&(-x.clone() + 1)), c(&(b.clone() - 1)))
+                        / norm.clone())
+                    .real()
+                    .clone()
+                },
+            )
+        }
+    };
+    Ok(match dist {
+        "norm" => {
+            let z = std_norm(&p);
+            if args.is_empty() {
+                z
+            } else {
+                z * arg(1)? + arg(0)?
+            }
+        }
+        "lognorm" => {
+            let z = std_norm(&p);
+            if args.is_empty() {
+                z.exp()
+            } else {
+                (z * arg(1)? + arg(0)?).exp()
+            }
+        }
+        "gamma" => std_gamma(&p, &arg(0)?) * arg(1)?,
+        "beta" => std_beta(&p, &arg(0)?, &arg(1)?),
+        "t" => {
+            let v = arg(0)?;
+            let half = Float::with_val(prec, 0.5);
+            if p == 0.5 {
+                zero
+            } else if p < 0.5 {
+                let y = std_beta(&(p * 2), &(v.clone() / 2), &half);
+                let r: Float = v * (-y.clone() + 1) / y;
+                -r.sqrt()
+            } else {
+                let y = std_beta(&((-p + 1) * 2), &(v.clone() / 2), &half);
+                let r: Float = v * (-y.clone() + 1) / y;
+                r.sqrt()
             }
         }
-    }
+        "chi2" => std_gamma(&p, &(arg(0)? / 2)) * 2,
+        "f" => {
+            let (d1, d2) = (arg(0)?, arg(1)?);
+            let y = std_beta(&p, &(d1.clone() / 2), &(d2.clone() / 2));
+            d2 * y.clone() / (d1 * (-y + 1))
+        }
+        "exponential" => {
+            let q: Float = -p + 1;
+            -q.ln() / arg(0)?
+        }
+        "uniform" => {
+            let a = arg(0)?;
+            (arg(1)? - &a) * p + a
+        }
+        "weibull" => {
+            let k = arg(0)?;
+            let q: Float = -p + 1;
+            let l: Float = -q.ln();
+            l.pow(k.recip()) * arg(1)?
+        }
+        "cauchy" => {
+            if p.is_zero() {
+                -inf
+            } else if p == 1 {
+                inf
+            } else {
+                let x: Float = (p - 0.5) * Float::with_val(prec, Constant::Pi);
+                x.tan() * arg(1)? + arg(0)?
+            }
+        }
+        "laplace" => {
+            let (m, b) = (arg(0)?, arg(1)?);
+            if p < 0.5 {
+                let q: Float = p * 2;
+                m + b * q.ln()
+            } else {
+                let q: Float = -p * 2 + 2;
+                m - b * q.ln()
+            }
+        }
+        "binomial" => {
+            let (n, q) = (arg(0)?, arg(1)?);
+            discrete_quantile(&p, zero.clone(), Some(n.clone()), |k: &Float| {
+                if *k >= n {
+                    one.clone()
+                } else {
+                    regularized_incomplete_beta(
+                        c(&(-q.clone() + 1)),
+                        c(&(n.clone() - k)),
+                        c(&(k.clone() + 1)),
+                    )
+                    .real()
+                    .clone()
+                }
+            })
+        }
+        "poisson" => {
+            let l = arg(0)?;
+            discrete_quantile(&p, zero.clone(), None, |k: &Float| {
+                let k = c(&(k.clone() + 1));
+                (incomplete_gamma(k.clone(), c(&l)) / gamma(k))
+                    .real()
+                    .clone()
+            })
+        }
+        "geometric" => {
+            let q: Float = -arg(0)? + 1;
+            discrete_quantile(&p, one.clone(), None, |k: &Float| {
+                -q.clone().pow(k.clone()) + 1
+            })
+        }
+        "neg_binomial" => {
+            let (r, q) = (arg(0)?, arg(1)?);
+            discrete_quantile(&p, zero.clone(), None, |k: &Float| {
+                regularized_incomplete_beta(c(&q), c(&r), c(&(k.clone() + 1)))
+                    .real()
+                    .clone()
+            })
+        }
+        _ => return Err("unsupported distribution"),
+    })
 }
-#[allow(clippy::type_complexity)]
-fn limsided<
+// density of the named distribution at x, args being its parameters
+pub fn pdf<
     Integer: crate::types::Integer<Float, Complex>,
     Float: crate::types::Float<Integer, Complex>,
     Complex: crate::types::Complex<Integer, Float>,
 >(
-    func: Vec<NumStr<Integer, Float, Complex>>,
-    func_vars: Vec<(String, Vec<NumStr<Integer, Float, Complex>>)>,
-    options: Options,
-    var: String,
-    point: Number<Integer, Float, Complex>,
-    right: bool,
-) -> Result<NumStr<Integer, Float, Complex>, &'static str> {
-    let xunits = point.units;
-    let point = point.number;
-    let h1 = Float::with_val(options.prec, 0.5).pow(options.prec / 4);
-    let h2 = Float::with_val(options.prec, 0.5).pow((options.prec / 3) as f64 + 7.0 / 0.94);
-    let n1 = do_math_with_var(
-        func.clone(),
-        options,
-        func_vars.clone(),
-        &var,
-        NumStr::new(Number::from(
-            point.clone() + if right { h1 } else { -h1 },
-            xunits,
-        )),
-    )?;
-    let n2 = do_math_with_var(
-        func.clone(),
-        options,
-        func_vars.clone(),
-        &var,
-        NumStr::new(Number::from(
-            point.clone() + if right { h2 } else { -h2 },
-            xunits,
-        )),
-    )?;
-    match (n1, n2) {
-        (Num(n1), Num(n2)) => {
-            let units = n1.units;
-            let n1 = n1.number;
-            let n2 = n2.number;
-            Ok(NumStr::new(Number::from(
-                if (n2.clone() - n1.clone()).abs().real().clone().log2() < options.prec as i32 / -16
-                {
-                    n1
-                } else {
-                    let h3 = Complex::with_val(options.prec, 0.5)
-                        .pow((options.prec / 2) as f64 + 13.0 / 0.7);
-                    let n3 = do_math_with_var(
-                        func.clone(),
-                        options,
-                        func_vars.clone(),
-                        &var,
-                        NumStr::new(Number::from(
-                            point.clone() + if right { h3 } else { -h3 },
-                            xunits,
-                        )),
-                    )?
-                    .num()?
-                    .number;
-                    let sign = n1.real().is_sign_positive() == n2.real().is_sign_positive()
-                        && n2.real().is_sign_positive() == n3.real().is_sign_positive()
-                        && n1.imag().is_sign_positive() == n2.imag().is_sign_positive()
-                        && n2.imag().is_sign_positive() == n3.imag().is_sign_positive();
-                    let n1r = n1.real().clone().abs();
-                    let n2r = n2.real().clone().abs();
-                    let n3r = n3.real().clone().abs();
-                    let n1i = n1.imag().clone().abs();
-                    let n2i = n2.imag().clone().abs();
-                    let n3i = n3.imag().clone().abs();
-                    if !sign {
-                        Complex::with_val(options.prec, Constant::Nan)
-                    } else {
-                        match (n3r > n2r && n2r > n1r, n3i > n2i && n2i > n1i) {
-                            (true, true) => {
-                                match (n1.real().is_sign_positive(), n1.imag().is_sign_positive()) {
-                                    (true, true) => Complex::with_val(
-                                        options.prec,
-                                        (Constant::Infinity, Constant::Infinity),
-                                    ),
-                                    (true, false) => Complex::with_val(
-                                        options.prec,
-                                        (Constant::Infinity, Constant::NegInfinity),
-                                    ),
-                                    (false, true) => Complex::with_val(
-                                        options.prec,
-                                        (Constant::NegInfinity, Constant::Infinity),
-                                    ),
-                                    (false, false) => -Complex::with_val(
-                                        options.prec,
-                                        (Constant::Infinity, Constant::Infinity),
-                                    ),
-                                }
-                            }
-                            (true, false) => {
-                                Complex::with_val(
-                                    options.prec,
-                                    if n1.real().is_sign_positive() {
-                                        Constant::Infinity
-                                    } else {
-                                        Constant::NegInfinity
-                                    },
-                                ) + Complex::with_val_imag(
-                                    options.prec,
-                                    if (n1.imag().clone() - n2.imag()).abs().log2()
-                                        < options.prec as i32 / -16
-                                    {
-                                        n2.imag().clone()
-                                    } else {
-                                        Float::new(options.prec)
-                                    },
-                                )
-                            }
-                            (false, true) => {
-                                Complex::with_val(
-                                    options.prec,
-                                    if (n1.real().clone() - n2.real()).abs().log2()
-                                        < options.prec as i32 / -16
-                                    {
-                                        n2.real().clone()
-                                    } else {
-                                        Float::new(options.prec)
-                                    },
-                                ) + Complex::with_val_imag(
-                                    options.prec,
-                                    if n1.imag().is_sign_positive() {
-                                        Constant::Infinity
-                                    } else {
-                                        Constant::NegInfinity
-                                    },
-                                )
-                            }
-                            (false, false) => Complex::with_val(options.prec, Constant::Nan),
-                        }
-                    }
-                },
-                units,
-            )))
+    dist: &str,
+    x: Float,
+    args: &[Float],
+) -> Result<Float, &'static str> {
+    let prec = x.prec();
+    let arg = |n: usize| args.get(n).cloned().ok_or("not enough args");
+    let c = |f: &Float| Complex::with_val(prec, f);
+    let zero = Float::new(prec);
+    let pi = Float::with_val(prec, Constant::Pi);
+    Ok(match dist {
+        "t" => {
+            let v = arg(0)?;
+            let h: Float = (v.clone() + 1) / 2;
+            let n: Float = x.clone() * x / v.clone() + 1;
+            let n = n.pow(-h.clone());
+            (gamma(c(&h)) / gamma(c(&(v.clone() / 2)))).real().clone() * n / (v * pi).sqrt()
         }
-        (Vector(n1), Vector(n2)) => {
-            let mut n3: Vec<Number<Integer, Float, Complex>> = Vec::new();
-            let mut vec = Vec::with_capacity(n1.len().max(1));
-            for (i, (n1, n2)) in n1.iter().zip(n2).enumerate() {
-                let units = n1.units;
-                let n1 = &n1.number;
-                let n2 = &n2.number;
-                vec.push(Number::from(
-                    if (n2.clone() - n1.clone()).abs().real().clone().log2()
-                        < options.prec as i32 / -16
-                    {
-                        n1.clone()
-                    } else {
-                        if n3.is_empty() {
-                            let h3 = Complex::with_val(options.prec, 0.5)
-                                .pow((options.prec / 2) as f64 + 13.0 / 0.7);
-                            n3 = do_math_with_var(
-                                func.clone(),
-                                options,
-                                func_vars.clone(),
-                                &var,
-                                NumStr::new(Number::from(point.clone() - h3.clone(), xunits)),
-                            )?
-                            .vec()?;
-                        }
-                        let n3 = &n3[i].number;
-                        let sign = n1.real().is_sign_positive() == n2.real().is_sign_positive()
-                            && n2.real().is_sign_positive() == n3.real().is_sign_positive()
-                            && n1.imag().is_sign_positive() == n2.imag().is_sign_positive()
-                            && n2.imag().is_sign_positive() == n3.imag().is_sign_positive();
-                        let n1r = n1.real().clone().abs();
-                        let n2r = n2.real().clone().abs();
-                        let n3r = n3.real().clone().abs();
-                        let n1i = n1.imag().clone().abs();
-                        let n2i = n2.imag().clone().abs();
-                        let n3i = n3.imag().clone().abs();
-                        if !sign {
-                            Complex::with_val(options.prec, Constant::Nan)
-                        } else {
-                            match (n3r > n2r && n2r > n1r, n3i > n2i && n2i > n1i) {
-                                (true, true) => {
-                                    match (
-                                        n1.real().is_sign_positive(),
-                                        n1.imag().is_sign_positive(),
-                                    ) {
-                                        (true, true) => Complex::with_val(
-                                            options.prec,
-                                            (Constant::Infinity, Constant::Infinity),
-                                        ),
-                                        (true, false) => Complex::with_val(
-                                            options.prec,
-                                            (Constant::Infinity, Constant::NegInfinity),
-                                        ),
-                                        (false, true) => Complex::with_val(
-                                            options.prec,
-                                            (Constant::NegInfinity, Constant::Infinity),
-                                        ),
-                                        (false, false) => -Complex::with_val(
-                                            options.prec,
-                                            (Constant::Infinity, Constant::Infinity),
-                                        ),
-                                    }
-                                }
-                                (true, false) => {
-                                    Complex::with_val(
-                                        options.prec,
-                                        if n1.real().is_sign_positive() {
-                                            Constant::Infinity
-                                        } else {
-                                            Constant::NegInfinity
-                                        },
-                                    ) + Complex::with_val_imag(
-                                        options.prec,
-                                        if (n2i - n3i.clone()).abs().log2()
-                                            < options.prec as i32 / -16
-                                        {
-                                            n3i
-                                        } else {
-                                            Float::new(options.prec)
-                                        },
-                                    )
-                                }
-                                (false, true) => {
-                                    Complex::with_val(
-                                        options.prec,
-                                        if (n2r - n3r.clone()).abs().log2()
-                                            < options.prec as i32 / -16
-                                        {
-                                            n3r
-                                        } else {
-                                            Float::new(options.prec)
-                                        },
-                                    ) + Complex::with_val_imag(
-                                        options.prec,
-                                        if n1.imag().is_sign_positive() {
-                                            Constant::Infinity
-                                        } else {
-                                            Constant::NegInfinity
-                                        },
-                                    )
-                                }
-                                (false, false) => Complex::with_val(options.prec, Constant::Nan),
-                            }
-                        }
-                    },
-                    units,
-                ))
+        "chi2" => {
+            let k: Float = arg(0)? / 2;
+            if x.is_sign_negative() || x.is_zero() {
+                zero
+            } else {
+                let e: Float = -x.clone() / 2;
+                let n: Float = x.pow(k.clone() - 1) * e.exp();
+                n / (Float::with_val(prec, 2).pow(k.clone()) * gamma(c(&k)).real())
             }
-            Ok(Vector(vec))
         }
-        (_, _) => Err("unsupported lim data"),
-    }
+        "f" => {
+            let (d1, d2) = (arg(0)?, arg(1)?);
+            if x.is_sign_negative() || x.is_zero() {
+                if !x.is_zero() || d1 > 2 {
+                    zero
+                } else if d1 == 2 {
+                    Float::with_val(prec, 1)
+                } else {
+                    Float::with_val(prec, f64::INFINITY)
+                }
+            } else {
+                let (a, b): (Float, Float) = (d1.clone() / 2, d2.clone() / 2);
+                let beta = (gamma(c(&a)) * gamma(c(&b)) / gamma(c(&(a.clone() + &b))))
+                    .real()
+                    .clone();
+                let r: Float = d1.clone() * &x / &d2;
+                let s: Float = r.clone() + 1;
+                r.pow(a.clone()) / s.pow(a + b) / (x * beta)
+            }
+        }
+        "exponential" => {
+            let l = arg(0)?;
+            if x.is_sign_negative() && !x.is_zero() {
+                zero
+            } else {
+                (-x * &l).exp() * l
+            }
+        }
+        "uniform" => {
+            let (a, b) = (arg(0)?, arg(1)?);
+            if x < a || x > b {
+                zero
+            } else {
+                (b - a).recip()
+            }
+        }
+        "weibull" => {
+            let (k, l) = (arg(0)?, arg(1)?);
+            if x.is_sign_negative() && !x.is_zero() {
+                zero
+            } else {
+                let r: Float = x / &l;
+                r.clone().pow(k.clone() - 1) * (-r.pow(k.clone())).exp() * k / l
+            }
+        }
+        "cauchy" => {
+            let (x0, g) = (arg(0)?, arg(1)?);
+            let r: Float = (x - x0) / &g;
+            let d: Float = pi * g * (r.clone() * r + 1);
+            d.recip()
+        }
+        "laplace" => {
+            let (m, b) = (arg(0)?, arg(1)?);
+            let r: Float = (x - m).abs() / &b;
+            (-r).exp() / (b * 2)
+        }
+        _ => return Err("unsupported distribution"),
+    })
 }
-//https://github.com/IstvanMezo/LambertW-function/blob/master/complex%20Lambert.cpp
-pub fn lambertw<
+// cumulative distribution of the named distribution at x, args being its parameters
+pub fn cdf<
     Integer: crate::types::Integer<Float, Complex>,
     Float: crate::types::Float<Integer, Complex>,
     Complex: crate::types::Complex<Integer, Float>,
 >(
-    z: Complex,
-    k: Integer,
-) -> Complex {
-    if z.is_zero() {
-        return if k == 0 {
-            Complex::new(z.prec())
-        } else {
-            -Complex::with_val(z.prec(), Constant::Infinity)
-        };
-    }
-    if z.imag().is_zero() && (k == 0 || k == -1) {
-        let e: Float = -Float::with_val(z.prec(), -1).exp();
-        if z.real() == &e {
-            return Complex::with_val(z.prec(), -1);
+    dist: &str,
+    x: Float,
+    args: &[Float],
+) -> Result<Float, &'static str> {
+    let prec = x.prec();
+    let arg = |n: usize| args.get(n).cloned().ok_or("not enough args");
+    let c = |f: &Float| Complex::with_val(prec, f);
+    let zero = Float::new(prec);
+    let one = Float::with_val(prec, 1);
+    let positive = x.is_sign_positive() && !x.is_zero();
+    Ok(match dist {
+        "t" => {
+            let v = arg(0)?;
+            let y: Float = v.clone() / (x.clone() * &x + &v);
+            let tail: Float =
+                regularized_incomplete_beta(c(&y), c(&(v / 2)), Complex::with_val(prec, 0.5))
+                    .real()
+                    .clone()
+                    / 2;
+            if x.is_sign_negative() {
+                tail
+            } else {
+                -tail + 1
+            }
         }
-    }
-    let mut w = initpoint(z.clone(), k);
-    for _ in 0..(z.prec() / 64).max(8) {
-        let zexp = w.clone().exp();
-        let zexpz = zexp.clone() * &w;
-        let zexpz_d = zexpz.clone() + &zexp;
-        let zexpz_dd = (zexp * 2) + &zexpz;
-        w -=
-            ((zexpz.clone() - &z) * &zexpz_d) / ((sqr(zexpz_d) * 2) - ((zexpz - &z) * zexpz_dd)) * 2
-    }
-    w
+        "chi2" => {
+            if positive {
+                let k: Float = arg(0)? / 2;
+                let q: Complex = incomplete_gamma(c(&k), c(&(x / 2))) / gamma(c(&k));
+                -q.real().clone() + 1
+            } else {
+                zero
+            }
+        }
+        "f" => {
+            if positive {
+                let (d1, d2) = (arg(0)?, arg(1)?);
+                let r: Float = d1.clone() * x;
+                let y: Float = r.clone() / (r + &d2);
+                regularized_incomplete_beta(c(&y), c(&(d1 / 2)), c(&(d2 / 2)))
+                    .real()
+                    .clone()
+            } else {
+                zero
+            }
+        }
+        "exponential" => {
+            if positive {
+                -(-x * arg(0)?).exp() + 1
+            } else {
+                zero
+            }
+        }
+        "uniform" => {
+            let (a, b) = (arg(0)?, arg(1)?);
+            if x < a {
+                zero
+            } else if x > b {
+                one
+            } else {
+                (x - &a) / (b - a)
+            }
+        }
+        "weibull" => {
+            if positive {
+                let (k, l) = (arg(0)?, arg(1)?);
+                let r: Float = x / l;
+                -(-r.pow(k)).exp() + 1
+            } else {
+                zero
+            }
+        }
+        "cauchy" => {
+            let (x0, g) = (arg(0)?, arg(1)?);
+            let r: Float = (x - x0) / g;
+            r.atan() / Float::with_val(prec, Constant::Pi) + 0.5
+        }
+        "laplace" => {
+            let (m, b) = (arg(0)?, arg(1)?);
+            let r: Float = (x - m) / b;
+            if r.is_sign_negative() {
+                r.exp() / 2
+            } else {
+                -(-r).exp() / 2 + 1
+            }
+        }
+        "norm" => {
+            let z = if args.is_empty() {
+                x
+            } else {
+                (x - arg(0)?) / arg(1)?
+            };
+            (-z / Float::with_val(prec, 2).sqrt()).erfc() / 2
+        }
+        _ => return Err("unsupported distribution"),
+    })
 }
-fn initpoint<
+// p-value of stat under the named distribution, tail 0 being two-sided, 1 upper and -1 lower
+fn p_value<
     Integer: crate::types::Integer<Float, Complex>,
     Float: crate::types::Float<Integer, Complex>,
     Complex: crate::types::Complex<Integer, Float>,
 >(
-    z: Complex,
-    k: Integer,
-) -> Complex {
-    let prec = z.prec();
-    {
-        let e = Float::with_val(prec, -1).exp();
-        let test: Complex = z.clone() + &e;
-        if test.abs().real() <= &1.005 {
-            if k == 0 && z.real() > &-0.5 {
-                let p1: Complex = z / e * 2 + 2;
-                let p = p1.clone().sqrt();
-                return p.clone() - (p1 / 3) + ((cube(p) * 11) / 72) - 1;
-            } else if k == -1 && z.real().is_sign_negative() && z.imag().is_zero() {
-                let p1: Complex = z / e * 2 + 2;
-                let p = p1.clone().sqrt();
-                return -p.clone() - (p1 / 3) - ((cube(p) * 11) / 72) - 1;
+    dist: &str,
+    stat: Float,
+    args: &[Float],
+    tail: i32,
+) -> Result<Float, &'static str> {
+    let lower = cdf(dist, stat, args)?;
+    let upper: Float = -lower.clone() + 1;
+    Ok(match tail {
+        0 => {
+            let p: Float = if lower < upper { lower } else { upper } * 2;
+            if p > 1 {
+                Float::with_val(p.prec(), 1)
+            } else {
+                p
             }
         }
-    }
-    {
-        let test: Complex = z.clone() - 0.5;
-        if test.abs().real() <= &0.6 {
-            if k == 0 {
-                return ((z.clone() * 7.061302897 + 0.1237166) * 0.35173371)
-                    / ((z * 2 + 1) * 0.827184 + 2);
-            } else if k == -1 {
-                return (Complex::with_val(prec, (-2.2591588985, -4.22096))
-                    * (Complex::with_val(prec, (-14.073271, -33.767687754)) * &z
-                        + Complex::with_val(prec, (-12.7127, 19.071643)) * (z.clone() * 2 + 1)))
-                    / (Complex::with_val(prec, (-17.23103, 10.629721)) * (z * 2 + 1) + 2);
+        1 => upper,
+        _ => lower,
+    })
+}
+// sample mean and variance of real data
+fn mean_var<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    a: &[Float],
+) -> (Float, Float) {
+    let prec = a[0].prec();
+    let a = a
+        .iter()
+        .map(|a| Number::from(a.clone().into(), None))
+        .collect::<Vec<Number<Integer, Float, Complex>>>();
+    let mean = a
+        .iter()
+        .fold(Complex::new(prec), |sum, val| sum + val.number.clone())
+        / a.len();
+    let var = variance(&a, Some(mean.clone()), prec);
+    (mean.real().clone(), var.number.real().clone())
+}
+// one-sample against mu, paired, or welch two-sample t-test, returning {t, df, p}
+pub fn ttest<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    a: &[Float],
+    b: Option<&[Float]>,
+    mu: Float,
+    tail: i32,
+    paired: bool,
+) -> Result<Vec<Float>, &'static str> {
+    let prec = mu.prec();
+    let (stat, df) = match b {
+        Some(b) if paired => {
+            if a.len() != b.len() {
+                return Err("paired samples differ in length");
             }
+            let d: Vec<Float> = a.iter().zip(b).map(|(a, b)| a.clone() - b).collect();
+            return ttest(&d, None, mu, tail, false);
         }
-    }
-    let zln = z.ln() + Complex::with_val_imag(prec, Float::with_val(prec, Constant::Pi) * k * 2);
-    zln.clone() - zln.ln()
+        Some(b) => {
+            if a.len() < 2 || b.len() < 2 {
+                return Err("not enough samples");
+            }
+            let (ma, va) = mean_var(a);
+            let (mb, vb) = mean_var(b);
+            let sa: Float = va / a.len();
+            let sb: Float = vb / b.len();
+            let s: Float = sa.clone() + &sb;
+            let df: Float = s.clone() * &s
+                / (sa.clone() * sa / (a.len() - 1) + sb.clone() * sb / (b.len() - 1));
+            ((ma - mb - mu) / s.sqrt(), df)
+        }
+        None => {
+            if a.len() < 2 {
+                return Err("not enough samples");
+            }
+            let (m, v) = mean_var(a);
+            let se: Float = v / a.len();
+            ((m - mu) / se.sqrt(), Float::with_val(prec, a.len() - 1))
+        }
+    };
+    let p = p_value("t", stat.clone(), std::slice::from_ref(&df), tail)?;
+    Ok(vec![stat, df, p])
 }
-#[cfg(feature = "fastrand")]
-pub fn rand_gamma<
+// z-test with known standard deviations, df being infinite, returning {z, df, p}
+pub fn ztest<
     Integer: crate::types::Integer<Float, Complex>,
     Float: crate::types::Float<Integer, Complex>,
     Complex: crate::types::Complex<Integer, Float>,
 >(
-    k: Float,
-    t: Float,
-) -> Float {
-    let prec = k.prec();
-    let mut sum = Float::new(prec);
-    for _ in 1..=k
-        .clone()
-        .floor()
-        .to_integer()
-        .unwrap_or_default()
-        .to_usize()
-        .unwrap_or_default()
+    a: &[Float],
+    b: Option<&[Float]>,
+    mu: Float,
+    sigma: (Float, Float),
+    tail: i32,
+) -> Result<Vec<Float>, &'static str> {
+    let prec = mu.prec();
+    if a.is_empty() || b.is_some_and(|b| b.is_empty()) {
+        return Err("not enough samples");
+    }
+    let mean = |a: &[Float]| a.iter().fold(Float::new(prec), |s, x| s + x) / a.len();
+    let var = |s: &Float, n: usize| s.clone() * s / n;
+    let stat = match b {
+        Some(b) => {
+            let se: Float = var(&sigma.0, a.len()) + var(&sigma.1, b.len());
+            (mean(a) - mean(b) - mu) / se.sqrt()
+        }
+        None => (mean(a) - mu) / var(&sigma.0, a.len()).sqrt(),
+    };
+    let p = p_value("norm", stat.clone(), &[], tail)?;
+    Ok(vec![stat, Float::with_val(prec, f64::INFINITY), p])
+}
+// pearson's chi-squared test, of independence for a contingency table
+// or of goodness of fit for observed counts against expected counts or probabilities
+pub fn chi2test<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    observed: &[Vec<Float>],
+    expected: Option<&[Float]>,
+    tail: i32,
+) -> Result<Vec<Float>, &'static str> {
+    if observed.is_empty()
+        || observed[0].is_empty()
+        || observed.iter().any(|r| r.len() != observed[0].len())
     {
-        let u: Float = Float::with_val(prec, fastrand::u128(1..)) / u128::MAX;
-        sum += u.ln();
+        return Err("invalid matrix");
     }
-    let s = k.clone().fract();
-    let e = Float::with_val(prec, 1).exp();
-    let check = e.clone() / (e + s.clone());
-    let mut eta: Float;
-    loop {
-        let u: Float = Float::with_val(prec, fastrand::u128(1..)) / u128::MAX;
-        let v: Float = Float::with_val(prec, fastrand::u128(1..)) / u128::MAX;
-        let w: Float = Float::with_val(prec, fastrand::u128(1..)) / u128::MAX;
-        let n;
-        if u <= check {
-            eta = v.pow(s.clone().recip());
-            n = w * eta.clone().pow(s.clone() - 1);
-        } else {
-            eta = -v.ln() + 1;
-            n = w * (-eta.clone()).exp();
+    let prec = observed[0][0].prec();
+    let zero = Float::new(prec);
+    let sum = |v: &mut dyn Iterator<Item = &Float>| v.fold(zero.clone(), |s, x| s + x);
+    let total = sum(&mut observed.iter().flatten());
+    let (stat, df) = if observed.len() == 1 {
+        let o = &observed[0];
+        let e: Vec<Float> = match expected {
+            Some(e) if e.len() == o.len() => {
+                let t = sum(&mut e.iter());
+                e.iter().map(|e| e.clone() * &total / &t).collect()
+            }
+            Some(_) => return Err("dimension mismatch"),
+            None => vec![total.clone() / o.len(); o.len()],
         };
-        if n <= eta.clone().pow(s.clone() - 1) * (-eta.clone()).exp() {
-            break;
+        let stat = o.iter().zip(e.iter()).fold(zero.clone(), |s, (o, e)| {
+            let d: Float = o.clone() - e;
+            s + d.clone() * d / e
+        });
+        (stat, Float::with_val(prec, o.len().saturating_sub(1)))
+    } else {
+        let rows: Vec<Float> = observed.iter().map(|r| sum(&mut r.iter())).collect();
+        let cols: Vec<Float> = (0..observed[0].len())
+            .map(|j| sum(&mut observed.iter().map(|r| &r[j])))
+            .collect();
+        let mut stat = zero.clone();
+        for (r, row) in rows.iter().zip(observed.iter()) {
+            for (c, o) in cols.iter().zip(row.iter()) {
+                let e: Float = r.clone() * c / &total;
+                let d: Float = o.clone() - &e;
+                stat += d.clone() * d / e
+            }
         }
-    }
-    let f: Float = eta - sum;
-    t * f
+        (
+            stat,
+            Float::with_val(prec, (rows.len() - 1) * (cols.len() - 1)),
+        )
+    };
+    let p = p_value("chi2", stat.clone(), std::slice::from_ref(&df), tail)?;
+    Ok(vec![stat, df, p])
 }
-#[cfg(feature = "fastrand")]
-pub fn rand_norm<
+// kolmogorov-smirnov test of a against a normal distribution or a second sample,
+// returning {d, effective n, p} with the asymptotic kolmogorov distribution
+pub fn kstest<
     Integer: crate::types::Integer<Float, Complex>,
     Float: crate::types::Float<Integer, Complex>,
     Complex: crate::types::Complex<Integer, Float>,
 >(
-    m: Complex,
-    s: Complex,
-) -> Complex {
-    let prec = s.prec();
-    let mut u: Float = Float::with_val(prec, fastrand::i128(i128::MIN + 2..i128::MAX)) / i128::MAX;
-    let mut v: Float = Float::with_val(prec, fastrand::i128(i128::MIN + 2..i128::MAX)) / i128::MAX;
-    let mut g: Float = u.clone().pow(2) + v.pow(2);
-    while g >= 1 {
-        u = Float::with_val(prec, fastrand::i128(i128::MIN + 2..i128::MAX)) / i128::MAX;
-        v = Float::with_val(prec, fastrand::i128(i128::MIN + 2..i128::MAX)) / i128::MAX;
-        g = u.clone().pow(2) + v.pow(2);
+    a: &[Float],
+    b: Option<&[Float]>,
+    norm: (Float, Float),
+    tail: i32,
+) -> Result<Vec<Float>, &'static str> {
+    if a.is_empty() || b.is_some_and(|b| b.is_empty()) {
+        return Err("not enough samples");
+    }
+    let prec = norm.0.prec();
+    let sort = |a: &[Float]| {
+        let mut a = a.to_vec();
+        a.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+        a
+    };
+    let a = sort(a);
+    let n = Float::with_val(prec, a.len());
+    let mut upper = Float::new(prec);
+    let mut lower = Float::new(prec);
+    let mut bump = |d: Float| {
+        if d > upper {
+            upper.clone_from(&d)
+        }
+        if -d.clone() > lower {
+            lower = -d
+        }
+    };
+    let ne = match b {
+        Some(b) => {
+            let b = sort(b);
+            let m = Float::with_val(prec, b.len());
+            let (mut i, mut j) = (0, 0);
+            while i < a.len() && j < b.len() {
+                let x = if a[i] <= b[j] {
+                    a[i].clone()
+                } else {
+                    b[j].clone()
+                };
+                while i < a.len() && a[i] <= x {
+                    i += 1
+                }
+                while j < b.len() && b[j] <= x {
+                    j += 1
+                }
+                bump(Float::with_val(prec, i) / &n - Float::with_val(prec, j) / &m)
+            }
+            n.clone() * &m / (n + m)
+        }
+        None => {
+            for (i, x) in a.iter().enumerate() {
+                let f = cdf("norm", x.clone(), &[norm.0.clone(), norm.1.clone()])?;
+                bump(Float::with_val(prec, i + 1) / &n - &f);
+                bump(Float::with_val(prec, i) / &n - f)
+            }
+            n
+        }
+    };
+    let (d, p) = match tail {
+        0 => {
+            let d = if upper > lower { upper } else { lower };
+            let s = ne.clone().sqrt();
+            let l: Float = (s.clone() + 0.12 + Float::with_val(prec, 0.11) / s) * &d;
+            (d, kolmogorov(l))
+        }
+        _ => {
+            let d = if tail == 1 { upper } else { lower };
+            let e: Float = -d.clone() * &d * &ne * 2;
+            (d, e.exp())
+        }
+    };
+    Ok(vec![d, ne, p])
+}
+// parses the arguments of ttest, ztest, chi2test and kstest, returning {statistic, df, p}
+#[allow(clippy::type_complexity)]
+pub fn hypothesis_test<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    test: &str,
+    a: &NumStr<Integer, Float, Complex>,
+    args: &[NumStr<Integer, Float, Complex>],
+    prec: u32,
+) -> Result<NumStr<Integer, Float, Complex>, &'static str> {
+    let real = |v: &[Number<Integer, Float, Complex>]| -> Vec<Float> {
+        v.iter().map(|n| n.number.real().clone()).collect()
+    };
+    let num = |n: usize| -> Result<Option<Float>, &'static str> {
+        args.get(n)
+            .map(|a| Ok(a.num()?.number.real().clone()))
+            .transpose()
+    };
+    let tail = |n: usize| -> Result<i32, &'static str> {
+        Ok(num(n)?
+            .map(|t| {
+                t.to_integer()
+                    .unwrap_or_default()
+                    .to_i32()
+                    .unwrap_or_default()
+            })
+            .unwrap_or(if test == "chi2test" { 1 } else { 0 }))
+    };
+    let zero = Float::new(prec);
+    let sample = match a {
+        Vector(v) => real(v),
+        Matrix(m) if test == "chi2test" => Vec::new(),
+        _ => return Err("expected vector of observations"),
+    };
+    let second = match args.first() {
+        Some(Vector(b)) => Some(real(b)),
+        _ => None,
+    };
+    let result = match test {
+        "ttest" => {
+            let paired = num(2)?.is_some_and(|p| !p.is_zero());
+            match second {
+                Some(b) => ttest(&sample, Some(&b), zero, tail(1)?, paired)?,
+                None => ttest(&sample, None, num(0)?.unwrap_or(zero), tail(1)?, false)?,
+            }
+        }
+        "ztest" => {
+            let sigma = match args.get(1) {
+                Some(Vector(s)) if s.len() == 2 => {
+                    (s[0].number.real().clone(), s[1].number.real().clone())
+                }
+                Some(Num(s)) => (s.number.real().clone(), s.number.real().clone()),
+                _ => return Err("not enough args"),
+            };
+            match second {
+                Some(b) => ztest(&sample, Some(&b), zero, sigma, tail(2)?)?,
+                None => ztest(&sample, None, num(0)?.unwrap_or(zero), sigma, tail(2)?)?,
+            }
+        }
+        "chi2test" => match a {
+            Matrix(m) => chi2test(
+                &m.iter().map(|r| real(r)).collect::<Vec<Vec<Float>>>(),
+                None,
+                tail(0)?,
+            )?,
+            _ => match second {
+                Some(e) => chi2test(&[sample], Some(&e), tail(1)?)?,
+                None => chi2test(&[sample], None, tail(0)?)?,
+            },
+        },
+        _ => match second {
+            Some(b) => kstest(
+                &sample,
+                Some(&b),
+                (zero, Float::with_val(prec, 1)),
+                tail(1)?,
+            )?,
+            None if args.len() >= 2 => kstest(
+                &sample,
+                None,
+                (num(0)?.unwrap_or(zero.clone()), num(1)?.unwrap_or(zero)),
+                tail(2)?,
+            )?,
+            None => kstest(&sample, None, (zero, Float::with_val(prec, 1)), tail(0)?)?,
+        },
+    };
+    Ok(Vector(
+        result
+            .into_iter()
+            .map(|f| Number::from(Complex::with_val(prec, f), None))
+            .collect(),
+    ))
+}
+// survival function of the kolmogorov distribution
+fn kolmogorov<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    l: Float,
+) -> Float {
+    let prec = l.prec();
+    let one = Float::with_val(prec, 1);
+    if l.is_zero() {
+        return one;
+    }
+    let mut sum = Float::new(prec);
+    if l < 1.18 {
+        let pi = Float::with_val(prec, Constant::Pi);
+        let y: Float = -pi.clone() * &pi / (l.clone() * &l * 8);
+        for k in (1..prec as usize).step_by(2) {
+            let t: Float = (y.clone() * (k * k)).exp();
+            if negligible(&t) {
+                break;
+            }
+            sum += t
+        }
+        let p: Float = -(Float::with_val(prec, Constant::Tau).sqrt() / l * sum) + 1;
+        if p.is_sign_negative() {
+            Float::new(prec)
+        } else {
+            p
+        }
+    } else {
+        let y: Float = -l.clone() * l * 2;
+        for k in 1..prec as usize {
+            let t: Float = (y.clone() * (k * k)).exp();
+            if negligible(&t) {
+                break;
+            }
+            if k % 2 == 1 { sum += t } else { sum -= t }
+        }
+        sum * 2
     }
-    let d: Float = g.clone().ln() / g * -2i32;
-    let z: Float = u * d.sqrt();
-    m + z * s
 }
 pub fn regularized_incomplete_beta<
     I: crate::types::Integer<F, C>,
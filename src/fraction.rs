@@ -3,6 +3,7 @@ use crate::types::Constant::Pi;
 use crate::types::{Complex, Float, Integer};
 use crate::{
     complex::prime_factors,
+    print::exact_digits,
     units::{Auto, Colors, Number, Options},
 };
 pub fn fraction<I: Integer<F, C>, F: Float<I, C>, C: Complex<I, F>>(
@@ -41,7 +42,9 @@ pub fn fraction<I: Integer<F, C>, F: Float<I, C>, C: Complex<I, F>>(
                     let mut num = orig.to_integer().unwrap();
                     let mut mul = String::new();
                     if num <= 65536 {
-                        let pf = prime_factors(num.clone());
+                        // at most 65536 so only trial division runs and it cannot fail,
+                        // an empty list would only leave the radicand unsimplified anyway
+                        let pf = prime_factors(num.clone()).unwrap_or_default();
                         let mut n = I::from(1);
                         for p in pf {
                             n *= p.0.clone().pow((p.1 / if i == 1 { 2 } else { 3 }) as u32);
@@ -137,7 +140,7 @@ pub fn fraction<I: Integer<F, C>, F: Float<I, C>, C: Complex<I, F>>(
                     if recip == 1 {
                         let mut mul = String::new();
                         if last <= 65536 {
-                            let pf = prime_factors(last.clone());
+                            let pf = prime_factors(last.clone()).unwrap_or_default();
                             let mut n = I::from(1);
                             for p in pf {
                                 n *= p.0.clone().pow((p.1 / if i == 1 { 2 } else { 3 }) as u32);
@@ -156,7 +159,7 @@ pub fn fraction<I: Integer<F, C>, F: Float<I, C>, C: Complex<I, F>>(
                     } else {
                         let mut mul = String::new();
                         if last <= 65536 {
-                            let pf = prime_factors(last.clone());
+                            let pf = prime_factors(last.clone()).unwrap_or_default();
                             let mut n = I::from(1);
                             for p in pf {
                                 n *= p.0.clone().pow((p.1 / if i == 1 { 2 } else { 3 }) as u32);
@@ -168,7 +171,7 @@ pub fn fraction<I: Integer<F, C>, F: Float<I, C>, C: Complex<I, F>>(
                         }
                         let mut div = I::from(1);
                         if recip <= 65536 {
-                            let pf = prime_factors(recip.clone());
+                            let pf = prime_factors(recip.clone()).unwrap_or_default();
                             let mut n = I::from(1);
                             for p in pf {
                                 n *= p.0.clone().pow((p.1 / if i == 1 { 2 } else { 3 }) as u32);
@@ -276,6 +279,25 @@ pub fn fraction<I: Integer<F, C>, F: Float<I, C>, C: Complex<I, F>>(
     }
     String::new()
 }
+// the exact value carried by number as p/q, digits formatted like exact integers in get_output,
+// empty for integers or when there is none
+pub fn exact_fraction<I: Integer<F, C>, F: Float<I, C>, C: Complex<I, F>>(
+    number: &Number<I, F, C>,
+    options: Options,
+) -> String {
+    match number.rational() {
+        Some((p, q)) if q != 1 => {
+            match (
+                exact_digits::<I, F, C>(&p, options),
+                exact_digits::<I, F, C>(&q, options),
+            ) {
+                (Some(p), Some(q)) => format!("{p}/{q}"),
+                _ => String::new(),
+            }
+        }
+        _ => String::new(),
+    }
+}
 pub fn rationalize<I: Integer<F, C>, F: Float<I, C>, C: Complex<I, F>>(
     value: F,
     options: Options,
@@ -342,3 +364,142 @@ pub fn c_to_rational<I: Integer<F, C>, F: Float<I, C>, C: Complex<I, F>>(
     }
     vec
 }
+// terms of the continued fraction of value, either the first n or as many as the precision
+// supports, along with the start of a repeating tail if one was found
+pub fn continued_fraction<I: Integer<F, C>, F: Float<I, C>, C: Complex<I, F>>(
+    value: F,
+    n: Option<usize>,
+) -> (Vec<I>, Option<usize>) {
+    if !value.is_finite() {
+        return (Vec::new(), None);
+    }
+    let prec = value.prec();
+    let tol = value.clone().abs() / F::with_val(prec, 2).pow(prec - prec / 8);
+    let (mut p, mut q) = ((I::from(1), I::new()), (I::new(), I::from(1)));
+    let mut terms = Vec::new();
+    let mut x = value.clone();
+    let mut exact = false;
+    while n.is_none_or(|n| terms.len() < n) {
+        let a = x.clone().floor();
+        let f = x - a.clone();
+        let a = a.to_integer().unwrap_or_default();
+        p = (a.clone() * p.0.clone() + p.1, p.0);
+        q = (a.clone() * q.0.clone() + q.1, q.0);
+        terms.push(a);
+        if f.is_zero() {
+            exact = true;
+            break;
+        }
+        let approx: F = F::with_val(prec, p.0.clone()) / F::with_val(prec, q.0.clone());
+        if (approx - value.clone()).abs() <= tol {
+            break;
+        }
+        x = f.recip()
+    }
+    let period = if n.is_none() && !exact {
+        period(&terms)
+    } else {
+        None
+    };
+    if let Some((start, len)) = period {
+        terms.truncate(start + len)
+    }
+    (terms, period.map(|p| p.0))
+}
+// smallest pre period and period that the tail of terms repeats at least three times with,
+// ignoring the last term as it may be off from rounding
+fn period<I: Integer<F, C>, F: Float<I, C>, C: Complex<I, F>>(
+    terms: &[I],
+) -> Option<(usize, usize)> {
+    let terms = &terms[..terms.len().saturating_sub(1)];
+    for start in 1..terms.len() {
+        let tail = &terms[start..];
+        for len in 1..=tail.len() / 3 {
+            if tail.len() >= 8 && (len..tail.len()).all(|i| tail[i] == tail[i - len]) {
+                return Some((start, len));
+            }
+        }
+    }
+    None
+}
+// numerators and denominators of the convergents of the given terms
+pub fn convergents<I: Integer<F, C>, F: Float<I, C>, C: Complex<I, F>>(terms: &[I]) -> Vec<(I, I)> {
+    let (mut p, mut q) = ((I::from(1), I::new()), (I::new(), I::from(1)));
+    terms
+        .iter()
+        .map(|a| {
+            p = (a.clone() * p.0.clone() + p.1.clone(), p.0.clone());
+            q = (a.clone() * q.0.clone() + q.1.clone(), q.0.clone());
+            (p.0.clone(), q.0.clone())
+        })
+        .collect()
+}
+// value of [pre; period, period, ...], the repeating part solved as a quadratic
+pub fn from_continued_fraction<I: Integer<F, C>, F: Float<I, C>, C: Complex<I, F>>(
+    pre: &[I],
+    period: &[I],
+    prec: u32,
+) -> F {
+    let tail = if period.is_empty() {
+        None
+    } else {
+        let c = convergents(period);
+        let (p, q) = c.last().unwrap().clone();
+        let (p1, q1) = if c.len() > 1 {
+            c[c.len() - 2].clone()
+        } else {
+            (I::from(1), I::new())
+        };
+        let b = F::with_val(prec, p.clone() - q1.clone());
+        let disc: F = b.clone().pow(2) + F::with_val(prec, q.clone() * p1 * 4);
+        Some((b + disc.sqrt()) / F::with_val(prec, q * 2))
+    };
+    let c = convergents(pre);
+    match (c.last(), tail) {
+        (None, Some(y)) => y,
+        (None, None) => F::with_val(prec, crate::types::Constant::Nan),
+        (Some((p, q)), None) => F::with_val(prec, p.clone()) / F::with_val(prec, q.clone()),
+        (Some((p, q)), Some(y)) => {
+            let (p1, q1) = if c.len() > 1 {
+                c[c.len() - 2].clone()
+            } else {
+                (I::from(1), I::new())
+            };
+            (y.clone() * F::with_val(prec, p.clone()) + F::with_val(prec, p1))
+                / (y * F::with_val(prec, q.clone()) + F::with_val(prec, q1))
+        }
+    }
+}
+// closest fraction to value with a denominator of at most max_den, picked from the
+// convergents and the semiconvergents between them
+pub fn best_rational<I: Integer<F, C>, F: Float<I, C>, C: Complex<I, F>>(
+    value: F,
+    max_den: I,
+) -> Option<(I, I)> {
+    if max_den < 1 || !value.is_finite() {
+        return None;
+    }
+    let prec = value.prec();
+    let (terms, _) = continued_fraction::<I, F, C>(value.clone(), None);
+    let c = convergents(&terms);
+    let k = c.iter().take_while(|(_, q)| *q <= max_den).count();
+    let (p, q) = c[k - 1].clone();
+    if k == c.len() {
+        return Some((p, q));
+    }
+    let (p1, q1) = if k > 1 {
+        c[k - 2].clone()
+    } else {
+        (I::from(1), I::new())
+    };
+    let m = (max_den - q1.clone()) / q.clone();
+    let semi = (p1 + m.clone() * p.clone(), q1 + m * q.clone());
+    let err = |(p, q): &(I, I)| -> F {
+        (F::with_val(prec, p.clone()) / F::with_val(prec, q.clone()) - value.clone()).abs()
+    };
+    Some(if semi.1 > q && err(&semi) < err(&(p.clone(), q.clone())) {
+        semi
+    } else {
+        (p, q)
+    })
+}
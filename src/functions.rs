@@ -16,6 +16,33 @@ pub fn functions() -> HashSet<&'static str> {
         "inverse",
         "transpose",
         "trans",
+        "permute",
+        "reshape",
+        "shape",
+        "tensordot",
+        "einsum",
+        "index",
+        "index_set",
+        "hstack",
+        "vstack",
+        "blockdiag",
+        "kron",
+        "lp",
+        "qp",
+        "ttest",
+        "ztest",
+        "chi2test",
+        "kstest",
+        "multinomial_pmf",
+        "categorical_pmf",
+        "dirichlet_pdf",
+        "rand_multinomial",
+        "rand_categorical",
+        "rand_dirichlet",
+        "diag",
+        "zeros",
+        "ones",
+        "repmat",
         "len",
         "length",
         "wid",
@@ -26,6 +53,13 @@ pub fn functions() -> HashSet<&'static str> {
         "determinant",
         "part",
         "norm",
+        "frobenius",
+        "orth",
+        "gram_schmidt",
+        "cond",
+        "is_orthogonal",
+        "is_hermitian",
+        "is_posdef",
         "norm_combine",
         "abs",
         "normalize",
@@ -180,6 +214,51 @@ pub fn functions() -> HashSet<&'static str> {
         "gcd",
         "gcf",
         "lcm",
+        "modpow",
+        "modinv",
+        "egcd",
+        "crt",
+        "discrete_log",
+        "dlog",
+        "primitive_root",
+        "legendre",
+        "jacobi",
+        "totient",
+        "mobius",
+        "sigma",
+        "numdivisors",
+        "carmichael",
+        "primepi",
+        "primes",
+        "bitnot",
+        "cf",
+        "continued_fraction",
+        "from_cf",
+        "period",
+        "convergents",
+        "best_rational",
+        "fib",
+        "fibonacci",
+        "lucas",
+        "bernoulli",
+        "stirling1",
+        "stirling2",
+        "bell",
+        "catalan",
+        "partitions",
+        "harmonic",
+        "permutations",
+        "combinations",
+        "perm_rank",
+        "perm_unrank",
+        "popcount",
+        "clz",
+        "ctz",
+        "rotl",
+        "rotr",
+        "float_bits",
+        "from_bits",
+        "qformat",
         "multinomial",
         "Β",
         "B",
@@ -235,6 +314,19 @@ pub fn functions() -> HashSet<&'static str> {
         "sarea",
         "cov",
         "covariance",
+        "corr",
+        "spearman",
+        "kendall",
+        "zscore",
+        "describe",
+        "hist",
+        "hist_sturges",
+        "hist_fd",
+        "hist_scott",
+        "kde",
+        "ecdf",
+        "montecarlo",
+        "bootstrap",
         "extrema",
         "plane",
         "is_nan",
@@ -245,6 +337,7 @@ pub fn functions() -> HashSet<&'static str> {
         "rand_norm",
         "rand_uniform",
         "rand_int",
+        "seed",
         "geo_mean",
         "γ",
         "lower_gamma",
@@ -286,6 +379,45 @@ pub fn functions() -> HashSet<&'static str> {
         "neg_hypergeometric_pmf",
         "neg_hypergeometric_cdf",
         "rand_neg_hypergeometric",
+        "norm_inv",
+        "lognorm_inv",
+        "gamma_inv",
+        "beta_inv",
+        "binomial_inv",
+        "poisson_inv",
+        "geometric_inv",
+        "neg_binomial_inv",
+        "t_pdf",
+        "t_cdf",
+        "t_inv",
+        "chi2_pdf",
+        "chi2_cdf",
+        "chi2_inv",
+        "f_pdf",
+        "f_cdf",
+        "f_inv",
+        "exponential_pdf",
+        "exponential_cdf",
+        "exponential_inv",
+        "uniform_pdf",
+        "uniform_cdf",
+        "uniform_inv",
+        "weibull_pdf",
+        "weibull_cdf",
+        "weibull_inv",
+        "cauchy_pdf",
+        "cauchy_cdf",
+        "cauchy_inv",
+        "laplace_pdf",
+        "laplace_cdf",
+        "laplace_inv",
+        "rand_t",
+        "rand_chi2",
+        "rand_f",
+        "rand_exponential",
+        "rand_weibull",
+        "rand_cauchy",
+        "rand_laplace",
         "cyl",
         "cylinder",
         "prime_factors",
@@ -302,6 +434,17 @@ pub fn functions() -> HashSet<&'static str> {
         "set_fix",
         "subset",
         "element",
+        "munion",
+        "mintersect",
+        "interval",
+        "interval_cc",
+        "interval_co",
+        "interval_oc",
+        "interval_oo",
+        "complement",
+        "card",
+        "is_disjoint",
+        "filter",
         "remove",
         "extend",
         "link",
@@ -324,6 +467,33 @@ pub fn functions_with_args() -> HashSet<&'static str> {
         "adjugate(mat)",
         "inverse(mat)",
         "transpose(mat)",
+        "transpose(tensor,axes)",
+        "permute(tensor,axes)",
+        "reshape(tensor,shape)",
+        "shape(tensor)",
+        "tensordot(a,b(,n|axes_a,axes_b))",
+        "einsum(a,sub_a,b,sub_b(,out))",
+        "hstack(a,b,...)",
+        "vstack(a,b,...)",
+        "blockdiag(a,b,...)",
+        "kron(a,b)",
+        "lp(c,A,b(,Aeq,beq(,bounds)))",
+        "qp(Q,c(,A,b(,Aeq,beq)))",
+        "ttest(a(,b|μ(,tail(,paired))))",
+        "ztest(a,b|μ,σ|{σa,σb}(,tail))",
+        "chi2test(mat(,tail))",
+        "chi2test(observed(,expected(,tail)))",
+        "kstest(a(,b|μ,σ)(,tail))",
+        "multinomial_pmf(x,p)",
+        "categorical_pmf(k,p)",
+        "dirichlet_pdf(x,α)",
+        "rand_multinomial(n,p)",
+        "rand_categorical(p)",
+        "rand_dirichlet(α)",
+        "diag(vec/mat)",
+        "zeros(n(,m))",
+        "ones(n(,m))",
+        "repmat(a,n(,m))",
         "length(vec/mat)",
         "width(vec/mat)",
         "trace(mat)",
@@ -332,6 +502,14 @@ pub fn functions_with_args() -> HashSet<&'static str> {
         "part(mat,x(,y))",
         "part(vec,x)",
         "norm(num/vec/mat)",
+        "norm(vec/mat,p)",
+        "frobenius(mat)",
+        "orth(mat)",
+        "gram_schmidt(mat)",
+        "cond(mat)",
+        "is_orthogonal(mat)",
+        "is_hermitian(mat)",
+        "is_posdef(mat)",
         "norm_combine(mat)",
         "abs(num/vec/mat)",
         "normalize(vec)",
@@ -429,32 +607,76 @@ pub fn functions_with_args() -> HashSet<&'static str> {
         "split(x)",
         "slog(b,p)",
         "doublefact(x)",
-        "mean(vec)",
+        "mean(vec/mat(,axis))",
         "weighted_mean(mat)",
-        "median(vec)",
-        "mode(vec)",
+        "median(vec/mat(,axis))",
+        "mode(vec/mat(,axis))",
         "quadratic(a,b,c(,real))",
         "cubic(a,b,c,d(,real))",
-        "standarddeviation(vec)",
-        "variance(vec)",
+        "standarddeviation(vec/mat(,axis))",
+        "variance(vec/mat(,axis))",
         "to_list(mat)",
         "to_freq(vec)",
-        "σ(vec)",
-        "quartiles(vec)",
-        "percentile(vec,nth)",
-        "percentilerank(vec,x)",
+        "σ(vec/mat(,axis))",
+        "quartiles(vec/mat(,axis))",
+        "percentile(vec/mat,nth(,axis))",
+        "percentilerank(vec/mat,x(,axis))",
         "norm_cdf(x,μ,σ)",
         "norm_pdf(x,μ,σ)",
         "piecewise({f(x),bool},{g(x),bool}...)",
         "is_prime(x)",
         "dice(vec/mat)",
-        "μ(vec)",
+        "μ(vec/mat(,axis))",
         "W((k,),x)",
         "productlog((k,),x)",
         "lambertw((k,),x)",
         "ssrt((k,),x)",
         "gcd(x,y)",
         "lcm(x,y)",
+        "modpow(b,e,m)",
+        "modinv(a,m)",
+        "egcd(a,b)",
+        "crt({r...},{m...})",
+        "discrete_log(g,h,p)",
+        "primitive_root(p)",
+        "legendre(a,p)",
+        "jacobi(a,n)",
+        "totient(n)",
+        "mobius(n)",
+        "sigma((k,)n)",
+        "numdivisors(n)",
+        "carmichael(n)",
+        "primepi(n)",
+        "primes((a,)b)",
+        "bitnot(x)",
+        "cf(x(,n))",
+        "continued_fraction(x(,n))",
+        "from_cf(vec(,period))",
+        "period(x)",
+        "convergents(x/vec(,n))",
+        "best_rational(x,max_den)",
+        "fib(n)",
+        "fibonacci(n)",
+        "lucas(n)",
+        "bernoulli(n)",
+        "stirling1(n,k)",
+        "stirling2(n,k)",
+        "bell(n)",
+        "catalan(n)",
+        "partitions(n)",
+        "harmonic(n)",
+        "permutations(vec(,k))",
+        "combinations(vec,k)",
+        "perm_rank(vec)",
+        "perm_unrank(n/vec,rank)",
+        "popcount(x(,bits))",
+        "clz(x(,bits))",
+        "ctz(x(,bits))",
+        "rotl(x,n(,bits))",
+        "rotr(x,n(,bits))",
+        "float_bits(x(,bits))",
+        "from_bits(n(,bits))",
+        "qformat(x,m,n)",
         "multinomial(a,b,c...)",
         "Β((z,)a,b)",
         "B((z,)a,b)",
@@ -489,7 +711,20 @@ pub fn functions_with_args() -> HashSet<&'static str> {
         "cossin(x)",
         "sincos(x)",
         "surfacearea(a,b,z(a,b),start_b,end_b,start_a,end_a)",
-        "covariance(vec)",
+        "covariance(vec,vec/mat(,axis))",
+        "corr(vec,vec/mat(,axis))",
+        "spearman(vec,vec/mat(,axis))",
+        "kendall(vec,vec/mat(,axis))",
+        "zscore(vec/mat(,axis))",
+        "describe(vec/mat(,axis))",
+        "hist(vec(,bins/edges))",
+        "hist_sturges(vec)",
+        "hist_fd(vec)",
+        "hist_scott(vec)",
+        "kde(vec,x(,bandwidth))",
+        "ecdf(vec,x)",
+        "montecarlo(f,n(,seed))",
+        "bootstrap(vec,statistic,n(,level))",
         "extrema(a,f(a)(,start))",
         "plane(mat(,x,y))",
         "is_nan(x)",
@@ -498,6 +733,7 @@ pub fn functions_with_args() -> HashSet<&'static str> {
         "nth_prime(n)",
         "rand_norm(μ,σ)",
         "rand_int(a,b)",
+        "seed(n)",
         "rand_uniform(a,b)",
         "geo_mean(vec)",
         "γ(s(,x))",
@@ -506,7 +742,7 @@ pub fn functions_with_args() -> HashSet<&'static str> {
         "gamma_cdf(k,θ)",
         "rand_gamma(k,θ)",
         "rand_beta(k,θ)",
-        "skewness(vec)",
+        "skewness(vec/mat(,axis))",
         "lognorm_cdf(x,μ,σ)",
         "binomial_cdf(k,n,p)",
         "geometric_cdf(k,p)",
@@ -521,7 +757,7 @@ pub fn functions_with_args() -> HashSet<&'static str> {
         "rand_poisson(λ)",
         "poisson_pmf(x,λ)",
         "poisson_cdf(x,λ)",
-        "kurtosis(vec)",
+        "kurtosis(vec/mat(,axis))",
         "rand_neg_binomial(r,p)",
         "neg_binomial_cdf(k,r,p)",
         "neg_binomial_pmf(k,r,p)",
@@ -531,6 +767,45 @@ pub fn functions_with_args() -> HashSet<&'static str> {
         "neg_hypergeometric_pmf(k,N,K,r)",
         "neg_hypergeometric_cdf(k,N,K,r)",
         "rand_neg_hypergeometric(N,K,r)",
+        "norm_inv(p(,μ,σ))",
+        "lognorm_inv(p(,μ,σ))",
+        "gamma_inv(p,k,θ)",
+        "beta_inv(p,α,β)",
+        "binomial_inv(p,n,p)",
+        "poisson_inv(p,λ)",
+        "geometric_inv(p,p)",
+        "neg_binomial_inv(p,r,p)",
+        "t_pdf(x,ν)",
+        "t_cdf(x,ν)",
+        "t_inv(p,ν)",
+        "rand_t(ν)",
+        "chi2_pdf(x,k)",
+        "chi2_cdf(x,k)",
+        "chi2_inv(p,k)",
+        "rand_chi2(k)",
+        "f_pdf(x,d1,d2)",
+        "f_cdf(x,d1,d2)",
+        "f_inv(p,d1,d2)",
+        "rand_f(d1,d2)",
+        "exponential_pdf(x,λ)",
+        "exponential_cdf(x,λ)",
+        "exponential_inv(p,λ)",
+        "rand_exponential(λ)",
+        "uniform_pdf(x,a,b)",
+        "uniform_cdf(x,a,b)",
+        "uniform_inv(p,a,b)",
+        "weibull_pdf(x,k,λ)",
+        "weibull_cdf(x,k,λ)",
+        "weibull_inv(p,k,λ)",
+        "rand_weibull(k,λ)",
+        "cauchy_pdf(x,x0,γ)",
+        "cauchy_cdf(x,x0,γ)",
+        "cauchy_inv(p,x0,γ)",
+        "rand_cauchy(x0,γ)",
+        "laplace_pdf(x,μ,b)",
+        "laplace_cdf(x,μ,b)",
+        "laplace_inv(p,μ,b)",
+        "rand_laplace(μ,b)",
         "cylinder({x,y,z})",
         "prime_factors(n)",
         "hsv_to_rgb({h,s,v})",
@@ -546,6 +821,15 @@ pub fn functions_with_args() -> HashSet<&'static str> {
         "set_fix(A)",
         "subset(A,B)",
         "element(A,b)",
+        "munion(A,B)",
+        "mintersect(A,B)",
+        "interval(a,b)",
+        "interval(a,b,\"[)\")",
+        "interval_co(a,b)",
+        "complement(A)",
+        "card(A)",
+        "is_disjoint(A,B)",
+        "filter(x,p(x),start,end)",
         "remove(vec,num/vec)",
         "extend(vec,num/vec)",
         "rand",
@@ -562,6 +846,9 @@ pub fn functions_with_args() -> HashSet<&'static str> {
         "implies",
         "nor",
         "converse",
+        "bitand",
+        "bitor",
+        "bitxor",
         "rationalize(x)",
     ]
     .iter()
@@ -805,6 +1092,7 @@ pub fn options_list() -> HashSet<&'static str> {
     [
         "graphcli=",
         "slowcheck=",
+        "seed=",
         "interactive=",
         "prompt=",
         "progress=",
@@ -826,10 +1114,13 @@ pub fn options_list() -> HashSet<&'static str> {
         "vars=",
         "onaxis=",
         "base=",
+        "word=",
+        "altbase=",
         "ticks=",
         "decimal=",
         "deci=",
         "decimals=",
+        "intdigits=",
         "graphprec=",
         "graphprecision=",
         "prec=",
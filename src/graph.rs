@@ -1357,7 +1357,7 @@ fn fail<
     stdout().flush().unwrap();
 }
 #[allow(clippy::type_complexity)]
-fn get_data<
+pub fn get_data<
     Integer: crate::types::Integer<Float, Complex>,
     Float: crate::types::Float<Integer, Complex>,
     Complex: crate::types::Complex<Integer, Float>,
@@ -1374,6 +1374,10 @@ fn get_data<
     data_dir: String,
 ) -> JoinHandle<((bool, bool), (bool, bool), bool, bool, usize, usize)> {
     thread::spawn(move || {
+        #[cfg(feature = "fastrand")]
+        if let Some(seed) = func.2.seed {
+            fastrand::seed(crate::complex::index_seed(seed, i))
+        }
         let mut rec_re = 0;
         let mut rec_im = 0;
         let mut lines = false;
@@ -1588,6 +1592,13 @@ fn get_data<
                         stdout().flush().unwrap();
                         return ((false, false), (false, false), false, true, 0, 0);
                     }
+                    let m = if m[0].len() == 3
+                        && matches!(func.0.first(), Some(Func(s)) if s.starts_with("hist"))
+                    {
+                        bars(m)
+                    } else {
+                        m
+                    };
                     lines = m.len() != 1;
                     match m[0].len() {
                         3 => {
@@ -1691,6 +1702,25 @@ fn get_data<
         (d2_or_d3, re_or_im, lines, false, rec_re, rec_im)
     })
 }
+// outline each {left edge, right edge, count} histogram row as a bar
+#[allow(clippy::type_complexity)]
+pub fn bars<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    m: Vec<Vec<Number<Integer, Float, Complex>>>,
+) -> Vec<Vec<Number<Integer, Float, Complex>>> {
+    let mut outline = Vec::with_capacity(4 * m.len());
+    for v in m {
+        let zero = Number::from(Complex::new(v[2].number.prec()), None);
+        outline.push(vec![v[0].clone(), zero.clone()]);
+        outline.push(vec![v[0].clone(), v[2].clone()]);
+        outline.push(vec![v[1].clone(), v[2].clone()]);
+        outline.push(vec![v[1].clone(), zero]);
+    }
+    outline
+}
 fn hsv2rgb(hue: f64, sat: f64, val: f64) -> u32 {
     if sat == 0.0 {
         return rgb2val(val, val, val);
@@ -103,7 +103,30 @@ pub fn help_for(thing: &str) -> String {
         "units" => "see \"units list\" for a list of all units supported\x1b[G\nsupports metric and binary prefixes, \"units\" function extracts the units of the given input",
         "units list" =>
             all_units(),
-        "help" => "W, atan\x1b[G\nunits, ->",
+        "lp" =>
+        {
+            "lp(c,A,b(,Aeq,beq(,bounds)))\x1b[G\n\
+            minimizes c.x subject to A.x<=b and Aeq.x=beq, x>=0 unless bounds gives {lo,hi} or one {lo,hi} row per variable\x1b[G\n\
+            returns the flat vector {status,objective,x1,x2,...}\x1b[G\n\
+            status 0 is optimal, 1 is infeasible (objective and x are nan), 2 is unbounded (objective is -inf, x is nan)\x1b[G\n\
+            example: lp({-1,-2},{{1,1}},{4}) = {0,-8,0,4}"
+        }
+        "qp" =>
+        {
+            "qp(Q,c(,A,b(,Aeq,beq)))\x1b[G\n\
+            minimizes x.Q.x/2+c.x subject to A.x<=b and Aeq.x=beq with x free, Q positive semidefinite\x1b[G\n\
+            returns {status,objective,x1,x2,...} with the same status codes as lp\x1b[G\n\
+            example: qp({{2,0},{0,2}},{-2,-5},{{1,2}},{3}) = {0,-5.45,0.4,1.3}"
+        }
+        "axis" =>
+        {
+            "statistics on a matrix take an optional last axis argument\x1b[G\n\
+            axis 0 works down each column and axis 1 along each row, anything else is an error\x1b[G\n\
+            without one cov, corr, spearman, kendall, zscore and describe treat columns as variables,\x1b[G\n\
+            max and min give one value per row and the rest use every entry\x1b[G\n\
+            example: sd({{1,5},{3,2}},0) = {1.41,2.12}"
+        }
+        "help" => "W, atan\x1b[G\nunits, ->\x1b[G\nlp, qp\x1b[G\naxis",
         "point"|"points"=>". - dot\x1b[G\n\
 + - plus\x1b[G\n\
 x - cross\x1b[G\n\
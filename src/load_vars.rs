@@ -2,9 +2,10 @@ use crate::complex::NumStr;
 use crate::types::Constant;
 use crate::{
     math::do_math,
+    misc::split_top,
     options::set_commands,
-    parse::input_var,
-    units::{Colors, Number, Options, Variable},
+    parse::{index_args, input_var},
+    units::{Auto, Colors, Number, Options, Variable},
 };
 pub fn get_file_vars<
     Integer: crate::types::Integer<Float, Complex>,
@@ -383,7 +384,7 @@ pub fn add_var<
                 },
             );
             if r.contains(':') {
-                let mut split = r.split(':').collect::<Vec<&str>>();
+                let mut split = split_top(r, ':');
                 r = split.pop().unwrap();
                 for i in split {
                     if i.contains('=') {
@@ -425,7 +426,7 @@ pub fn add_var<
             )?
         } else {
             if r.contains(':') {
-                let mut split = r.split(':').collect::<Vec<&str>>();
+                let mut split = split_top(r, ':');
                 r = split.pop().unwrap();
                 for i in split {
                     if i.contains('=') {
@@ -534,7 +535,7 @@ pub fn add_var<
                     let mut unparsed = v.unparsed.clone();
                     if unparsed.contains(':') {
                         let un = unparsed;
-                        let mut split = un.split(':').collect::<Vec<&str>>();
+                        let mut split = split_top(&un, ':');
                         unparsed = split.pop().unwrap().to_string();
                         for i in split {
                             if i.contains('=') {
@@ -615,6 +616,31 @@ pub fn set_commands_or_vars<
     input: &[char],
 ) -> Result<(), &'static str> {
     let n = input.iter().collect::<String>();
+    if let Some(open) = n.find('[') {
+        let name = n[..open].trim();
+        let mut count = 0;
+        let close = n[open..].find(|c| {
+            match c {
+                '[' => count += 1,
+                ']' => count -= 1,
+                _ => {}
+            }
+            count == 0
+        });
+        if let Some(close) = close {
+            let close = open + close;
+            if let Some(r) = n[close + 1..].trim_start().strip_prefix('=') {
+                if !name.is_empty()
+                    && !r.starts_with('=')
+                    && name
+                        .chars()
+                        .all(|c| c.is_alphanumeric() || matches!(c, '\'' | '`' | '_'))
+                {
+                    return set_index(options, vars, name, &n[open + 1..close], r);
+                }
+            }
+        }
+    }
     let mut split = n.splitn(2, '=');
     let s = split.next().unwrap().replace(' ', "");
     let l = s;
@@ -654,3 +680,125 @@ pub fn set_commands_or_vars<
     add_var(l, r, vars.len(), vars, *options, true, false, false)?;
     Ok(())
 }
+fn set_index<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    options: &mut Options,
+    vars: &mut Vec<Variable<Integer, Float, Complex>>,
+    name: &str,
+    args: &str,
+    r: &str,
+) -> Result<(), &'static str> {
+    let l = name.chars().collect::<Vec<char>>();
+    let Some(i) = vars.iter().position(|v| v.name == l) else {
+        return Err("undefined var");
+    };
+    if vars[i].unparsed.is_empty() {
+        return Err("cant index constant");
+    }
+    let parsed = input_var(
+        &format!("index_set({name},({r}),{})", index_args(args)),
+        vars,
+        &mut Vec::new(),
+        &mut 0,
+        *options,
+        false,
+        0,
+        Vec::new(),
+        false,
+        &mut Vec::new(),
+        None,
+        None,
+    )?;
+    // the new value is stored as a literal so repeated assignments do not grow the definition
+    let value = literal(&do_math(parsed.0, *options, parsed.1)?, *options)?;
+    add_var(l, &value, i, vars, *options, true, true, false)
+}
+// source text that parses back to the given value in the input base, exact rationals as p/q
+fn literal<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    value: &NumStr<Integer, Float, Complex>,
+    options: Options,
+) -> Result<String, &'static str> {
+    let radix = options.base.0;
+    let float = |f: &Float| {
+        if f.is_nan() {
+            "nan".to_string()
+        } else if f.is_infinite() {
+            if f.is_sign_negative() { "-inf" } else { "inf" }.to_string()
+        } else {
+            // the exponent comes back in decimal after e, or @ once e is a digit, but E reads
+            // it as a power of the input base written in that base
+            let s = f.to_string_radix(radix, None);
+            match s.split_once(if radix > 10 { '@' } else { 'e' }) {
+                Some((m, e)) => match e.parse::<isize>() {
+                    Ok(e) => format!("{m}E{}", Integer::from(e).to_string_radix(radix)),
+                    Err(_) => s,
+                },
+                None => s,
+            }
+        }
+    };
+    let num = |n: &Number<Integer, Float, Complex>| {
+        let value = match n.rational() {
+            Some((p, q)) if q == 1 => format!("({})", p.to_string_radix(radix)),
+            Some((p, q)) => format!(
+                "({}/{})",
+                p.to_string_radix(radix),
+                q.to_string_radix(radix)
+            ),
+            None if n.number.imag().is_zero() => format!("({})", float(n.number.real())),
+            None => format!("({}+({})i)", float(n.number.real()), float(n.number.imag())),
+        };
+        match n.units {
+            Some(units) if units != Default::default() => format!(
+                "{value}*(1{})",
+                units.to_string(
+                    Options {
+                        color: Auto::False,
+                        ..options
+                    },
+                    &Colors::<Integer, Float, Complex>::default()
+                )
+            ),
+            _ => value,
+        }
+    };
+    let vec = |v: &[Number<Integer, Float, Complex>]| {
+        format!(
+            "{{{}}}",
+            v.iter().map(num).collect::<Vec<String>>().join(",")
+        )
+    };
+    match value {
+        NumStr::Num(n) => Ok(num(n)),
+        NumStr::Vector(v) => Ok(vec(v)),
+        NumStr::Matrix(m) => Ok(format!(
+            "{{{}}}",
+            m.iter().map(|v| vec(v)).collect::<Vec<String>>().join(",")
+        )),
+        NumStr::Tensor(shape, data) => {
+            fn nest(shape: &[usize], items: &[String]) -> String {
+                if shape.len() <= 1 {
+                    format!("{{{}}}", items.join(","))
+                } else {
+                    format!(
+                        "{{{}}}",
+                        items
+                            .chunks(shape[1..].iter().product::<usize>().max(1))
+                            .map(|c| nest(&shape[1..], c))
+                            .collect::<Vec<String>>()
+                            .join(",")
+                    )
+                }
+            }
+            Ok(nest(shape, &data.iter().map(num).collect::<Vec<String>>()))
+        }
+        _ => Err("cant index assign this value"),
+    }
+}
@@ -1,29 +1,43 @@
 use crate::cas::isolate;
 #[cfg(feature = "fastrand")]
-use crate::complex::{rand_gamma, rand_norm};
+use crate::complex::{bootstrap, rand_dist, rand_gamma, rand_multivariate, rand_norm};
 use crate::types::{Constant, IsPrime};
 use crate::{
     complex::{
         LimSide::{Both, Left, Right},
         NumStr,
         NumStr::{
-            And, Comma, Converse, Conversion, Division, Equal, Exponent, Func, Greater,
-            GreaterEqual, Implies, InternalMultiplication, LeftBracket, LeftCurlyBracket, Lesser,
-            LesserEqual, Matrix, Minus, Modulo, Multiplication, Nand, NearEqual, Nor, Not,
-            NotEqual, Num, Or, Plus, PlusMinus, Range, RightBracket, RightCurlyBracket, Root,
-            ShiftLeft, ShiftRight, Tetration, Vector, Xor,
+            And, BitAnd, BitOr, BitXor, Comma, Converse, Conversion, Division, Equal, Exponent,
+            Func, Greater, GreaterEqual, Implies, InternalMultiplication, LeftBracket,
+            LeftCurlyBracket, Lesser, LesserEqual, Matrix, Minus, Modulo, Multiplication, Nand,
+            NearEqual, Nor, Not, NotEqual, Num, Or, Plus, PlusMinus, Range, RightBracket,
+            RightCurlyBracket, Root, ShiftLeft, ShiftRight, Tensor, Tetration, Vector, Xor,
         },
-        about_eq, add, and, area, atan, binomial, change_basis, cofactor, coordinate, cube, cubic,
-        determinant, digamma, div, eigenvalues, eigenvectors, eq, erf, erfc, eta, euleriannumbers,
-        euleriannumbersint, extrema, gamma, gcd, ge, generalized_eigenvectors, gt, hsv2rgb,
-        identity, implies, incomplete_beta, incomplete_gamma, inverse, iter, jcf, kernel, lambertw,
-        length, limit, lower_incomplete_gamma, minors, mul_units, mvec, nand, ne, nor, not,
-        nth_prime, or, pow_nth, prime_factors, quadratic, quartic, range, rcf, recursion,
-        regularized_incomplete_beta, rem, root, rref, shl, shr, slog, slope, solve, sort, sort_mat,
-        sqr, sub, subfactorial, sum, surface_area, taylor, tetration, to, to_cyl, to_polar, trace,
-        transpose, unity, variance, xor, zeta,
+        RECURRENCE_WORK, about_eq, add, and, area, atan, bell, bernoulli, bin_count, bin_edges,
+        binomial, bitand, bitor, bitxor, blockdiag, carmichael, catalan, cdf, change_basis,
+        cofactor, combinations, cond, coordinate, correlation, correlation_matrix, crt, cube,
+        cubic, describe, determinant, digamma, discrete_log, div, divisors, ecdf, egcd,
+        eigenvalues, eigenvectors, einsum, eq, erf, erfc, eta, euleriannumbers, euleriannumbersint,
+        extrema, factorize, fibonacci, float_bits, frobenius, from_bits, from_intervals, gamma,
+        gcd, ge, generalized_eigenvectors, gram_schmidt, gt, harmonic, histogram, hstack, hsv2rgb,
+        hypothesis_test, identity, implies, incomplete_beta, incomplete_gamma, index, index_set,
+        interval_complement, interval_contains, interval_intersection, inverse, is_hermitian,
+        is_interval_set, is_orthogonal, is_posdef, iter, jacobi_symbol, jcf, kde, kernel, kron,
+        lambertw, length, limit, lower_incomplete_gamma, lp, lucas, matrix_norm, minors, mobius,
+        modinv, modpow, montecarlo, mul_units, multinomial, multivariate_pdf, mvec, nand, ne, nor,
+        normalize_intervals, not, nth_prime, or, partitions, partitions_asymptotic, pdf, perm_rank,
+        perm_unrank, permutations, permute, pow_nth, primepi, primes, primitive_root, qformat, qp,
+        quadratic, quantile, quartic, quartiles, range, range_product, rcf, recurrence_work,
+        recursion, regularized_incomplete_beta, rem, repmat, reshape, root, rref, set_builder, shl,
+        shr, sigma, silverman, slog, slope, solve, sort, sort_mat, sqr, stack, stirling1,
+        stirling2, sub, subfactorial, sum, surface_area, taylor, tensordot, tetration, to, to_cyl,
+        to_intervals, to_polar, to_seed, totient, trace, transpose, unity, variance, vstack, word,
+        wrap_word, xor, zeta, zscore,
+    },
+    fraction::{
+        best_rational, c_to_rational, continued_fraction, convergents, from_continued_fraction,
+        rationalize,
     },
-    fraction::{c_to_rational, rationalize},
     misc::do_math_with_var,
     units::{AngleType, Number, Options, Units},
 };
@@ -68,6 +82,7 @@ pub fn do_math<
                 let v = &function[i + 1..j - 1];
                 let mut vec = Vec::new();
                 let mut mat = Vec::<Vec<Number<Integer, Float, Complex>>>::new();
+                let mut ten = Vec::new();
                 for (f, n) in v.iter().enumerate() {
                     match &n {
                         Comma if count == 0 => {
@@ -75,6 +90,7 @@ pub fn do_math<
                             match z {
                                 Num(n) => vec.push(*n),
                                 Vector(n) => mat.push(n),
+                                Matrix(_) | Tensor(_, _) => ten.push(z),
                                 _ => return Err("broken matrix"),
                             }
                             single = f + 1;
@@ -89,11 +105,18 @@ pub fn do_math<
                     match z {
                         Num(n) => vec.push(*n),
                         Vector(n) => mat.push(n),
+                        Matrix(_) | Tensor(_, _) => ten.push(z),
                         _ => return Err("broken matrix"),
                     }
                 }
                 function.drain(i..j);
-                if !mat.is_empty() {
+                if !ten.is_empty() {
+                    if vec.is_empty() && mat.is_empty() {
+                        function.insert(i, stack(&ten)?);
+                    } else {
+                        return Err("vector err");
+                    }
+                } else if !mat.is_empty() {
                     if vec.is_empty() {
                         function.insert(i, Matrix(mat));
                     } else {
@@ -140,6 +163,29 @@ pub fn do_math<
                                 | "multinomial"
                                 | "gcd"
                                 | "gcf"
+                                | "modpow"
+                                | "modinv"
+                                | "egcd"
+                                | "crt"
+                                | "discrete_log"
+                                | "dlog"
+                                | "legendre"
+                                | "jacobi"
+                                | "sigma"
+                                | "primes"
+                                | "cf"
+                                | "continued_fraction"
+                                | "from_cf"
+                                | "convergents"
+                                | "best_rational"
+                                | "popcount"
+                                | "clz"
+                                | "ctz"
+                                | "rotl"
+                                | "rotr"
+                                | "float_bits"
+                                | "from_bits"
+                                | "qformat"
                                 | "lcm"
                                 | "ssrt"
                                 | "W"
@@ -169,6 +215,7 @@ pub fn do_math<
                                 | "cross"
                                 | "dot"
                                 | "part"
+                                | "norm"
                                 | "proj"
                                 | "project"
                                 | "oproj"
@@ -177,6 +224,11 @@ pub fn do_math<
                                 | "P"
                                 | "Ap"
                                 | "An"
+                                | "stirling1"
+                                | "stirling2"
+                                | "permutations"
+                                | "combinations"
+                                | "perm_unrank"
                                 | "gamma"
                                 | "Γ"
                                 | "γ"
@@ -201,6 +253,30 @@ pub fn do_math<
                                 | "mod"
                                 | "covariance"
                                 | "cov"
+                                | "corr"
+                                | "spearman"
+                                | "kendall"
+                                | "zscore"
+                                | "describe"
+                                | "hist"
+                                | "kde"
+                                | "ecdf"
+                                | "mean"
+                                | "μ"
+                                | "median"
+                                | "mode"
+                                | "geo_mean"
+                                | "sd"
+                                | "standarddeviation"
+                                | "σ"
+                                | "variance"
+                                | "var"
+                                | "quartiles"
+                                | "kurtosis"
+                                | "skew"
+                                | "skewness"
+                                | "max"
+                                | "min"
                                 | "rand_norm"
                                 | "rand_uniform"
                                 | "rand_int"
@@ -231,7 +307,54 @@ pub fn do_math<
                                 | "neg_hypergeometric_pmf"
                                 | "neg_hypergeometric_cdf"
                                 | "rand_neg_hypergeometric"
+                                | "norm_inv"
+                                | "lognorm_inv"
+                                | "gamma_inv"
+                                | "beta_inv"
+                                | "binomial_inv"
+                                | "poisson_inv"
+                                | "geometric_inv"
+                                | "neg_binomial_inv"
+                                | "t_pdf"
+                                | "t_cdf"
+                                | "t_inv"
+                                | "chi2_pdf"
+                                | "chi2_cdf"
+                                | "chi2_inv"
+                                | "f_pdf"
+                                | "f_cdf"
+                                | "f_inv"
+                                | "exponential_pdf"
+                                | "exponential_cdf"
+                                | "exponential_inv"
+                                | "uniform_pdf"
+                                | "uniform_cdf"
+                                | "uniform_inv"
+                                | "weibull_pdf"
+                                | "weibull_cdf"
+                                | "weibull_inv"
+                                | "cauchy_pdf"
+                                | "cauchy_cdf"
+                                | "cauchy_inv"
+                                | "laplace_pdf"
+                                | "laplace_cdf"
+                                | "laplace_inv"
+                                | "rand_t"
+                                | "rand_chi2"
+                                | "rand_f"
+                                | "rand_exponential"
+                                | "rand_weibull"
+                                | "rand_cauchy"
+                                | "rand_laplace"
                                 | "union"
+                                | "munion"
+                                | "mintersect"
+                                | "is_disjoint"
+                                | "interval"
+                                | "interval_cc"
+                                | "interval_co"
+                                | "interval_oc"
+                                | "interval_oo"
                                 | "poly"
                                 | "polynomial"
                                 | "intersection"
@@ -243,6 +366,33 @@ pub fn do_math<
                                 | "link"
                                 | "subset"
                                 | "element"
+                                | "transpose"
+                                | "trans"
+                                | "permute"
+                                | "reshape"
+                                | "tensordot"
+                                | "einsum"
+                                | "index"
+                                | "index_set"
+                                | "hstack"
+                                | "vstack"
+                                | "blockdiag"
+                                | "kron"
+                                | "repmat"
+                                | "zeros"
+                                | "ones"
+                                | "lp"
+                                | "qp"
+                                | "ttest"
+                                | "ztest"
+                                | "chi2test"
+                                | "kstest"
+                                | "multinomial_pmf"
+                                | "categorical_pmf"
+                                | "dirichlet_pdf"
+                                | "rand_multinomial"
+                                | "rand_categorical"
+                                | "rand_dirichlet"
                         ) {
                             function.remove(j - 1);
                             function.remove(i);
@@ -306,6 +456,7 @@ pub fn do_math<
                                 | "Σ"
                                 | "Π"
                                 | "vec"
+                                | "filter"
                                 | "mat"
                                 | "piecewise"
                                 | "pw"
@@ -316,6 +467,8 @@ pub fn do_math<
                                 | "limit"
                                 | "set"
                                 | "isolate"
+                                | "montecarlo"
+                                | "bootstrap"
                         ) {
                             i = j - 1;
                             continue;
@@ -353,13 +506,19 @@ pub fn do_math<
         }
         i += 1;
     }
+    // in word mode every arithmetic result wraps, not just the final one, so brackets do not
+    // change what overflows
+    let wrap = |n: NumStr<Integer, Float, Complex>| match options.word {
+        Some(w) => word(n, w),
+        None => n,
+    };
     if function.len() == 1 {
         if let Func(s) = &function[0] {
             if !matches!(s.as_str(), "rnd" | "rand" | "epoch") {
                 return Ok(function[0].clone());
             }
         } else {
-            return Ok(function[0].clone());
+            return Ok(wrap(function[0].clone()));
         }
     }
     i = 0;
@@ -394,6 +553,7 @@ pub fn do_math<
                         | "Σ"
                         | "Π"
                         | "vec"
+                        | "filter"
                         | "mat"
                         | "piecewise"
                         | "pw"
@@ -404,6 +564,8 @@ pub fn do_math<
                         | "limit"
                         | "set"
                         | "isolate"
+                        | "montecarlo"
+                        | "bootstrap"
                 ) {
                     let mut place = Vec::new();
                     let mut count = 0;
@@ -463,6 +625,84 @@ pub fn do_math<
                             function[place[0] - 1].clone()
                         },
                     ) {
+                        #[cfg(feature = "fastrand")]
+                        ("bootstrap", _) if place.len() == 3 || place.len() == 4 => {
+                            let data = do_math(
+                                function[i + 2..place[0]].to_vec(),
+                                options,
+                                func_vars.clone(),
+                            )?
+                            .vec()?;
+                            let Func(stat) = &function[place[0] + 1] else {
+                                return Err("statistic must be a function name");
+                            };
+                            if place[0] + 2 != place[1] {
+                                return Err("statistic must be a function name");
+                            }
+                            let n = do_math(
+                                function[place[1] + 1..place[2]].to_vec(),
+                                options,
+                                func_vars.clone(),
+                            )?
+                            .num()?
+                            .number
+                            .real()
+                            .to_integer()
+                            .unwrap_or_default()
+                            .to_usize()
+                            .unwrap_or_default();
+                            let level = if place.len() == 4 {
+                                do_math(
+                                    function[place[2] + 1..place[3]].to_vec(),
+                                    options,
+                                    func_vars.clone(),
+                                )?
+                                .num()?
+                                .number
+                                .real()
+                                .clone()
+                            } else {
+                                Float::with_val(options.prec, 0.95)
+                            };
+                            function[i] = bootstrap(data, &stat.clone(), n, level, options)?;
+                            function.drain(i + 1..=*place.last().unwrap());
+                        }
+                        ("montecarlo", _) if place.len() == 2 || place.len() == 3 => {
+                            let n = do_math(
+                                function[place[0] + 1..place[1]].to_vec(),
+                                options,
+                                func_vars.clone(),
+                            )?
+                            .num()?
+                            .number
+                            .real()
+                            .to_integer()
+                            .unwrap_or_default()
+                            .to_usize()
+                            .unwrap_or_default();
+                            let seed = if place.len() == 3 {
+                                Some(to_seed(
+                                    do_math(
+                                        function[place[1] + 1..place[2]].to_vec(),
+                                        options,
+                                        func_vars.clone(),
+                                    )?
+                                    .num()?
+                                    .number
+                                    .real(),
+                                )?)
+                            } else {
+                                None
+                            };
+                            function[i] = montecarlo(
+                                function[i + 2..place[0]].to_vec(),
+                                func_vars.clone(),
+                                options,
+                                n,
+                                seed,
+                            )?;
+                            function.drain(i + 1..=*place.last().unwrap());
+                        }
                         ("iter", Func(var)) if place.len() == 4 || place.len() == 5 => {
                             function[i] = iter(
                                 function[place[0] + 1..place[1]].to_vec(),
@@ -790,7 +1030,8 @@ pub fn do_math<
                             function.drain(i + 1..=*place.last().unwrap());
                         }
                         (
-                            "sum" | "product" | "prod" | "summation" | "Σ" | "Π" | "vec" | "mat",
+                            "sum" | "product" | "prod" | "summation" | "Σ" | "Π" | "vec" | "mat"
+                            | "filter",
                             Func(var),
                         ) if place.len() == 4 => {
                             let start = do_math(
@@ -834,6 +1075,14 @@ pub fn do_math<
                                     s == "vec",
                                     options,
                                 )?,
+                                "filter" => set_builder(
+                                    function[place[0] + 1..place[1]].to_vec(),
+                                    func_vars.clone(),
+                                    &var,
+                                    start,
+                                    end,
+                                    options,
+                                )?,
                                 _ => sum(
                                     function[place[0] + 1..place[1]].to_vec(),
                                     func_vars.clone(),
@@ -912,7 +1161,491 @@ pub fn do_math<
                 } else {
                     let arg = function.remove(i + 1);
                     function[i] = match arg.clone() {
+                        a if matches!(
+                            s.as_str(),
+                            "shape"
+                                | "reshape"
+                                | "permute"
+                                | "tensordot"
+                                | "einsum"
+                                | "index"
+                                | "index_set"
+                                | "hstack"
+                                | "vstack"
+                                | "blockdiag"
+                                | "kron"
+                                | "repmat"
+                                | "diag"
+                                | "zeros"
+                                | "ones"
+                                | "lp"
+                                | "qp"
+                                | "ttest"
+                                | "ztest"
+                                | "chi2test"
+                                | "kstest"
+                                | "multinomial_pmf"
+                                | "categorical_pmf"
+                                | "dirichlet_pdf"
+                                | "rand_multinomial"
+                                | "rand_categorical"
+                                | "rand_dirichlet"
+                        ) || (matches!(s.as_str(), "transpose" | "trans")
+                            && (matches!(a, Tensor(_, _)) || i + 1 < function.len())) =>
+                        {
+                            match s.as_str() {
+                                "shape" => Vector(
+                                    a.tensor()?
+                                        .0
+                                        .iter()
+                                        .map(|n| {
+                                            Number::from(Complex::with_val(options.prec, *n), None)
+                                        })
+                                        .collect(),
+                                ),
+                                "reshape" => {
+                                    if i + 1 < function.len() {
+                                        reshape(&a, axes(&function.remove(i + 1))?)?
+                                    } else {
+                                        return Err("not enough args");
+                                    }
+                                }
+                                "tensordot" => {
+                                    if i + 1 < function.len() {
+                                        let b = function.remove(i + 1);
+                                        let (axes_a, axes_b) = if i + 2 < function.len() {
+                                            (
+                                                axes(&function.remove(i + 1))?,
+                                                axes(&function.remove(i + 1))?,
+                                            )
+                                        } else {
+                                            let n = if i + 1 < function.len() {
+                                                function
+                                                    .remove(i + 1)
+                                                    .num()?
+                                                    .number
+                                                    .real()
+                                                    .to_integer()
+                                                    .unwrap_or_default()
+                                                    .to_usize()
+                                                    .unwrap_or_default()
+                                            } else {
+                                                2
+                                            };
+                                            let rank = a.tensor()?.0.len();
+                                            if n > rank {
+                                                return Err("invalid axes");
+                                            }
+                                            ((rank - n..rank).collect(), (0..n).collect())
+                                        };
+                                        tensordot(&a, &b, &axes_a, &axes_b)?
+                                    } else {
+                                        return Err("not enough args");
+                                    }
+                                }
+                                "hstack" | "vstack" | "blockdiag" => {
+                                    let mut args = vec![a];
+                                    while i + 1 < function.len() {
+                                        args.push(function.remove(i + 1))
+                                    }
+                                    match s.as_str() {
+                                        "hstack" => hstack(&args)?,
+                                        "vstack" => vstack(&args)?,
+                                        _ => blockdiag(&args, options.prec)?,
+                                    }
+                                }
+                                "multinomial_pmf" | "categorical_pmf" | "dirichlet_pdf"
+                                | "rand_multinomial" | "rand_categorical" | "rand_dirichlet" => {
+                                    let real = |a: NumStr<Integer, Float, Complex>| {
+                                        Ok(match a {
+                                            Num(n) => vec![n.number.real().clone()],
+                                            Vector(v) => {
+                                                v.iter().map(|n| n.number.real().clone()).collect()
+                                            }
+                                            _ => return Err("expected vector"),
+                                        })
+                                    };
+                                    let mut args = vec![real(a)?];
+                                    while i + 1 < function.len() {
+                                        args.push(real(function.remove(i + 1))?)
+                                    }
+                                    let p = args.pop().unwrap_or_default();
+                                    #[cfg(feature = "fastrand")]
+                                    let vec = |v: Vec<Float>| {
+                                        Vector(
+                                            v.into_iter()
+                                                .map(|f| {
+                                                    Number::from(
+                                                        Complex::with_val(options.prec, f),
+                                                        None,
+                                                    )
+                                                })
+                                                .collect(),
+                                        )
+                                    };
+                                    match s.as_str() {
+                                        #[cfg(feature = "fastrand")]
+                                        "rand_multinomial" => {
+                                            let n = args
+                                                .first()
+                                                .and_then(|n| n.first())
+                                                .ok_or("not enough args")?
+                                                .to_integer()
+                                                .unwrap_or_default()
+                                                .to_usize()
+                                                .unwrap_or_default();
+                                            vec(rand_multivariate("multinomial", &p, n)?)
+                                        }
+                                        #[cfg(feature = "fastrand")]
+                                        "rand_categorical" => NumStr::new(Number::from(
+                                            Complex::with_val(
+                                                options.prec,
+                                                rand_multivariate("categorical", &p, 1)?.remove(0),
+                                            ),
+                                            None,
+                                        )),
+                                        #[cfg(feature = "fastrand")]
+                                        "rand_dirichlet" => {
+                                            vec(rand_multivariate("dirichlet", &p, 1)?)
+                                        }
+                                        _ => {
+                                            let (dist, _) = s.rsplit_once('_').unwrap_or_default();
+                                            NumStr::new(Number::from(
+                                                Complex::with_val(
+                                                    options.prec,
+                                                    multivariate_pdf(
+                                                        dist,
+                                                        args.first().ok_or("not enough args")?,
+                                                        &p,
+                                                    )?,
+                                                ),
+                                                None,
+                                            ))
+                                        }
+                                    }
+                                }
+                                "ttest" | "ztest" | "chi2test" | "kstest" => {
+                                    let mut args = Vec::new();
+                                    while i + 1 < function.len() {
+                                        args.push(function.remove(i + 1))
+                                    }
+                                    hypothesis_test(s, &a, &args, options.prec)?
+                                }
+                                "lp" | "qp" => {
+                                    let mut args = vec![a];
+                                    while i + 1 < function.len() {
+                                        args.push(function.remove(i + 1))
+                                    }
+                                    if s == "lp" {
+                                        lp(&args, options)?
+                                    } else {
+                                        qp(&args, options)?
+                                    }
+                                }
+                                "kron" => {
+                                    if i + 1 < function.len() {
+                                        kron(&a, &function.remove(i + 1))?
+                                    } else {
+                                        return Err("not enough args");
+                                    }
+                                }
+                                "repmat" => {
+                                    if i + 1 < function.len() {
+                                        let rows = size(function.remove(i + 1))?;
+                                        let cols = if i + 1 < function.len() {
+                                            Some(size(function.remove(i + 1))?)
+                                        } else {
+                                            None
+                                        };
+                                        repmat(&a, rows, cols)?
+                                    } else {
+                                        return Err("not enough args");
+                                    }
+                                }
+                                "diag" => match a {
+                                    Vector(v) => {
+                                        let mut mat =
+                                            vec![
+                                                vec![
+                                                    Number::from(Complex::new(options.prec), None);
+                                                    v.len()
+                                                ];
+                                                v.len()
+                                            ];
+                                        for (i, n) in v.into_iter().enumerate() {
+                                            mat[i][i] = n
+                                        }
+                                        Matrix(mat)
+                                    }
+                                    Matrix(m) => Vector(
+                                        m.iter()
+                                            .enumerate()
+                                            .filter_map(|(i, v)| v.get(i).cloned())
+                                            .collect(),
+                                    ),
+                                    _ => return Err("diag needs vec or mat"),
+                                },
+                                "zeros" | "ones" => {
+                                    let n = Number::from(
+                                        Complex::with_val(options.prec, (s == "ones") as u32),
+                                        None,
+                                    );
+                                    let rows = size(a)?;
+                                    if i + 1 < function.len() {
+                                        let cols = size(function.remove(i + 1))?;
+                                        Matrix(vec![vec![n; cols]; rows])
+                                    } else {
+                                        Vector(vec![n; rows])
+                                    }
+                                }
+                                "index" | "index_set" => {
+                                    let value = if s == "index_set" {
+                                        if i + 1 < function.len() {
+                                            Some(function.remove(i + 1))
+                                        } else {
+                                            return Err("not enough args");
+                                        }
+                                    } else {
+                                        None
+                                    };
+                                    let mut spec = Vec::new();
+                                    while i + 2 < function.len() {
+                                        let mode = function
+                                            .remove(i + 1)
+                                            .num()?
+                                            .number
+                                            .real()
+                                            .to_integer()
+                                            .unwrap_or_default()
+                                            .to_usize()
+                                            .unwrap_or_default();
+                                        spec.push((mode, function.remove(i + 1)))
+                                    }
+                                    match value {
+                                        Some(value) => index_set(&a, &value, &spec)?,
+                                        None => index(&a, &spec)?,
+                                    }
+                                }
+                                "einsum" => {
+                                    if i + 1 < function.len() {
+                                        let mut operands =
+                                            vec![(a, axes(&function.remove(i + 1))?)];
+                                        let mut out = None;
+                                        while i + 1 < function.len() {
+                                            let t = function.remove(i + 1);
+                                            if i + 1 < function.len() {
+                                                operands.push((t, axes(&function.remove(i + 1))?))
+                                            } else {
+                                                out = Some(axes(&t)?)
+                                            }
+                                        }
+                                        einsum(&operands, out.as_deref())?
+                                    } else {
+                                        return Err("not enough args");
+                                    }
+                                }
+                                _ => {
+                                    let axes = if i + 1 < function.len() {
+                                        axes(&function.remove(i + 1))?
+                                    } else {
+                                        (0..a.tensor()?.0.len()).rev().collect()
+                                    };
+                                    permute(&a, &axes)?
+                                }
+                            }
+                        }
+                        a if is_interval_set(&a)
+                            && matches!(
+                                s.as_str(),
+                                "union"
+                                    | "intersection"
+                                    | "set_difference"
+                                    | "is_disjoint"
+                                    | "complement"
+                                    | "card"
+                                    | "element"
+                            ) =>
+                        {
+                            match s.as_str() {
+                                "union" | "intersection" | "set_difference" | "is_disjoint" => {
+                                    if function.len() > i + 1 {
+                                        let a = to_intervals(&a)?;
+                                        let b = to_intervals(&function.remove(i + 1))?;
+                                        match s.as_str() {
+                                            "union" => {
+                                                let mut a = a;
+                                                a.extend(b);
+                                                from_intervals(a, options.prec)
+                                            }
+                                            "intersection" => from_intervals(
+                                                interval_intersection(&a, &b),
+                                                options.prec,
+                                            ),
+                                            "set_difference" => from_intervals(
+                                                interval_intersection(
+                                                    &a,
+                                                    &interval_complement(b, options.prec),
+                                                ),
+                                                options.prec,
+                                            ),
+                                            _ => NumStr::new(Number::from(
+                                                Complex::with_val(
+                                                    options.prec,
+                                                    interval_intersection(&a, &b).is_empty(),
+                                                ),
+                                                None,
+                                            )),
+                                        }
+                                    } else {
+                                        return Err("not enough args");
+                                    }
+                                }
+                                "complement" => from_intervals(
+                                    interval_complement(to_intervals(&a)?, options.prec),
+                                    options.prec,
+                                ),
+                                "card" => {
+                                    let a = normalize_intervals(to_intervals(&a)?);
+                                    NumStr::new(Number::from(
+                                        if a.iter().all(|(s, e, _, _)| s == e) {
+                                            Complex::with_val(options.prec, a.len())
+                                        } else {
+                                            Complex::with_val(options.prec, Constant::Infinity)
+                                        },
+                                        None,
+                                    ))
+                                }
+                                "element" => {
+                                    if function.len() > i + 1 {
+                                        let x = function.remove(i + 1).num()?;
+                                        NumStr::new(Number::from(
+                                            Complex::with_val(
+                                                options.prec,
+                                                interval_contains(
+                                                    &to_intervals(&a)?,
+                                                    x.number.real(),
+                                                ),
+                                            ),
+                                            None,
+                                        ))
+                                    } else {
+                                        return Err("not enough args");
+                                    }
+                                }
+                                _ => unreachable!(),
+                            }
+                        }
                         Matrix(a) => match s.as_str() {
+                            "cov" | "covariance" | "corr" | "spearman" | "kendall" | "zscore"
+                            | "describe" | "geo_mean" | "sd" | "standarddeviation" | "σ"
+                            | "variance" | "var" | "quartiles" | "percentile"
+                            | "percentilerank" | "kurtosis" | "skew" | "skewness" | "mean"
+                            | "μ" | "median" | "mode" | "max" | "min"
+                                if function.len()
+                                    > i + 1
+                                        + usize::from(matches!(
+                                            s.as_str(),
+                                            "percentile" | "percentilerank"
+                                        ))
+                                    || !matches!(
+                                        s.as_str(),
+                                        "mean" | "μ" | "median" | "mode" | "max" | "min"
+                                    ) =>
+                            {
+                                let arg = if matches!(s.as_str(), "percentile" | "percentilerank") {
+                                    if i + 1 < function.len() {
+                                        Some(function.remove(i + 1))
+                                    } else {
+                                        return Err("not enough input");
+                                    }
+                                } else {
+                                    None
+                                };
+                                // axis 0 runs down the columns and axis 1 along the rows, without
+                                // one the matrix functions take columns as variables and the rest
+                                // use every entry like mean and median always have
+                                let axis = if i + 1 < function.len() {
+                                    match integer(function.remove(i + 1)) {
+                                        Ok(n) if n == 0 => Some(0),
+                                        Ok(n) if n == 1 => Some(1),
+                                        _ => return Err("axis must be 0 or 1"),
+                                    }
+                                } else if matches!(
+                                    s.as_str(),
+                                    "cov"
+                                        | "covariance"
+                                        | "corr"
+                                        | "spearman"
+                                        | "kendall"
+                                        | "zscore"
+                                        | "describe"
+                                ) {
+                                    Some(0)
+                                } else {
+                                    None
+                                };
+                                if let Some(axis) = axis {
+                                    let a = if axis == 0 { a } else { transpose(&a) };
+                                    match s.as_str() {
+                                        "cov" | "covariance" | "corr" | "spearman" | "kendall" => {
+                                            Matrix(correlation_matrix(s, &a, options.prec)?)
+                                        }
+                                        _ => {
+                                            let mut res = Vec::new();
+                                            for v in transpose(&a) {
+                                                res.push(match s.as_str() {
+                                                    "zscore" => Vector(zscore(&v, options.prec)),
+                                                    "describe" => {
+                                                        Vector(describe(v, options.prec)?)
+                                                    }
+                                                    _ => {
+                                                        let mut func =
+                                                            vec![Func(s.clone()), Vector(v)];
+                                                        func.extend(arg.clone());
+                                                        do_math(func, options, func_vars.clone())?
+                                                    }
+                                                })
+                                            }
+                                            if res.iter().all(|r| matches!(r, Num(_))) {
+                                                Vector(
+                                                    res.into_iter()
+                                                        .map(|r| r.num())
+                                                        .collect::<Result<
+                                                            Vec<Number<Integer, Float, Complex>>,
+                                                            &str,
+                                                        >>(
+                                                        )?,
+                                                )
+                                            } else {
+                                                let res = res
+                                                    .into_iter()
+                                                    .map(|r| r.vec())
+                                                    .collect::<Result<
+                                                        Vec<Vec<Number<Integer, Float, Complex>>>,
+                                                        &str,
+                                                    >>(
+                                                    )?;
+                                                if res.iter().any(|r| r.len() != res[0].len()) {
+                                                    return Err("uneven results");
+                                                }
+                                                let res = transpose(&res);
+                                                Matrix(if axis == 0 {
+                                                    res
+                                                } else {
+                                                    transpose(&res)
+                                                })
+                                            }
+                                        }
+                                    }
+                                } else {
+                                    let mut func = vec![
+                                        Func(s.clone()),
+                                        Vector(a.into_iter().flatten().collect()),
+                                    ];
+                                    func.extend(arg);
+                                    do_math(func, options, func_vars.clone())?
+                                }
+                            }
                             "plane" => {
                                 if a.len() != 3 || a.iter().any(|a| a.len() != 3) {
                                     return Err("dimensions too high");
@@ -1052,7 +1785,28 @@ pub fn do_math<
                                 None,
                             )),
                             "tr" | "trace" => NumStr::new(trace(&a)),
-                            "det" | "norm" | "determinant" => NumStr::new(determinant(&a)?),
+                            "norm" if i + 1 < function.len() => NumStr::new(matrix_norm(
+                                &a,
+                                function.remove(i + 1).num()?.number.real().clone(),
+                            )?),
+                            "norm" | "frobenius" => NumStr::new(frobenius(&a)?),
+                            "det" | "determinant" => NumStr::new(determinant(&a)?),
+                            "orth" => Matrix(gram_schmidt(&transpose(&a))?),
+                            "gram_schmidt" => Matrix(gram_schmidt(&a)?),
+                            "cond" => NumStr::new(cond(&a)?),
+                            "is_orthogonal" | "is_hermitian" | "is_posdef" => {
+                                NumStr::new(Number::from(
+                                    Complex::with_val(
+                                        options.prec,
+                                        match s.as_str() {
+                                            "is_orthogonal" => is_orthogonal(&a),
+                                            "is_hermitian" => is_hermitian(&a),
+                                            _ => is_posdef(&a),
+                                        },
+                                    ),
+                                    None,
+                                ))
+                            }
                             "part" => {
                                 if function.len() > i + 2 {
                                     match (function.remove(i + 1), function.remove(i + 1)) {
@@ -1749,64 +2503,7 @@ pub fn do_math<
                                     )
                                 }
                             }
-                            "quartiles" => {
-                                if a.len() < 2 {
-                                    return Err("not enough data");
-                                }
-                                let units = a[0].units;
-                                let a = sort(a);
-                                let half1 = &a[0..a.len() / 2];
-                                let half2 = if a.len() % 2 == 0 {
-                                    &a[a.len() / 2..a.len()]
-                                } else {
-                                    &a[a.len() / 2 + 1..a.len()]
-                                };
-                                if half1.len() % 2 == 0 {
-                                    Vector(vec![
-                                        Number::from(
-                                            (half1[half1.len() / 2 - 1].number.clone()
-                                                + half1[half1.len() / 2].number.clone())
-                                                / 2,
-                                            units,
-                                        ),
-                                        Number::from(
-                                            if a.len() % 2 == 0 {
-                                                (half1[half1.len().saturating_sub(1)]
-                                                    .number
-                                                    .clone()
-                                                    + half2[0].number.clone())
-                                                    / 2
-                                            } else {
-                                                a[a.len() / 2].number.clone()
-                                            },
-                                            units,
-                                        ),
-                                        Number::from(
-                                            (half2[half2.len() / 2 - 1].number.clone()
-                                                + half2[half2.len() / 2].number.clone())
-                                                / 2,
-                                            units,
-                                        ),
-                                    ])
-                                } else {
-                                    Vector(vec![
-                                        Number::from(half1[half1.len() / 2].number.clone(), units),
-                                        Number::from(
-                                            if a.len() % 2 == 0 {
-                                                (half1[half1.len().saturating_sub(1)]
-                                                    .number
-                                                    .clone()
-                                                    + half2[0].number.clone())
-                                                    / 2
-                                            } else {
-                                                a[a.len() / 2].number.clone()
-                                            },
-                                            units,
-                                        ),
-                                        Number::from(half2[half2.len() / 2].number.clone(), units),
-                                    ])
-                                }
-                            }
+                            "quartiles" => Vector(quartiles(a)?),
                             "percentile" => {
                                 if function.len() <= i + 1 {
                                     return Err("not enough input");
@@ -1845,17 +2542,98 @@ pub fn do_math<
                                     None,
                                 ))
                             }
-                            "to_freq" => {
+                            "kde" | "ecdf" => {
+                                if function.len() <= i + 1 {
+                                    return Err("not enough input");
+                                }
                                 if a.is_empty() {
-                                    return Err("bad list");
+                                    return Err("not enough data");
                                 }
-                                let mut a = sort(a);
-                                let mut last = a[0].clone();
-                                let mut count = 1;
-                                a.remove(0);
-                                let mut mat = Vec::new();
-                                for a in a {
-                                    if a != last {
+                                let data = a
+                                    .iter()
+                                    .map(|a| a.number.real().clone())
+                                    .collect::<Vec<Float>>();
+                                let x = function.remove(i + 1);
+                                let h = if s == "kde" {
+                                    if i + 1 < function.len() {
+                                        function.remove(i + 1).num()?.number.real().clone()
+                                    } else {
+                                        silverman(&data)?
+                                    }
+                                } else {
+                                    Float::new(options.prec)
+                                };
+                                if s == "kde"
+                                    && !(h.is_finite() && h.cmp0() == Some(Ordering::Greater))
+                                {
+                                    return Err("bandwidth must be positive");
+                                }
+                                let units = if s == "kde" {
+                                    a[0].units.map(|u| u.pow(-1.0))
+                                } else {
+                                    None
+                                };
+                                let f = |x: &Number<Integer, Float, Complex>| {
+                                    let x = x.number.real();
+                                    Number::from(
+                                        if s == "kde" {
+                                            kde(&data, x, &h)
+                                        } else {
+                                            ecdf(&data, x)
+                                        }
+                                        .into(),
+                                        units,
+                                    )
+                                };
+                                match x {
+                                    Num(x) => NumStr::new(f(&x)),
+                                    Vector(x) => Vector(x.iter().map(f).collect()),
+                                    _ => return Err("x must be a number or vector"),
+                                }
+                            }
+                            "hist" | "hist_sturges" | "hist_fd" | "hist_scott" => {
+                                let x = a
+                                    .iter()
+                                    .map(|a| a.number.real().clone())
+                                    .collect::<Vec<Float>>();
+                                let edges = if s == "hist" && i + 1 < function.len() {
+                                    match function.remove(i + 1) {
+                                        Num(n) => bin_edges(
+                                            &x,
+                                            n.number
+                                                .real()
+                                                .to_integer()
+                                                .unwrap_or_default()
+                                                .to_usize()
+                                                .unwrap_or_default(),
+                                        )?,
+                                        Vector(e) => {
+                                            e.into_iter().map(|e| e.number.real().clone()).collect()
+                                        }
+                                        _ => return Err("bins must be a number or edge list"),
+                                    }
+                                } else {
+                                    bin_edges(
+                                        &x,
+                                        bin_count(
+                                            &x,
+                                            if s == "hist" { "sturges" } else { &s[5..] },
+                                        )?,
+                                    )?
+                                };
+                                Matrix(histogram(&a, &edges)?)
+                            }
+                            "to_freq" => {
+                                if a.is_empty() {
+                                    return Err("bad list");
+                                }
+                                let mut a = sort(a);
+                                let mut last = a[0].clone();
+                                let mut count = 1;
+                                a.remove(0);
+                                let mut mat = Vec::new();
+                                for a in a {
+                                    if a != last {
                                         mat.push(vec![
                                             last.clone(),
                                             Number::from(
@@ -1921,30 +2699,15 @@ pub fn do_math<
                                 a[0].units,
                             )),
                             "variance" | "var" => NumStr::new(variance(&a, None, options.prec)),
-                            "covariance" | "cov" => {
-                                let mut sum = Complex::new(options.prec);
+                            "covariance" | "cov" | "corr" | "spearman" | "kendall" => {
                                 if function.len() <= i + 1 {
                                     return Err("not enough input");
                                 }
                                 let b = function.remove(i + 1).vec()?;
-                                if a.len() != b.len() {
-                                    return Err("different sized data sets");
-                                }
-                                let ma = a.iter().fold(Complex::new(options.prec), |sum, val| {
-                                    sum + val.number.clone()
-                                }) / a.len();
-                                let mb = b.iter().fold(Complex::new(options.prec), |sum, val| {
-                                    sum + val.number.clone()
-                                }) / b.len();
-                                for (a, b) in a.iter().zip(b.iter()) {
-                                    sum += (a.number.clone() - ma.clone())
-                                        * (b.number.clone() - mb.clone());
-                                }
-                                NumStr::new(Number::from(
-                                    sum / (a.len() - 1),
-                                    mul_units(a[0].units, b[0].units),
-                                ))
+                                NumStr::new(correlation(s, &a, &b, options.prec)?)
                             }
+                            "zscore" => Vector(zscore(&a, options.prec)),
+                            "describe" => Vector(describe(a, options.prec)?),
                             "all" => {
                                 let mut res = true;
                                 for a in a {
@@ -2058,6 +2821,28 @@ pub fn do_math<
                                 Complex::with_val(options.prec, a.len()),
                                 None,
                             )),
+                            "norm" if i + 1 < function.len() => {
+                                let p = function.remove(i + 1).num()?.number.real().clone();
+                                if p < 1 {
+                                    return Err("unsupported norm");
+                                }
+                                let units = a[0].units;
+                                let mut n = Float::new(options.prec);
+                                for i in a {
+                                    let x = i.number.abs().real().clone();
+                                    if p.is_infinite() {
+                                        if x > n {
+                                            n = x
+                                        }
+                                    } else {
+                                        n += x.pow(p.clone())
+                                    }
+                                }
+                                if !p.is_infinite() {
+                                    n = n.pow(p.recip())
+                                }
+                                NumStr::new(Number::from(Complex::with_val(options.prec, n), units))
+                            }
                             "norm" => {
                                 let units = a[0].units;
                                 let mut n = Complex::new(options.prec);
@@ -2357,22 +3142,20 @@ pub fn do_math<
                                     let num = num.number;
                                     if num.imag().clone().is_zero() {
                                         if num.real().clone().fract().is_zero() {
-                                            let mut vec = Vec::new();
-                                            let n = num
-                                                .real()
-                                                .to_integer()
-                                                .unwrap_or_default()
-                                                .to_usize()
-                                                .unwrap_or_default();
-                                            for i in 1..=n {
-                                                if n % i == 0 {
-                                                    vec.push(Number::from(
-                                                        Complex::with_val(options.prec, i),
-                                                        None,
-                                                    ));
-                                                }
-                                            }
-                                            mat.push(vec);
+                                            let n = num.real().to_integer().unwrap_or_default();
+                                            mat.push(if n < 1 {
+                                                Vec::new()
+                                            } else {
+                                                divisors(&factorize(n, options.progress)?)
+                                                    .into_iter()
+                                                    .map(|d| {
+                                                        Number::from(
+                                                            Complex::with_val(options.prec, d),
+                                                            None,
+                                                        )
+                                                    })
+                                                    .collect()
+                                            });
                                         } else {
                                             fail = true;
                                             break;
@@ -2392,13 +3175,43 @@ pub fn do_math<
                                 }
                             }
                             "union" => {
+                                if function.len() > i + 1 {
+                                    match function.remove(i + 1) {
+                                        Vector(b) => {
+                                            let mut a = a;
+                                            a.extend(b);
+                                            a = sort(a);
+                                            a.dedup();
+                                            Vector(a)
+                                        }
+                                        b @ Tensor(_, _) => {
+                                            let mut a = to_intervals(&Vector(a))?;
+                                            a.extend(to_intervals(&b)?);
+                                            from_intervals(a, options.prec)
+                                        }
+                                        _ => return Err("arg not vector"),
+                                    }
+                                } else {
+                                    return Err("arg not vector");
+                                }
+                            }
+                            "munion" | "mintersect" => {
                                 if function.len() > i + 1 {
                                     if let Vector(b) = function.remove(i + 1) {
-                                        let mut a = a;
-                                        a.extend(b);
-                                        a = sort(a);
-                                        a.dedup();
-                                        Vector(a)
+                                        let mut rest = b;
+                                        let mut v = Vec::new();
+                                        for n in a {
+                                            if let Some(p) = rest.iter().position(|m| m == &n) {
+                                                rest.remove(p);
+                                                v.push(n)
+                                            } else if s == "munion" {
+                                                v.push(n)
+                                            }
+                                        }
+                                        if s == "munion" {
+                                            v.extend(rest)
+                                        }
+                                        Vector(sort(v))
                                     } else {
                                         return Err("arg not vector");
                                     }
@@ -2406,21 +3219,56 @@ pub fn do_math<
                                     return Err("arg not vector");
                                 }
                             }
+                            "card" => {
+                                let mut a = sort(a);
+                                a.dedup();
+                                NumStr::new(Number::from(
+                                    Complex::with_val(options.prec, a.len()),
+                                    None,
+                                ))
+                            }
+                            "is_disjoint" => {
+                                if function.len() > i + 1 {
+                                    let disjoint = match function.remove(i + 1) {
+                                        Vector(b) => !a.iter().any(|n| b.contains(n)),
+                                        b @ Tensor(_, _) => interval_intersection(
+                                            &to_intervals(&Vector(a))?,
+                                            &to_intervals(&b)?,
+                                        )
+                                        .is_empty(),
+                                        _ => return Err("arg not vector"),
+                                    };
+                                    NumStr::new(Number::from(
+                                        Complex::with_val(options.prec, disjoint),
+                                        None,
+                                    ))
+                                } else {
+                                    return Err("arg not vector");
+                                }
+                            }
                             "intersection" => {
                                 if function.len() > i + 1 {
-                                    if let Vector(b) = function.remove(i + 1) {
-                                        let mut v = Vec::new();
-                                        'main: for n1 in a {
-                                            for n2 in &b {
-                                                if &n1 == n2 {
-                                                    v.push(n2.clone());
-                                                    continue 'main;
+                                    match function.remove(i + 1) {
+                                        Vector(b) => {
+                                            let mut v = Vec::new();
+                                            'main: for n1 in a {
+                                                for n2 in &b {
+                                                    if &n1 == n2 {
+                                                        v.push(n2.clone());
+                                                        continue 'main;
+                                                    }
                                                 }
                                             }
+                                            Vector(v)
                                         }
-                                        Vector(v)
-                                    } else {
-                                        return Err("arg not vector");
+                                        b @ Tensor(_, _) => from_intervals(
+                                            interval_intersection(
+                                                &to_intervals(&Vector(a))?,
+                                                &to_intervals(&b)?,
+                                            ),
+                                            options.prec,
+                                        ),
+                                        _ => return Err("arg not vector"),
                                     }
                                 } else {
                                     return Err("arg not vector");
@@ -2428,19 +3276,30 @@ pub fn do_math<
                             }
                             "set_difference" => {
                                 if function.len() > i + 1 {
-                                    if let Vector(b) = function.remove(i + 1) {
-                                        let mut v = Vec::new();
-                                        'main: for n1 in a {
-                                            for n2 in &b {
-                                                if &n1 == n2 {
-                                                    continue 'main;
+                                    match function.remove(i + 1) {
+                                        Vector(b) => {
+                                            let mut v = Vec::new();
+                                            'main: for n1 in a {
+                                                for n2 in &b {
+                                                    if &n1 == n2 {
+                                                        continue 'main;
+                                                    }
                                                 }
+                                                v.push(n1);
                                             }
-                                            v.push(n1);
+                                            Vector(v)
                                         }
-                                        Vector(v)
-                                    } else {
-                                        return Err("arg not vector");
+                                        b @ Tensor(_, _) => from_intervals(
+                                            interval_intersection(
+                                                &to_intervals(&Vector(a))?,
+                                                &interval_complement(
+                                                    to_intervals(&b)?,
+                                                    options.prec,
+                                                ),
+                                            ),
+                                            options.prec,
+                                        ),
+                                        _ => return Err("arg not vector"),
                                     }
                                 } else {
                                     return Err("arg not vector");
@@ -2508,6 +3367,10 @@ pub fn do_math<
                                 }
                                 Matrix(m)
                             }
+                            "complement" => from_intervals(
+                                interval_complement(to_intervals(&Vector(a))?, options.prec),
+                                options.prec,
+                            ),
                             "set_fix" => {
                                 let mut a = sort(a);
                                 a.dedup();
@@ -2616,21 +3479,141 @@ pub fn do_math<
                                     return Err("bad range");
                                 }
                             }
+                            "permutations" | "combinations" => {
+                                let k = if i + 1 < function.len() {
+                                    integer(function.remove(i + 1))?
+                                        .to_usize()
+                                        .unwrap_or(usize::MAX)
+                                } else if s == "permutations" {
+                                    a.len()
+                                } else {
+                                    return Err("not enough args");
+                                };
+                                if k > a.len() {
+                                    return Err("k larger than vector");
+                                }
+                                Matrix(
+                                    if s == "permutations" {
+                                        permutations(a.len(), k)?
+                                    } else {
+                                        combinations(a.len(), k)?
+                                    }
+                                    .into_iter()
+                                    .map(|v| v.into_iter().map(|j| a[j].clone()).collect())
+                                    .collect(),
+                                )
+                            }
+                            "perm_rank" | "perm_unrank" => {
+                                // nan equals nothing, so it has no place in the order
+                                if a.iter().any(|x| x.number.real().is_nan()) {
+                                    return Err("elements must be comparable");
+                                }
+                                let mut sorted = a.clone();
+                                sorted.sort_by(|x, y| {
+                                    x.number
+                                        .real()
+                                        .partial_cmp(y.number.real())
+                                        .unwrap_or(Ordering::Equal)
+                                });
+                                if sorted.windows(2).any(|w| w[0].number == w[1].number) {
+                                    return Err("elements must be distinct");
+                                }
+                                if s == "perm_rank" {
+                                    let perm = a
+                                        .iter()
+                                        .map(|x| {
+                                            sorted
+                                                .iter()
+                                                .position(|y| y.number == x.number)
+                                                .ok_or("elements must be comparable")
+                                        })
+                                        .collect::<Result<Vec<usize>, &'static str>>()?;
+                                    integer_num(Some(perm_rank(&perm)), options)
+                                } else if i + 1 < function.len() {
+                                    let r = integer(function.remove(i + 1))?;
+                                    match perm_unrank(a.len(), r) {
+                                        Some(perm) => Vector(
+                                            perm.into_iter().map(|j| sorted[j].clone()).collect(),
+                                        ),
+                                        None => integer_num(None, options),
+                                    }
+                                } else {
+                                    return Err("not enough args");
+                                }
+                            }
+                            "from_cf" | "convergents" => {
+                                let int = |v: Vec<Number<Integer, Float, Complex>>| {
+                                    v.into_iter()
+                                        .map(|n| integer(NumStr::new(n)))
+                                        .collect::<Result<Vec<Integer>, &'static str>>()
+                                };
+                                let terms = int(a)?;
+                                if s == "from_cf" {
+                                    let period = if i + 1 < function.len() {
+                                        int(function.remove(i + 1).vec()?)?
+                                    } else {
+                                        Vec::new()
+                                    };
+                                    NumStr::new(Number::from(
+                                        Complex::with_val(
+                                            options.prec,
+                                            from_continued_fraction(&terms, &period, options.prec),
+                                        ),
+                                        None,
+                                    ))
+                                } else {
+                                    Matrix(
+                                        convergents(&terms)
+                                            .into_iter()
+                                            .map(|(p, q)| {
+                                                vec![
+                                                    Number::from(
+                                                        Complex::with_val(options.prec, p),
+                                                        None,
+                                                    ),
+                                                    Number::from(
+                                                        Complex::with_val(options.prec, q),
+                                                        None,
+                                                    ),
+                                                ]
+                                            })
+                                            .collect(),
+                                    )
+                                }
+                            }
                             "rationalize" => Matrix(
                                 a.iter()
                                     .map(|c| c_to_rational(c.number.clone(), options))
                                     .collect::<Vec<Vec<Number<Integer, Float, Complex>>>>(),
                             ),
+                            "crt" => {
+                                if i + 1 < function.len() {
+                                    let m = function.remove(i + 1).vec()?;
+                                    if a.len() != m.len() {
+                                        return Err("crt needs equal length vectors");
+                                    }
+                                    let int = |v: Vec<Number<Integer, Float, Complex>>| {
+                                        v.into_iter()
+                                            .map(|n| integer(NumStr::new(n)))
+                                            .collect::<Result<Vec<Integer>, &'static str>>()
+                                    };
+                                    integer_num(crt(int(a)?, int(m)?).map(|(x, _)| x), options)
+                                } else {
+                                    return Err("not enough args");
+                                }
+                            }
                             "prime_factors" => {
                                 if a.len() != 2 {
                                     return Err("expected vector length 2");
                                 }
-                                let mut p1 = prime_factors(
+                                let mut p1 = factorize(
                                     a[0].number.real().to_integer().unwrap_or_default(),
-                                );
-                                let p2 = prime_factors(
+                                    options.progress,
+                                )?;
+                                let p2 = factorize(
                                     a[1].number.real().to_integer().unwrap_or_default(),
-                                );
+                                    options.progress,
+                                )?;
                                 for p in p1.iter_mut() {
                                     for m in &p2 {
                                         if p.0 == m.0 {
@@ -2683,6 +3666,72 @@ pub fn do_math<
                         },
                         _ => match s.as_str() {
                             "rationalize" => Vector(c_to_rational(arg.num()?.number, options)),
+                            "perm_unrank" => {
+                                if i + 1 < function.len() {
+                                    let n = integer(arg)?.to_usize().unwrap_or_default();
+                                    let r = integer(function.remove(i + 1))?;
+                                    match perm_unrank(n, r) {
+                                        Some(perm) => Vector(
+                                            perm.into_iter()
+                                                .map(|j| {
+                                                    Number::from(
+                                                        Complex::with_val(options.prec, j + 1),
+                                                        None,
+                                                    )
+                                                })
+                                                .collect(),
+                                        ),
+                                        None => integer_num(None, options),
+                                    }
+                                } else {
+                                    return Err("not enough args");
+                                }
+                            }
+                            "cf" | "continued_fraction" | "period" | "convergents" => {
+                                let x = arg.num()?.number.real().clone();
+                                let n = if i + 1 < function.len() {
+                                    integer(function.remove(i + 1))?.to_usize()
+                                } else {
+                                    None
+                                };
+                                let (terms, start) = continued_fraction(x, n);
+                                let num = |n: Integer| {
+                                    Number::from(Complex::with_val(options.prec, n), None)
+                                };
+                                if s == "convergents" {
+                                    Matrix(
+                                        convergents(&terms)
+                                            .into_iter()
+                                            .map(|(p, q)| vec![num(p), num(q)])
+                                            .collect(),
+                                    )
+                                } else if s == "period" {
+                                    Vector(
+                                        terms
+                                            .into_iter()
+                                            .skip(start.unwrap_or(usize::MAX))
+                                            .map(num)
+                                            .collect(),
+                                    )
+                                } else {
+                                    Vector(terms.into_iter().map(num).collect())
+                                }
+                            }
+                            "best_rational" => {
+                                if i + 1 < function.len() {
+                                    let x = arg.num()?.number.real().clone();
+                                    let max = integer(function.remove(i + 1))?;
+                                    match best_rational(x, max) {
+                                        Some((p, q)) => Vector(vec![
+                                            Number::from(Complex::with_val(options.prec, p), None),
+                                            Number::from(Complex::with_val(options.prec, q), None),
+                                        ]),
+                                        None => integer_num(None, options),
+                                    }
+                                } else {
+                                    return Err("not enough args");
+                                }
+                            }
                             "domain_coloring_rgb" => {
                                 let pi = Float::with_val(options.prec, Constant::Pi);
                                 let num = arg.num()?.number;
@@ -2697,16 +3746,12 @@ pub fn do_math<
                                 Vector(hsv2rgb(hue * 3, sat, val))
                             }
                             "multinomial" => {
-                                let mut numerator: Complex = arg.num()?.number + 1;
-                                let mut divisor = gamma(numerator.clone());
+                                let mut k = vec![arg.num()?.number];
                                 while i + 1 < function.len() && !matches!(&function[i + 1], Func(_))
                                 {
-                                    let temp = function.remove(i + 1).num()?.number;
-                                    numerator += temp.clone();
-                                    let temp = temp.clone() + 1;
-                                    divisor *= gamma(temp);
+                                    k.push(function.remove(i + 1).num()?.number)
                                 }
-                                NumStr::new(Number::from(gamma(numerator) / divisor, None))
+                                NumStr::new(Number::from(multinomial(&k), None))
                             }
                             "Β" | "B" | "beta" => {
                                 if i + 1 < function.len() {
@@ -3096,6 +4141,33 @@ pub fn do_math<
                                     return Err("not enough args");
                                 }
                             }
+                            "norm_inv" | "lognorm_inv" | "gamma_inv" | "beta_inv"
+                            | "binomial_inv" | "poisson_inv" | "geometric_inv"
+                            | "neg_binomial_inv" | "t_pdf" | "t_cdf" | "t_inv" | "chi2_pdf"
+                            | "chi2_cdf" | "chi2_inv" | "f_pdf" | "f_cdf" | "f_inv"
+                            | "exponential_pdf" | "exponential_cdf" | "exponential_inv"
+                            | "uniform_pdf" | "uniform_cdf" | "uniform_inv" | "weibull_pdf"
+                            | "weibull_cdf" | "weibull_inv" | "cauchy_pdf" | "cauchy_cdf"
+                            | "cauchy_inv" | "laplace_pdf" | "laplace_cdf" | "laplace_inv" => {
+                                let x = arg.num()?.number.real().clone();
+                                let mut args = Vec::new();
+                                while i + 1 < function.len() && !matches!(&function[i + 1], Func(_))
+                                {
+                                    args.push(function.remove(i + 1).num()?.number.real().clone())
+                                }
+                                let (dist, kind) = s.rsplit_once('_').unwrap_or_default();
+                                NumStr::new(Number::from(
+                                    Complex::with_val(
+                                        options.prec,
+                                        match kind {
+                                            "pdf" => pdf(dist, x, &args)?,
+                                            "cdf" => cdf(dist, x, &args)?,
+                                            _ => quantile(dist, x, &args)?,
+                                        },
+                                    ),
+                                    None,
+                                ))
+                            }
                             "geometric_cdf" => {
                                 if i + 1 < function.len() {
                                     let k = arg.num()?.number;
@@ -3428,7 +4500,7 @@ pub fn do_math<
                                 if a.imag().is_zero() {
                                     if a.real().clone().fract().is_zero() {
                                         let n = a.real().to_integer().unwrap_or_default();
-                                        let m = prime_factors(n);
+                                        let m = factorize(n, options.progress)?;
                                         if m.is_empty() {
                                             NumStr::new(Number::from(
                                                 Complex::with_val(options.prec, Constant::Nan),
@@ -3453,8 +4525,8 @@ pub fn do_math<
                                             )
                                         }
                                     } else if let Some(a) = rationalize(a.real().clone(), options) {
-                                        let mut p1 = prime_factors(a.0);
-                                        let p2 = prime_factors(a.1);
+                                        let mut p1 = factorize(a.0, options.progress)?;
+                                        let p2 = factorize(a.1, options.progress)?;
                                         for p in p1.iter_mut() {
                                             for m in &p2 {
                                                 if p.0 == m.0 {
@@ -3503,22 +4575,20 @@ pub fn do_math<
                                 let a = arg.num()?.number;
                                 if a.imag().clone().is_zero() {
                                     if a.real().clone().fract().is_zero() {
-                                        let mut vec = Vec::new();
-                                        let n = a
-                                            .real()
-                                            .to_integer()
-                                            .unwrap_or_default()
-                                            .to_usize()
-                                            .unwrap_or_default();
-                                        for i in 1..=n {
-                                            if n % i == 0 {
-                                                vec.push(Number::from(
-                                                    Complex::with_val(options.prec, i),
-                                                    None,
-                                                ));
-                                            }
-                                        }
-                                        Vector(vec)
+                                        let n = a.real().to_integer().unwrap_or_default();
+                                        Vector(if n < 1 {
+                                            Vec::new()
+                                        } else {
+                                            divisors(&factorize(n, options.progress)?)
+                                                .into_iter()
+                                                .map(|d| {
+                                                    Number::from(
+                                                        Complex::with_val(options.prec, d),
+                                                        None,
+                                                    )
+                                                })
+                                                .collect()
+                                        })
                                     } else {
                                         NumStr::new(Number::from(
                                             Complex::with_val(options.prec, Constant::Nan),
@@ -3532,6 +4602,293 @@ pub fn do_math<
                                     ))
                                 }
                             }
+                            "modpow" => {
+                                if i + 2 < function.len() {
+                                    let b = integer(arg)?;
+                                    let e = integer(function.remove(i + 1))?;
+                                    let m = integer(function.remove(i + 1))?;
+                                    integer_num(modpow(b, e, &m), options)
+                                } else {
+                                    return Err("not enough args");
+                                }
+                            }
+                            "modinv" => {
+                                if i + 1 < function.len() {
+                                    let a = integer(arg)?;
+                                    let m = integer(function.remove(i + 1))?.abs();
+                                    integer_num(modinv(a, &m), options)
+                                } else {
+                                    return Err("not enough args");
+                                }
+                            }
+                            "egcd" => {
+                                if i + 1 < function.len() {
+                                    let (g, x, y) =
+                                        egcd(integer(arg)?, integer(function.remove(i + 1))?);
+                                    Vector(
+                                        [g, x, y]
+                                            .into_iter()
+                                            .map(|n| {
+                                                Number::from(
+                                                    Complex::with_val(options.prec, n),
+                                                    None,
+                                                )
+                                            })
+                                            .collect(),
+                                    )
+                                } else {
+                                    return Err("not enough args");
+                                }
+                            }
+                            "discrete_log" | "dlog" => {
+                                if i + 2 < function.len() {
+                                    let g = integer(arg)?;
+                                    let h = integer(function.remove(i + 1))?;
+                                    let n = integer(function.remove(i + 1))?;
+                                    integer_num(discrete_log(g, h, n)?, options)
+                                } else {
+                                    return Err("not enough args");
+                                }
+                            }
+                            "primitive_root" => integer_num(
+                                primitive_root(integer(arg)?, options.progress)?,
+                                options,
+                            ),
+                            "legendre" | "jacobi" => {
+                                if i + 1 < function.len() {
+                                    let a = integer(arg)?;
+                                    let n = integer(function.remove(i + 1))?;
+                                    if s == "legendre"
+                                        && (n == 2 || n.is_probably_prime(100) == IsPrime::No)
+                                    {
+                                        return Err("legendre needs an odd prime");
+                                    }
+                                    integer_num(jacobi_symbol(a, n).map(Integer::from), options)
+                                } else {
+                                    return Err("not enough args");
+                                }
+                            }
+                            "totient" | "mobius" | "numdivisors" | "carmichael" => {
+                                let n = integer(arg)?;
+                                if n < 1 {
+                                    integer_num(None, options)
+                                } else {
+                                    let f = factorize(n, options.progress)?;
+                                    integer_num(
+                                        Some(match s.as_str() {
+                                            "totient" => totient(&f),
+                                            "mobius" => Integer::from(mobius(&f)),
+                                            "numdivisors" => sigma(0, &f),
+                                            _ => carmichael(&f),
+                                        }),
+                                        options,
+                                    )
+                                }
+                            }
+                            "sigma" => {
+                                let (k, n) = if i + 1 < function.len() {
+                                    (integer(arg)?, integer(function.remove(i + 1))?)
+                                } else {
+                                    (Integer::from(1), integer(arg)?)
+                                };
+                                match k.to_u32() {
+                                    Some(k) if n >= 1 => integer_num(
+                                        Some(sigma(k, &factorize(n, options.progress)?)),
+                                        options,
+                                    ),
+                                    _ => integer_num(None, options),
+                                }
+                            }
+                            "primepi" => {
+                                let n = integer(arg)?;
+                                if n.cmp0() == Ordering::Less {
+                                    integer_num(Some(Integer::new()), options)
+                                } else if let Some(n) =
+                                    n.to_u128().filter(|n| *n <= u64::MAX as u128)
+                                {
+                                    NumStr::new(Number::from(
+                                        Complex::with_val(options.prec, primepi(n as u64)? as u128),
+                                        None,
+                                    ))
+                                } else {
+                                    return Err("primepi input too large");
+                                }
+                            }
+                            "primes" => {
+                                let (a, b) = if i + 1 < function.len() {
+                                    (integer(arg)?, integer(function.remove(i + 1))?)
+                                } else {
+                                    (Integer::from(2), integer(arg)?)
+                                };
+                                let u = |n: Integer| {
+                                    if n.cmp0() == Ordering::Less {
+                                        Some(0)
+                                    } else {
+                                        n.to_u128()
+                                            .filter(|n| *n <= u64::MAX as u128)
+                                            .map(|n| n as u64)
+                                    }
+                                };
+                                let (Some(a), Some(b)) = (u(a), u(b)) else {
+                                    return Err("primes input too large");
+                                };
+                                Vector(
+                                    primes(a, b)?
+                                        .into_iter()
+                                        .map(|p| {
+                                            Number::from(
+                                                Complex::with_val(options.prec, p as u128),
+                                                None,
+                                            )
+                                        })
+                                        .collect(),
+                                )
+                            }
+                            "bitnot" => {
+                                let x = integer(arg)?;
+                                integer_num(Some(-x - 1), options)
+                            }
+                            "popcount" | "clz" | "ctz" => {
+                                let x = integer(arg)?;
+                                let bits = if i + 1 < function.len() {
+                                    integer(function.remove(i + 1))?.to_u32()
+                                } else {
+                                    options.word.map(|w| w.0)
+                                };
+                                let x = match bits {
+                                    Some(0) => return Err("zero bit width"),
+                                    Some(bits) => wrap_word(x, bits, false),
+                                    None => x,
+                                };
+                                let b = x.to_string_radix(2);
+                                let len = if x == 0 { 0 } else { b.len() };
+                                integer_num(
+                                    match s.as_str() {
+                                        _ if x.cmp0() == Ordering::Less => None,
+                                        "popcount" => Some(b.matches('1').count()),
+                                        "ctz" if x == 0 => bits.map(|b| b as usize),
+                                        "ctz" => Some(b.len() - b.trim_end_matches('0').len()),
+                                        _ => (bits.unwrap_or(64) as usize).checked_sub(len),
+                                    }
+                                    .map(Integer::from),
+                                    options,
+                                )
+                            }
+                            "rotl" | "rotr" => {
+                                if i + 1 < function.len() {
+                                    let x = integer(arg)?;
+                                    let n = integer(function.remove(i + 1))?;
+                                    let bits = if i + 1 < function.len() {
+                                        integer(function.remove(i + 1))?.to_u32().unwrap_or(0)
+                                    } else {
+                                        options.word.map(|w| w.0).unwrap_or(64)
+                                    };
+                                    if bits == 0 {
+                                        return Err("zero bit width");
+                                    }
+                                    let n = wrap_word(n, bits, false).to_u32().unwrap_or_default();
+                                    let n = if s == "rotl" { n } else { (bits - n) % bits };
+                                    let (q, r) = (wrap_word(x, bits, false)
+                                        * Integer::from(2).pow(n))
+                                    .div_rem(Integer::from(2).pow(bits));
+                                    integer_num(Some(q + r), options)
+                                } else {
+                                    return Err("not enough args");
+                                }
+                            }
+                            "float_bits" | "from_bits" => {
+                                let bits = if i + 1 < function.len() {
+                                    integer(function.remove(i + 1))?
+                                        .to_u32()
+                                        .unwrap_or_default()
+                                } else {
+                                    64
+                                };
+                                if !matches!(bits, 16 | 32 | 64) {
+                                    return Err("bits must be 16, 32 or 64");
+                                }
+                                if s == "float_bits" {
+                                    integer_num(float_bits(arg.num()?.number.real(), bits), options)
+                                } else {
+                                    NumStr::new(Number::from(
+                                        Complex::with_val(
+                                            options.prec,
+                                            from_bits(&integer(arg)?, bits).unwrap_or(f64::NAN),
+                                        ),
+                                        None,
+                                    ))
+                                }
+                            }
+                            "interval" | "interval_cc" | "interval_co" | "interval_oc"
+                            | "interval_oo" => {
+                                if i + 1 < function.len() {
+                                    let a = arg.num()?;
+                                    let b = function.remove(i + 1).num()?;
+                                    if i + 1 < function.len() {
+                                        return Err(
+                                            "interval spec must be \"[]\", \"[)\", \"(]\" or \"()\"",
+                                        );
+                                    }
+                                    let (l, r) = match s.as_str() {
+                                        "interval_co" => (true, false),
+                                        "interval_oc" => (false, true),
+                                        "interval_oo" => (false, false),
+                                        _ => (true, true),
+                                    };
+                                    from_intervals(
+                                        vec![(
+                                            a.clone(),
+                                            b.clone(),
+                                            l && a.number.real().is_finite(),
+                                            r && b.number.real().is_finite(),
+                                        )],
+                                        options.prec,
+                                    )
+                                } else {
+                                    return Err("not enough args");
+                                }
+                            }
+                            "qformat" => {
+                                if i + 2 < function.len() {
+                                    let x = arg.num()?.number.real().clone();
+                                    let m = integer(function.remove(i + 1))?;
+                                    let n = integer(function.remove(i + 1))?;
+                                    if m.cmp0() == Ordering::Less
+                                        || n.cmp0() == Ordering::Less
+                                        || m.significant_bits() > 16
+                                        || n.significant_bits() > 16
+                                    {
+                                        return Err("bad q format");
+                                    }
+                                    let (m, n) = (
+                                        m.to_u32().unwrap_or_default(),
+                                        n.to_u32().unwrap_or_default(),
+                                    );
+                                    match qformat(&x, m, n) {
+                                        Some((bits, raw)) => Vector(vec![
+                                            Number::from_rational(
+                                                (bits, Integer::from(1)),
+                                                None,
+                                                options.prec,
+                                            ),
+                                            Number::from_rational(
+                                                (raw, Integer::from(2).pow(n)),
+                                                None,
+                                                options.prec,
+                                            ),
+                                        ]),
+                                        None => Vector(vec![
+                                            Number::from(
+                                                Complex::with_val(options.prec, Constant::Nan),
+                                                None
+                                            );
+                                            2
+                                        ]),
+                                    }
+                                } else {
+                                    return Err("not enough args");
+                                }
+                            }
                             "unity" => {
                                 let vec = if i + 1 < function.len()
                                     && !matches!(&function[i + 1], Func(_))
@@ -3591,7 +4948,7 @@ pub fn do_math<
     i = 1;
     while i < function.len().saturating_sub(1) {
         function[i] = match &function[i] {
-            Modulo => function[i - 1].func(&function[i + 1], rem)?,
+            Modulo => wrap(function[i - 1].func(&function[i + 1], rem)?),
             Range => to(&function[i - 1], &function[i + 1])?,
             _ => {
                 i += 1;
@@ -3604,9 +4961,9 @@ pub fn do_math<
     i = function.len().saturating_sub(2);
     while i != 0 {
         function[i] = match &function[i] {
-            Exponent => function[i - 1].pow(&function[i + 1])?,
-            Tetration => function[i - 1].func(&function[i + 1], tetration)?,
-            Root => function[i - 1].func(&function[i + 1], root)?,
+            Exponent => wrap(function[i - 1].pow(&function[i + 1])?),
+            Tetration => wrap(function[i - 1].func(&function[i + 1], tetration)?),
+            Root => wrap(function[i - 1].func(&function[i + 1], root)?),
             _ => {
                 i -= 1;
                 continue;
@@ -3619,7 +4976,7 @@ pub fn do_math<
     i = 1;
     while i < function.len().saturating_sub(1) {
         function[i] = match &function[i] {
-            InternalMultiplication => function[i - 1].mul(&function[i + 1])?,
+            InternalMultiplication => wrap(function[i - 1].mul(&function[i + 1])?),
             _ => {
                 i += 1;
                 continue;
@@ -3631,8 +4988,8 @@ pub fn do_math<
     i = 1;
     while i < function.len().saturating_sub(1) {
         function[i] = match &function[i] {
-            Multiplication => function[i - 1].mul(&function[i + 1])?,
-            Division => function[i - 1].func(&function[i + 1], div)?,
+            Multiplication => wrap(function[i - 1].mul(&function[i + 1])?),
+            Division => wrap(function[i - 1].func(&function[i + 1], div)?),
             _ => {
                 i += 1;
                 continue;
@@ -3644,9 +5001,9 @@ pub fn do_math<
     i = 1;
     while i < function.len().saturating_sub(1) {
         function[i] = match &function[i] {
-            PlusMinus => function[i - 1].pm(&function[i + 1])?,
-            Plus => function[i - 1].func(&function[i + 1], add)?,
-            Minus => function[i - 1].func(&function[i + 1], sub)?,
+            PlusMinus => wrap(function[i - 1].pm(&function[i + 1])?),
+            Plus => wrap(function[i - 1].func(&function[i + 1], add)?),
+            Minus => wrap(function[i - 1].func(&function[i + 1], sub)?),
             _ => {
                 i += 1;
                 continue;
@@ -3763,8 +5120,17 @@ pub fn do_math<
                 }
                 function[i - 1].func(&function[i + 1], about_eq)?
             }
+            ShiftRight if options.word.is_some() => {
+                wrap(function[i - 1].func(&function[i + 1], |a, b| {
+                    let n = shr(a, b);
+                    Number::from(
+                        Complex::with_val(n.number.prec(), n.number.real().clone().floor()),
+                        n.units,
+                    )
+                })?)
+            }
             ShiftRight => function[i - 1].func(&function[i + 1], shr)?,
-            ShiftLeft => function[i - 1].func(&function[i + 1], shl)?,
+            ShiftLeft => wrap(function[i - 1].func(&function[i + 1], shl)?),
             _ => {
                 i += 1;
                 continue;
@@ -3773,6 +5139,22 @@ pub fn do_math<
         function.remove(i + 1);
         function.remove(i - 1);
     }
+    for op in [BitAnd, BitXor, BitOr] {
+        i = 1;
+        while i < function.len().saturating_sub(1) {
+            if function[i] != op {
+                i += 1;
+                continue;
+            }
+            function[i] = wrap(match op {
+                BitAnd => function[i - 1].func(&function[i + 1], bitand)?,
+                BitXor => function[i - 1].func(&function[i + 1], bitxor)?,
+                _ => function[i - 1].func(&function[i + 1], bitor)?,
+            });
+            function.remove(i + 1);
+            function.remove(i - 1);
+        }
+    }
     if !function.is_empty() && function[0] == Not {
         function[0] = not(&function.remove(1))?;
     }
@@ -3799,11 +5181,186 @@ pub fn do_math<
         function.remove(i - 1);
     }
     if function.len() == 1 {
-        Ok(function[0].clone())
+        Ok(wrap(function[0].clone()))
     } else {
         Err("failed to compute")
     }
 }
+fn axes<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    a: &NumStr<Integer, Float, Complex>,
+) -> Result<Vec<usize>, &'static str> {
+    let to_usize = |n: &Number<Integer, Float, Complex>| {
+        if n.number.real().is_sign_negative() {
+            Err("negative axis")
+        } else if !n.number.imag().is_zero() || !n.number.real().clone().fract().is_zero() {
+            Err("axis must be an integer")
+        } else {
+            n.number
+                .real()
+                .to_integer()
+                .and_then(|n| n.to_usize())
+                .ok_or("invalid axes")
+        }
+    };
+    match a {
+        Num(n) => Ok(vec![to_usize(n)?]),
+        Vector(v) => v.iter().map(to_usize).collect(),
+        _ => Err("invalid axes"),
+    }
+}
+fn integer<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    n: NumStr<Integer, Float, Complex>,
+) -> Result<Integer, &'static str> {
+    let n = n.num()?;
+    match n.rational() {
+        Some((p, q)) if q == 1 => Ok(p),
+        _ if n.number.imag().is_zero() && n.number.real().clone().fract().is_zero() => {
+            n.number.real().to_integer().ok_or("expected integer")
+        }
+        _ => Err("expected integer"),
+    }
+}
+fn size<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    n: NumStr<Integer, Float, Complex>,
+) -> Result<usize, &'static str> {
+    integer(n)?
+        .to_isize()
+        .and_then(|n| usize::try_from(n).ok())
+        .ok_or("size must be a non negative integer")
+}
+fn integer_num<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    n: Option<Integer>,
+    options: Options,
+) -> NumStr<Integer, Float, Complex> {
+    NumStr::new(match n {
+        Some(n) => Number::from_rational((n, Integer::from(1)), None, options.prec),
+        None => Number::from(Complex::with_val(options.prec, Constant::Nan), None),
+    })
+}
+// integer valued functions of exact integers, done on Integer so large results keep every digit
+fn exact_functions<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    a: &Number<Integer, Float, Complex>,
+    c: Option<&Number<Integer, Float, Complex>>,
+    s: &str,
+) -> Option<Number<Integer, Float, Complex>> {
+    let int =
+        |n: &Number<Integer, Float, Complex>| n.rational().filter(|(_, q)| q == &1).map(|(p, _)| p);
+    let nat = |n: &Integer| {
+        (n.cmp0() != Ordering::Less && n.significant_bits() <= 32)
+            .then(|| n.to_u32().unwrap_or_default() as u64)
+    };
+    let bits = |n: u64| (u64::BITS - n.leading_zeros()) as u64;
+    // log2(m!) from above by stirling's series
+    let lf = |m: u64| {
+        if m < 2 {
+            0.0
+        } else {
+            let m = m as f64;
+            (m * m.ln() - m + (2.0 * std::f64::consts::PI * m).ln() / 2.0 + 1.0 / (12.0 * m))
+                / std::f64::consts::LN_2
+        }
+    };
+    // upper bound on the bits of the result, past the cap the float path is used
+    let fits = |b: u64| (b <= Integer::max_bits() as u64).then_some(());
+    let fits_log = |l: f64| fits((l.max(0.0) + 0.01) as u64 + 1);
+    // and on the work of the recurrences, past it do_functions picks another route
+    let cheap = |n: u64, k: u64| (recurrence_work(s, n, k) <= RECURRENCE_WORK).then_some(());
+    let n = int(a)?;
+    let k = match c {
+        Some(c) => Some(int(c)?),
+        None => None,
+    };
+    let r = match (s, k) {
+        ("fact" | "factorial", None) => {
+            let m = nat(&n)?;
+            fits_log(lf(m))?;
+            range_product::<Integer, Float, Complex>(1, m, 1)
+        }
+        ("doublefact" | "doublefactorial", None) => {
+            let m = nat(&n)?;
+            fits_log(lf(m + 1) / 2.0)?;
+            range_product::<Integer, Float, Complex>(2 - m % 2, m, 2)
+        }
+        ("C" | "bi" | "binomial", Some(k)) => {
+            let (m, k) = (nat(&n)?, nat(&k)?);
+            if k <= m {
+                fits_log(lf(m) - lf(k) - lf(m - k))?;
+            }
+            n.binomial(k as u32)
+        }
+        ("P", Some(k)) => {
+            let (m, k) = (nat(&n)?, nat(&k)?);
+            if k > m {
+                Integer::new()
+            } else {
+                fits_log(lf(m) - lf(m - k))?;
+                range_product::<Integer, Float, Complex>(m - k + 1, m, 1)
+            }
+        }
+        ("fib" | "fibonacci" | "lucas", None) => {
+            fits(nat(&n.clone().abs())? * 7 / 10 + 4)?;
+            if s == "lucas" {
+                lucas::<Integer, Float, Complex>(&n)
+            } else {
+                fibonacci::<Integer, Float, Complex>(&n).0
+            }
+        }
+        ("catalan", None) => {
+            let m = nat(&n)?;
+            fits(2 * m + bits(m) + 4)?;
+            cheap(m, 0)?;
+            catalan::<Integer, Float, Complex>(m as u32)
+        }
+        ("bell", None) => {
+            let m = nat(&n)?;
+            fits((m + 1) * bits(m + 1))?;
+            cheap(m, 0)?;
+            bell::<Integer, Float, Complex>(m as usize)
+        }
+        ("partitions", None) => {
+            let m = nat(&n)?;
+            fits(4 * (m as f64).sqrt() as u64 + 4)?;
+            cheap(m, 0)?;
+            partitions::<Integer, Float, Complex>(m as usize)
+        }
+        ("stirling1" | "stirling2", Some(k)) => {
+            let (m, k) = (nat(&n)?, nat(&k)?);
+            fits(m * bits(m) + 1)?;
+            cheap(m, k)?;
+            if s == "stirling1" {
+                stirling1::<Integer, Float, Complex>(m as usize, k as usize)
+            } else {
+                stirling2::<Integer, Float, Complex>(m as usize, k as usize)
+            }
+        }
+        _ => return None,
+    };
+    Some(Number::from_rational(
+        (r, Integer::from(1)),
+        None,
+        a.number.prec(),
+    ))
+}
 fn do_functions<
     Integer: crate::types::Integer<Float, Complex>,
     Float: crate::types::Float<Integer, Complex>,
@@ -3942,10 +5499,41 @@ fn do_functions<
                 }
                 return Ok(Matrix(mat));
             }
+            (Tensor(t, a), Tensor(u, b)) if t == u => {
+                function.remove(k + 1);
+                let mut vec = Vec::new();
+                for (a, b) in a.into_iter().zip(b) {
+                    vec.push(functions(a, Some(b), to_deg.clone(), s, options)?)
+                }
+                return Ok(Tensor(t, vec));
+            }
+            (Num(a), Tensor(t, b)) => {
+                function.remove(k + 1);
+                let mut vec = Vec::new();
+                for i in b {
+                    vec.push(functions(*a.clone(), Some(i), to_deg.clone(), s, options)?)
+                }
+                return Ok(Tensor(t, vec));
+            }
+            (Tensor(t, a), Num(b)) => {
+                function.remove(k + 1);
+                let mut vec = Vec::new();
+                for i in a {
+                    vec.push(functions(i, Some(*b.clone()), to_deg.clone(), s, options)?)
+                }
+                return Ok(Tensor(t, vec));
+            }
             _ => {}
         }
     }
     match a {
+        Tensor(t, a) => {
+            let mut vec = Vec::new();
+            for i in a {
+                vec.push(functions(i, None, to_deg.clone(), s, options)?)
+            }
+            Ok(Tensor(t, vec))
+        }
         Matrix(a) => {
             let mut mat = Vec::new();
             for i in a {
@@ -3988,6 +5576,9 @@ fn functions<
     if a.number.imag().is_zero() && !a.number.imag().is_sign_positive() {
         a.number = Complex::with_val(a.number.prec(), a.number.real())
     }
+    if let Some(n) = exact_functions(&a, c.as_ref(), s) {
+        return Ok(n);
+    }
     let n = if matches!(
         s,
         "root"
@@ -4394,6 +5985,160 @@ fn functions<
                         return Err("no args");
                     }
                 }
+                "fib" | "fibonacci" | "lucas" => {
+                    // F(n) has about 0.7 |n| bits, past the integer cap binet's formula is used
+                    if a.imag().is_zero()
+                        && a.real().clone().fract().is_zero()
+                        && a.real().clone().abs() * 7 / 10 + 4 <= Integer::max_bits()
+                    {
+                        let n = a.real().to_integer().unwrap_or_default();
+                        Complex::with_val(
+                            options.prec,
+                            if s == "lucas" {
+                                lucas(&n)
+                            } else {
+                                fibonacci(&n).0
+                            },
+                        )
+                    } else {
+                        let five = Float::with_val(options.prec, 5).sqrt();
+                        let phi = Complex::with_val(options.prec, (five.clone() + 1) / 2);
+                        if a.imag().is_zero() {
+                            // real powers so a huge n overflows to inf instead of a nan part
+                            let n = a.real().clone();
+                            let phi = phi.real().clone();
+                            let cos = if n.clone().fract().is_zero() {
+                                // exact sign, the cosine of a huge multiple of pi is noise
+                                let half: Float = n.clone() / 2;
+                                Float::with_val(
+                                    options.prec,
+                                    if half.fract().is_zero() { 1 } else { -1 },
+                                )
+                            } else {
+                                (n.clone() * Float::with_val(options.prec, Constant::Pi)).cos()
+                            };
+                            let back: Float = cos * phi.clone().pow(-n.clone());
+                            let f: Float = if s == "lucas" {
+                                phi.pow(n) + back
+                            } else {
+                                (phi.pow(n) - back) / five
+                            };
+                            f.into()
+                        } else {
+                            let cos: Complex =
+                                (a.clone() * Float::with_val(options.prec, Constant::Pi)).cos();
+                            let back = cos * phi.clone().pow(-a.clone());
+                            if s == "lucas" {
+                                phi.pow(a) + back
+                            } else {
+                                (phi.pow(a) - back) / five
+                            }
+                        }
+                    }
+                }
+                "harmonic" | "bernoulli" | "bell" | "catalan" | "partitions" => {
+                    // the exact recurrences where their work allows, otherwise a closed form
+                    let int = a.imag().is_zero()
+                        && a.real().clone().fract().is_zero()
+                        && !a.real().is_sign_negative();
+                    let exact = int
+                        .then(|| a.real().to_integer().unwrap_or_default().to_usize())
+                        .flatten()
+                        .filter(|n| recurrence_work(s, *n as u64, 0) <= RECURRENCE_WORK);
+                    match (s, exact) {
+                        ("harmonic", Some(n)) => {
+                            let (p, q) = harmonic::<Integer, Float, Complex>(n);
+                            Complex::with_val(options.prec, p) / Complex::with_val(options.prec, q)
+                        }
+                        ("harmonic", None) if int => {
+                            let one = Float::with_val(options.prec, 1);
+                            let n: Float = a.real().clone() + 1;
+                            (n.digamma() - one.digamma()).into()
+                        }
+                        ("harmonic", None) => {
+                            digamma(a + 1, 0) - digamma(Complex::with_val(options.prec, 1), 0)
+                        }
+                        ("bernoulli", Some(n)) => {
+                            let (p, q) = bernoulli::<Integer, Float, Complex>(n);
+                            Complex::with_val(options.prec, p) / Complex::with_val(options.prec, q)
+                        }
+                        ("bell", Some(n)) => {
+                            Complex::with_val(options.prec, bell::<Integer, Float, Complex>(n))
+                        }
+                        ("catalan", Some(n)) => Complex::with_val(
+                            options.prec,
+                            catalan::<Integer, Float, Complex>(n as u32),
+                        ),
+                        ("partitions", Some(n)) => Complex::with_val(
+                            options.prec,
+                            partitions::<Integer, Float, Complex>(n),
+                        ),
+                        (_, None) if !int => Complex::with_val(options.prec, Constant::Nan),
+                        // B(n) = (-1)^(n/2+1) 2 n! zeta(n) / (2 pi)^n for even n, zero for odd
+                        ("bernoulli", None) => {
+                            let n = a.real().clone();
+                            let half: Float = n.clone() / 2;
+                            if half.clone().fract().is_zero() {
+                                let fact: Float = n.clone() + 1;
+                                let tau: Float = Float::with_val(options.prec, Constant::Pi) * 2;
+                                let b: Float = fact.gamma() * n.clone().zeta() * 2;
+                                let b: Float = b / tau.pow(n);
+                                let quarter: Float = half / 2;
+                                if quarter.fract().is_zero() { -b } else { b }.into()
+                            } else {
+                                Complex::new(options.prec)
+                            }
+                        }
+                        ("catalan", None) => {
+                            // C(n) = (2n)! / (n! (n+1)!)
+                            let n = a.real().clone();
+                            let top: Float = n.clone() * 2 + 1;
+                            let (lo, hi): (Float, Float) = (n.clone() + 1, n + 2);
+                            let c: Float = top.gamma() / (lo.gamma() * hi.gamma());
+                            c.into()
+                        }
+                        ("partitions", None) => {
+                            partitions_asymptotic::<Integer, Float, Complex>(a.real().clone())
+                                .into()
+                        }
+                        _ => return Err("bell input too large"),
+                    }
+                }
+                "stirling1" | "stirling2" => {
+                    if let Some(b) = d {
+                        let int = |z: &Complex| {
+                            if z.imag().is_zero()
+                                && z.real().clone().fract().is_zero()
+                                && !z.real().is_sign_negative()
+                            {
+                                z.real()
+                                    .to_integer()
+                                    .unwrap_or_default()
+                                    .to_usize()
+                                    .map(|n| n as u64)
+                            } else {
+                                None
+                            }
+                        };
+                        match (int(&a), int(&b)) {
+                            (Some(n), Some(k)) if k > n => Complex::new(options.prec),
+                            (Some(n), Some(k)) if recurrence_work(s, n, k) <= RECURRENCE_WORK => {
+                                Complex::with_val(
+                                    options.prec,
+                                    if s == "stirling1" {
+                                        stirling1::<Integer, Float, Complex>(n as usize, k as usize)
+                                    } else {
+                                        stirling2::<Integer, Float, Complex>(n as usize, k as usize)
+                                    },
+                                )
+                            }
+                            (Some(_), Some(_)) => return Err("stirling input too large"),
+                            _ => Complex::with_val(options.prec, Constant::Nan),
+                        }
+                    } else {
+                        return Err("not enough args");
+                    }
+                }
                 "An" => {
                     if let Some(b) = d {
                         euleriannumbers(
@@ -4638,6 +6383,11 @@ fn functions<
                     }
                 }
                 #[cfg(feature = "fastrand")]
+                "seed" => {
+                    fastrand::seed(to_seed(a.real())?);
+                    a
+                }
+                #[cfg(feature = "fastrand")]
                 "rand_gamma" => {
                     if let Some(b) = d {
                         rand_gamma(a.real().clone(), b.real().clone()).into()
@@ -4656,6 +6406,15 @@ fn functions<
                     }
                 }
                 #[cfg(feature = "fastrand")]
+                "rand_t" | "rand_chi2" | "rand_f" | "rand_exponential" | "rand_weibull"
+                | "rand_cauchy" | "rand_laplace" => {
+                    let mut args = vec![a.real().clone()];
+                    if let Some(b) = d {
+                        args.push(b.real().clone())
+                    }
+                    rand_dist(&s[5..], &args)?.into()
+                }
+                #[cfg(feature = "fastrand")]
                 "rand_lognorm" => {
                     if let Some(b) = d {
                         rand_norm(a, b).exp()
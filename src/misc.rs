@@ -3,16 +3,16 @@ use crate::{
     complex::{
         NumStr,
         NumStr::{
-            And, Comma, Converse, Conversion, Division, Equal, Exponent, Func, Greater,
-            GreaterEqual, Implies, InternalMultiplication, LeftBracket, LeftCurlyBracket, Lesser,
-            LesserEqual, Matrix, Minus, Modulo, Multiplication, Nand, NearEqual, Nor, Not,
-            NotEqual, Num, Or, Plus, PlusMinus, Range, RightBracket, RightCurlyBracket, Root,
-            ShiftLeft, ShiftRight, Tetration, Vector, Xor,
+            And, BitAnd, BitOr, BitXor, Comma, Converse, Conversion, Division, Equal, Exponent,
+            Func, Greater, GreaterEqual, Implies, InternalMultiplication, LeftBracket,
+            LeftCurlyBracket, Lesser, LesserEqual, Matrix, Minus, Modulo, Multiplication, Nand,
+            NearEqual, Nor, Not, NotEqual, Num, Or, Plus, PlusMinus, Range, RightBracket,
+            RightCurlyBracket, Root, ShiftLeft, ShiftRight, Tensor, Tetration, Vector, Xor,
         },
     },
     functions::functions,
     math::do_math,
-    print::{custom_units, get_output},
+    print::{custom_units, get_output, tensor_string},
     units::{Auto, Colors, Options, Variable},
 };
 #[cfg(feature = "bin-deps")]
@@ -22,10 +22,13 @@ use crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, rea
 use libc::{STDOUT_FILENO, TIOCGWINSZ, ioctl, winsize};
 use std::path::PathBuf;
 use std::process::Command;
+use std::sync::atomic::AtomicBool;
 use std::{fs::File, io::Write};
 #[cfg(not(unix))]
 #[cfg(feature = "bin-deps")]
 use term_size::dimensions;
+// set by a frontend (e.g. on ctrl-c) to stop long running computations like factoring
+pub static INTERRUPT: AtomicBool = AtomicBool::new(false);
 #[cfg(feature = "bin-deps")]
 #[cfg(unix)]
 pub fn get_terminal_dimensions() -> (usize, usize) {
@@ -918,6 +921,32 @@ pub fn parsed_to_string<
                 str.pop();
                 format!("{{{str}}}")
             }
+            Tensor(s, n) => tensor_string(
+                &s,
+                &n.into_iter()
+                    .map(|i| {
+                        let i = custom_units(i, *options, colors);
+                        let num = get_output(*options, colors, &i);
+                        format!(
+                            "{}{}{}{}",
+                            num.0,
+                            num.1,
+                            num.2.unwrap_or_default(),
+                            if options.color == Auto::True {
+                                "\x1b[0m"
+                            } else {
+                                ""
+                            }
+                        )
+                    })
+                    .collect::<Vec<String>>(),
+                Options {
+                    color: Auto::False,
+                    ..*options
+                },
+                colors,
+                0,
+            ),
             Func(n) if n.starts_with('@') && n.contains('(') => {
                 n.split('(').next().unwrap().replace('@', "")
             }
@@ -956,6 +985,9 @@ pub fn parsed_to_string<
             Not => "¬".to_string(),
             Nor => " Nor ".to_string(),
             Converse => " Converse ".to_string(),
+            BitAnd => " bitand ".to_string(),
+            BitOr => " bitor ".to_string(),
+            BitXor => " bitxor ".to_string(),
         })
     }
     to_output(
@@ -965,6 +997,24 @@ pub fn parsed_to_string<
         colors,
     )
 }
+pub fn split_top(input: &str, split: char) -> Vec<&str> {
+    let mut out = Vec::new();
+    let mut count = 0;
+    let mut start = 0;
+    for (i, c) in input.char_indices() {
+        match c {
+            '(' | '{' | '[' => count += 1,
+            ')' | '}' | ']' => count -= 1,
+            _ if c == split && count == 0 => {
+                out.push(&input[start..i]);
+                start = i + c.len_utf8()
+            }
+            _ => {}
+        }
+    }
+    out.push(&input[start..]);
+    out
+}
 pub fn insert_last(input: &[char], last: &str) -> String {
     let mut output = String::new();
     let mut word = String::new();
@@ -2,14 +2,18 @@ use crate::complex::NumStr;
 use crate::help::man;
 use crate::types::Constant;
 use crate::{
-    complex::NumStr::{Matrix, Num, Vector},
+    complex::{
+        NumStr::{Matrix, Num, Vector},
+        to_seed,
+    },
     help::{help, help_for},
     load_vars::get_vars,
     math::do_math,
-    misc::{insert_last, parsed_to_string, to_output},
+    misc::{insert_last, parsed_to_string, split_top, to_output},
     parse::input_var,
     print::{custom_units, get_output},
     units::{
+        AltBase,
         AngleType::{Degrees, Gradians, Radians},
         Auto, Colors, GraphType, HowGraphing,
         Notation::{LargeEngineering, Normal, Scientific, SmallEngineering},
@@ -238,6 +242,30 @@ pub fn set_commands<
                 _ => return Err("bad graph type"),
             }
         }
+        "word" => {
+            options.word = match r {
+                "off" | "null" | "none" | "false" | "0" => None,
+                _ => {
+                    let (signed, bits) = if let Some(n) = r.strip_prefix('u') {
+                        (false, n)
+                    } else {
+                        (true, r.strip_prefix('i').unwrap_or(r))
+                    };
+                    match bits.parse::<u32>() {
+                        Ok(n @ (8 | 16 | 32 | 64 | 128)) => Some((n, signed)),
+                        _ => return Err("word must be 8, 16, 32, 64 or 128 bits"),
+                    }
+                }
+            }
+        }
+        "altbase" => {
+            options.alt_base = match r {
+                "off" | "null" | "none" | "false" => None,
+                "negabinary" | "nb" | "-2" => Some(AltBase::Negabinary),
+                "balanced" | "balancedternary" | "bt" => Some(AltBase::BalancedTernary),
+                _ => return Err("altbase must be negabinary, balanced or off"),
+            }
+        }
         "saveto" => {
             if r == "null" {
                 colors.graphtofile.clear()
@@ -364,12 +392,12 @@ pub fn set_commands<
                 }
             }
         }
-        "graphcli" | "slowcheck" | "interactive" | "prompt" | "surface" | "rt" | "siunits"
-        | "keepzeros" | "polar" | "frac" | "fractions" | "fractionsv" | "fractionsm" | "multi"
-        | "tabbed" | "comma" | "units" | "scalegraph" | "debug" | "vars" | "onaxis" | "base"
-        | "ticks" | "decimal" | "deci" | "decimals" | "graphprec" | "graphprecision" | "prec"
-        | "windowsize" | "precision" | "range" | "xr" | "yr" | "zr" | "vrange" | "vxr" | "vyr"
-        | "vzr" | "2d" | "3d" | "progress" => {
+        "graphcli" | "slowcheck" | "seed" | "interactive" | "prompt" | "surface" | "rt"
+        | "siunits" | "keepzeros" | "polar" | "frac" | "fractions" | "fractionsv"
+        | "fractionsm" | "multi" | "tabbed" | "comma" | "units" | "scalegraph" | "debug"
+        | "vars" | "onaxis" | "base" | "ticks" | "decimal" | "deci" | "decimals" | "intdigits"
+        | "graphprec" | "graphprecision" | "prec" | "windowsize" | "precision" | "range" | "xr"
+        | "yr" | "zr" | "vrange" | "vxr" | "vyr" | "vzr" | "2d" | "3d" | "progress" => {
             let mut args: Vec<Float> = Vec::new();
             {
                 let mut bracket = 0;
@@ -498,6 +526,12 @@ pub fn set_commands<
                         .to_u128()
                         .unwrap_or_default()
                 }
+                "seed" => {
+                    let n = to_seed(&args[0])?;
+                    #[cfg(feature = "fastrand")]
+                    fastrand::seed(n);
+                    options.seed = Some(n)
+                }
                 "decimal" | "deci" | "decimals" => {
                     options.decimal_places = match args[0]
                         .to_integer()
@@ -511,6 +545,18 @@ pub fn set_commands<
                         _ => return Err("Invalid decimal"),
                     };
                 }
+                "intdigits" => {
+                    options.int_digits = match args[0]
+                        .to_integer()
+                        .unwrap_or_default()
+                        .to_isize()
+                        .unwrap_or_default()
+                    {
+                        -1 => usize::MAX,
+                        n if n >= 0 => n as usize,
+                        _ => return Err("Invalid intdigits"),
+                    };
+                }
                 "graphprec" | "graphprecision" => match args[0]
                     .to_integer()
                     .unwrap_or_default()
@@ -581,7 +627,7 @@ pub fn set_commands<
                                     let mut unparsed = var.unparsed.clone();
                                     if unparsed.contains(':') {
                                         let un = unparsed;
-                                        let mut split = un.split(':').collect::<Vec<&str>>();
+                                        let mut split = split_top(&un, ':');
                                         unparsed = split.pop().unwrap().to_string();
                                         for i in split {
                                             if i.contains('=') {
@@ -1217,6 +1263,20 @@ pub fn equal_to<
             )
         }
         "slowcheck" => format!("{}", options.slowcheck),
+        "seed" => options
+            .seed
+            .map(|n| n.to_string())
+            .unwrap_or("none".to_string()),
+        "word" => options
+            .word
+            .map(|(n, signed)| format!("{}{n}", if signed { "i" } else { "u" }))
+            .unwrap_or("off".to_string()),
+        "altbase" => match options.alt_base {
+            Some(AltBase::Negabinary) => "negabinary",
+            Some(AltBase::BalancedTernary) => "balanced",
+            None => "off",
+        }
+        .to_string(),
         "label" => format!("{},{},{}", colors.label.0, colors.label.1, colors.label.2),
         "color" | "colour" => (match options.color {
             Auto::Auto => "auto",
@@ -1256,6 +1316,7 @@ pub fn equal_to<
         ),
         "onaxis" => format!("{}", options.onaxis),
         "decimal" | "deci" | "decimals" => format!("{}", options.decimal_places),
+        "intdigits" => format!("{}", options.int_digits),
         "prec" | "precision" => format!("{}", options.prec),
         "windowsize" => format!("{},{}", options.window_size.0, options.window_size.1),
         "graphprec" | "graphprecision" => format!("{}", options.graph_prec),
@@ -3,16 +3,17 @@ use crate::{
     complex::{
         NumStr,
         NumStr::{
-            And, Comma, Converse, Conversion, Division, Equal, Exponent, Func, Greater,
-            GreaterEqual, Implies, InternalMultiplication, LeftBracket, LeftCurlyBracket, Lesser,
-            LesserEqual, Matrix, Minus, Modulo, Multiplication, Nand, NearEqual, Nor, Not,
-            NotEqual, Num, Or, Plus, PlusMinus, Range, RightBracket, RightCurlyBracket, Root,
-            ShiftLeft, ShiftRight, Tetration, Vector, Xor,
+            And, BitAnd, BitOr, BitXor, Comma, Converse, Conversion, Division, Equal, Exponent,
+            Func, Greater, GreaterEqual, Implies, InternalMultiplication, LeftBracket,
+            LeftCurlyBracket, Lesser, LesserEqual, Matrix, Minus, Modulo, Multiplication, Nand,
+            NearEqual, Nor, Not, NotEqual, Num, Or, Plus, PlusMinus, Range, RightBracket,
+            RightCurlyBracket, Root, ShiftLeft, ShiftRight, Tetration, Vector, Xor,
         },
         pow_nth,
     },
     functions::functions,
     math::do_math,
+    misc::split_top,
     units::{
         GraphType, HowGraphing, Notation::SmallEngineering, Number, Options, Variable, is_unit,
         prefixes, to_unit,
@@ -52,7 +53,7 @@ pub fn input_var<
     let prec = options.prec;
     let mut funcvars = Vec::new();
     if input.starts_with("history")
-        || input.starts_with("his")
+        || (input.starts_with("his") && !input.starts_with("hist(") && !input.starts_with("hist_"))
         || input.starts_with("onaxis")
         || input.starts_with("exit")
         || input.starts_with("quit")
@@ -77,7 +78,7 @@ pub fn input_var<
     let mut iso = Vec::new();
     let mut isop = Vec::new();
     let mut slope = Vec::new();
-    let mut chars = input
+    let mut chars = index_syntax(&set_syntax(&interval_syntax(input)?))
         .replace('[', "(car{")
         .replace(']', "})")
         .chars()
@@ -259,13 +260,26 @@ pub fn input_var<
                 num.insert(0, '-');
                 i += 1;
             }
-            output.push(NumStr::new(Number::from(
-                match Complex::parse_radix(prec, &num, options.base.0) {
-                    Some(n) => n,
-                    None => return Err("probably radix error"),
+            let n = match Complex::parse_radix(prec, &num, options.base.0) {
+                Some(n) => n,
+                None => return Err("probably radix error"),
+            };
+            // integer and terminating literals like 0.1 are exact fractions over a power of the base
+            let digits = Some(match num.split_once('.') {
+                Some((whole, frac)) => (whole.to_owned() + frac, frac.len() as u32),
+                None => (num.clone(), 0),
+            })
+            .filter(|(_, k)| k * (u32::BITS - (options.base.0 as u32).leading_zeros()) <= n.prec());
+            output.push(NumStr::new(
+                match digits
+                    .and_then(|(p, k)| Some((Integer::from_str_radix(&p, options.base.0)?, k)))
+                {
+                    Some((p, k)) => {
+                        Number::from_rational((p, Integer::from(options.base.0).pow(k)), None, prec)
+                    }
+                    None => Number::from(n, None),
                 },
-                None,
-            )));
+            ));
             if scientific {
                 output.push(RightBracket);
                 scientific = false;
@@ -433,6 +447,12 @@ pub fn input_var<
                     i += 1;
                     output.push(And);
                 }
+                '&' if i != 0
+                    && i + 1 < chars.len()
+                    && !matches!(chars[i + 1], ')' | '}' | ']') =>
+                {
+                    output.push(BitAnd);
+                }
                 '¬' => {
                     output.push(Not);
                 }
@@ -1143,7 +1163,8 @@ pub fn input_var<
                 depthcheck = !depthcheck;
             } else if c.is_alphabetic()
                 || matches!(*c, '°' | '\'' | '`' | '_' | '∫' | '$' | '¢')
-                || (c == &'2' && word == "atan")
+                || (c == &'2' && matches!(word.as_str(), "atan" | "chi" | "rand_chi"))
+                || (matches!(c, '1' | '2') && word == "stirling")
             {
                 word.push(*c);
             } else if !depthcheck {
@@ -1177,23 +1198,31 @@ pub fn input_var<
         if var_overrule {
             if (word.ends_with('x')
                 && word != "max"
+                && word != "index"
                 && !word.ends_with("lx")
                 && !word.ends_with("lux"))
                 || (word.ends_with('y')
                     && word != "any"
                     && word != "unity"
                     && word != "identity"
+                    && word != "rand_cauchy"
                     && !word.ends_with("day")
                     && !word.ends_with("gravity")
                     && !word.ends_with("ly")
                     && !word.ends_with("henry")
                     && !word.ends_with("Gy")
                     && !word.ends_with("gray"))
-                || (word.ends_with('w') && word != "pw" && word != "lambertw" && word != "skew")
+                || (word.ends_with('w')
+                    && word != "pw"
+                    && word != "lambertw"
+                    && word != "skew"
+                    && word != "modpow")
                 || (word.ends_with('z')
                     && !word.ends_with("Hz")
                     && !word.ends_with("hertz")
-                    && !word.ends_with("oz"))
+                    && !word.ends_with("oz")
+                    && word != "clz"
+                    && word != "ctz")
             {
                 countv -= 1;
                 word.pop();
@@ -1227,6 +1256,7 @@ pub fn input_var<
                 | "taylor"
                 | "sum"
                 | "iter"
+                | "filter"
                 | "extrema"
                 | "summation"
                 | "prod"
@@ -1261,8 +1291,8 @@ pub fn input_var<
                 }
             }
             if match word.as_str() {
-                "integrate" | "vec" | "arclength" | "mat" | "prod" | "production" | "iter"
-                | "length" | "∫" | "area" | "sum" | "Σ" | "summation" | "Π" | "taylor" => {
+                "integrate" | "vec" | "filter" | "arclength" | "mat" | "prod" | "production"
+                | "iter" | "length" | "∫" | "area" | "sum" | "Σ" | "summation" | "Π" | "taylor" => {
                     place >= 3
                 }
                 "sarea" | "surfacearea" => place >= 6,
@@ -1443,6 +1473,10 @@ pub fn input_var<
                     chars[i + countv],
                     'x' | 'y' | 'w' | 'z' | '(' | '|' | '{' | '0'..='9' | '⁻' | '*' | '\'' | '`'
                 ) || (chars[i + countv] == '^' && chars[i] != 'C' && countv != 1)))
+                || (functions.contains(word.as_str())
+                    && i + countv < chars.len()
+                    && chars[i + countv] == ','
+                    && statistic_arg(&output))
                 || matches!(
                     word.to_ascii_lowercase().as_str(),
                     "rnd" | "rand" | "epoch" | "inf" | "true" | "false" | "nan"
@@ -1513,7 +1547,17 @@ pub fn input_var<
             }
         } else if matches!(
             word.to_ascii_lowercase().as_str(),
-            "and" | "or" | "not" | "xor" | "nand" | "implies" | "nor" | "converse"
+            "and"
+                | "or"
+                | "not"
+                | "xor"
+                | "nand"
+                | "implies"
+                | "nor"
+                | "converse"
+                | "bitand"
+                | "bitor"
+                | "bitxor"
         ) {
             if word.eq_ignore_ascii_case("not") {
                 place_multiplier(&mut output, sumrec, &sumvar);
@@ -1538,6 +1582,9 @@ pub fn input_var<
                 "implies" => output.push(Implies),
                 "nor" => output.push(Nor),
                 "converse" => output.push(Converse),
+                "bitand" => output.push(BitAnd),
+                "bitor" => output.push(BitOr),
+                "bitxor" => output.push(BitXor),
                 _ => output.push(Func(word)),
             }
         } else if options.units
@@ -3154,7 +3201,10 @@ pub fn input_var<
             | Nand
             | Implies
             | Nor
-            | Converse => {
+            | Converse
+            | BitAnd
+            | BitOr
+            | BitXor => {
                 output.pop();
             }
             Func(s) if functions.contains(s.as_str()) => {
@@ -3195,6 +3245,32 @@ fn place_multiplier<
         _ => {}
     }
 }
+fn statistic_arg<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    output: &[NumStr<Integer, Float, Complex>],
+) -> bool {
+    //true when output ends in "bootstrap(data," so the next word names the statistic
+    if !matches!(output.last(), Some(Comma)) {
+        return false;
+    }
+    let mut depth = 0;
+    for (i, o) in output[..output.len() - 1].iter().enumerate().rev() {
+        match o {
+            RightBracket | RightCurlyBracket => depth += 1,
+            LeftCurlyBracket => depth -= 1,
+            LeftBracket if depth == 0 => {
+                return i > 0 && matches!(&output[i - 1], Func(s) if s == "bootstrap");
+            }
+            LeftBracket => depth -= 1,
+            Comma if depth == 0 => return false,
+            _ => {}
+        }
+    }
+    false
+}
 fn can_abs<
     Integer: crate::types::Integer<Float, Complex>,
     Float: crate::types::Float<Integer, Complex>,
@@ -3210,6 +3286,241 @@ fn can_abs<
         true
     }
 }
+fn word_start(chars: &[char], end: usize) -> usize {
+    let mut start = end;
+    while start > 0
+        && (chars[start - 1].is_alphanumeric() || matches!(chars[start - 1], '_' | '\''))
+    {
+        start -= 1
+    }
+    while start < end && chars[start].is_ascii_digit() {
+        start += 1
+    }
+    start
+}
+fn index_target(chars: &[char]) -> Option<usize> {
+    match chars.last()? {
+        ')' | '}' => {
+            let mut count = 0;
+            for (i, c) in chars.iter().enumerate().rev() {
+                match c {
+                    ')' | '}' => count += 1,
+                    '(' | '{' => {
+                        count -= 1;
+                        if count == 0 {
+                            return Some(word_start(chars, i));
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            None
+        }
+        c if c.is_alphanumeric() || matches!(c, '_' | '\'') => {
+            let start = word_start(chars, chars.len());
+            if start == chars.len()
+                || functions().contains(chars[start..].iter().collect::<String>().as_str())
+            {
+                None
+            } else {
+                Some(start)
+            }
+        }
+        _ => None,
+    }
+}
+pub fn index_args(input: &str) -> String {
+    split_top(input, ',')
+        .iter()
+        .map(|arg| {
+            let parts = split_top(arg, ':');
+            if arg.trim().is_empty() || parts.len() > 1 {
+                format!(
+                    "1,{{{}}}",
+                    (0..3)
+                        .map(|i| match parts.get(i) {
+                            Some(p) if !p.is_empty() => format!("({p})"),
+                            _ => "nan".to_string(),
+                        })
+                        .collect::<Vec<String>>()
+                        .join(",")
+                )
+            } else if arg.contains(['<', '>', '=', '≈', '≠', '≤', '≥', '¬'])
+                || arg.contains("&&")
+                || arg.contains("||")
+            {
+                format!("2,({arg})")
+            } else {
+                format!("0,({arg})")
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(",")
+}
+// rewrites interval(a,b,"[)") into interval_co(a,b), likewise "[]", "(]" and "()" into
+// interval_cc, interval_oc and interval_oo, since the parser has no strings
+pub fn interval_syntax(input: &str) -> Result<String, &'static str> {
+    const NAME: &str = "interval(";
+    if !input.contains(NAME) {
+        return Ok(input.to_string());
+    }
+    let chars = input.chars().collect::<Vec<char>>();
+    let mut out = String::with_capacity(input.len());
+    let mut i = 0;
+    while i < chars.len() {
+        if !chars[i..]
+            .iter()
+            .take(NAME.len())
+            .eq(NAME.chars().collect::<Vec<char>>().iter())
+            || (i != 0 && (chars[i - 1].is_alphanumeric() || chars[i - 1] == '_'))
+        {
+            out.push(chars[i]);
+            i += 1;
+            continue;
+        }
+        let start = i + NAME.len();
+        let mut count = 0;
+        let mut quote = false;
+        let mut commas = Vec::new();
+        let mut end = None;
+        for (k, c) in chars.iter().enumerate().skip(start) {
+            if quote {
+                quote = *c != '"';
+                continue;
+            }
+            match c {
+                '"' => quote = true,
+                '(' | '{' | '[' => count += 1,
+                ')' if count == 0 => {
+                    end = Some(k);
+                    break;
+                }
+                ')' | '}' | ']' => count -= 1,
+                ',' if count == 0 => commas.push(k),
+                _ => {}
+            }
+        }
+        let Some(end) = end else {
+            out.extend(&chars[i..]);
+            break;
+        };
+        let last = chars[commas.last().map(|c| c + 1).unwrap_or(start)..end]
+            .iter()
+            .collect::<String>();
+        if last.trim().starts_with('"') {
+            let name = match last.trim() {
+                "\"[]\"" => "interval_cc",
+                "\"[)\"" => "interval_co",
+                "\"(]\"" => "interval_oc",
+                "\"()\"" => "interval_oo",
+                _ => return Err("interval spec must be \"[]\", \"[)\", \"(]\" or \"()\""),
+            };
+            let Some(last_comma) = commas.last() else {
+                return Err("not enough args");
+            };
+            out.push_str(&format!(
+                "{name}({})",
+                interval_syntax(&chars[start..*last_comma].iter().collect::<String>())?
+            ));
+        } else {
+            out.push_str(&format!(
+                "{NAME}{})",
+                interval_syntax(&chars[start..end].iter().collect::<String>())?
+            ));
+        }
+        i = end + 1
+    }
+    Ok(out)
+}
+// rewrites {x ∈ a..b | p} into filter(x,p,a,b)
+pub fn set_syntax(input: &str) -> String {
+    if !input.contains('∈') {
+        return input.to_string();
+    }
+    let chars = input.chars().collect::<Vec<char>>();
+    let mut out = String::with_capacity(input.len());
+    let mut i = 0;
+    'main: while i < chars.len() {
+        if chars[i] == '{' {
+            let mut j = i + 1;
+            let skip = |j: &mut usize| {
+                while *j < chars.len() && chars[*j].is_whitespace() {
+                    *j += 1
+                }
+            };
+            skip(&mut j);
+            let var_start = j;
+            while j < chars.len() && (chars[j].is_alphabetic() || chars[j] == '_') {
+                j += 1
+            }
+            let var = chars[var_start..j].iter().collect::<String>();
+            skip(&mut j);
+            if !var.is_empty() && j < chars.len() && chars[j] == '∈' {
+                let mut bar = None;
+                let mut count = 0;
+                for (k, c) in chars.iter().enumerate().skip(j + 1) {
+                    match c {
+                        '(' | '{' | '[' => count += 1,
+                        ')' | ']' => count -= 1,
+                        '}' if count == 0 => {
+                            if let Some(bar) = bar {
+                                let range = chars[j + 1..bar].iter().collect::<String>();
+                                if let Some((a, b)) = range.split_once("..") {
+                                    out.push_str(&format!(
+                                        "filter({var},{},{},{})",
+                                        set_syntax(&chars[bar + 1..k].iter().collect::<String>())
+                                            .trim(),
+                                        a.trim(),
+                                        b.trim()
+                                    ));
+                                    i = k + 1;
+                                    continue 'main;
+                                }
+                            }
+                            break;
+                        }
+                        '}' => count -= 1,
+                        '|' if count == 0 && bar.is_none() => bar = Some(k),
+                        _ => {}
+                    }
+                }
+            }
+        }
+        out.push(chars[i]);
+        i += 1
+    }
+    out
+}
+pub fn index_syntax(input: &str) -> String {
+    let chars = input.chars().collect::<Vec<char>>();
+    let mut out: Vec<char> = Vec::with_capacity(chars.len());
+    let mut i = 0;
+    while i < chars.len() {
+        if chars[i] == '[' {
+            if let Some(start) = index_target(&out) {
+                let mut count = 0;
+                let end = chars[i..].iter().position(|c| {
+                    match c {
+                        '[' => count += 1,
+                        ']' => count -= 1,
+                        _ => {}
+                    }
+                    count == 0
+                });
+                if let Some(end) = end {
+                    let target = out.drain(start..).collect::<String>();
+                    let inner = index_syntax(&chars[i + 1..i + end].iter().collect::<String>());
+                    out.extend(format!("index({target},{})", index_args(&inner)).chars());
+                    i += end + 1;
+                    continue;
+                }
+            }
+        }
+        out.push(chars[i]);
+        i += 1
+    }
+    out.into_iter().collect()
+}
 fn is_digit(char: char, base: i32) -> bool {
     char.is_ascii_digit() || (base > 10 && (97..=97 + (base as u8 - 11)).contains(&(char as u8)))
 }
@@ -2,22 +2,22 @@ use crate::types::Constant;
 use crate::{
     complex::{
         NumStr,
-        NumStr::{Matrix, Num, Vector},
-        to_polar,
+        NumStr::{Matrix, Num, Tensor, Vector},
+        alt_base_digits, to_polar, wrap_word,
     },
-    fraction::fraction,
+    fraction::{exact_fraction, fraction},
     help::help_for,
     load_vars::set_commands_or_vars,
     math::do_math,
     misc::{
         clear, get_terminal_dimensions, handle_err, insert_last, no_col, no_col_len,
-        parsed_to_string, prompt, to_output,
+        parsed_to_string, prompt, split_top, to_output,
     },
     options::{equal_to, list_vars, silent_commands},
     parse::input_var,
     units::{
-        AngleType, Auto, Colors, HowGraphing, Notation, Notation::Normal, Number, Options, Units,
-        Variable,
+        AltBase, AngleType, Auto, Colors, HowGraphing, Notation, Notation::Normal, Number, Options,
+        Units, Variable,
     },
 };
 use std::cmp::Ordering;
@@ -429,7 +429,7 @@ pub fn print_concurrent<
                 }
                 if inputs.contains(':') {
                     let inp = inputs;
-                    let mut split = inp.split(':').collect::<Vec<&str>>();
+                    let mut split = split_top(&inp, ':');
                     inputs = split.pop().unwrap().to_string();
                     for i in split {
                         if i.contains('=') {
@@ -743,7 +743,14 @@ pub fn print_concurrent<
             }
         };
     }
-    let num = match do_math(input.0, options, input.1) {
+    #[cfg(feature = "fastrand")]
+    let state = options.seed.map(|_| fastrand::get_seed());
+    let num = do_math(input.0, options, input.1);
+    #[cfg(feature = "fastrand")]
+    if let Some(state) = state {
+        fastrand::seed(state)
+    }
+    let num = match num {
         Ok(n) => n,
         Err(s) => {
             return if s == " " {
@@ -761,9 +768,15 @@ pub fn print_concurrent<
         Num(n) => {
             let n = custom_units(*n, options, &colors);
             let mut output = get_output(options, &colors, &n);
+            let word = word_output(options, &n.number);
             let (mut frac_a, frac_b) = if options.frac.num {
+                let exact = exact_fraction(&n, options);
                 let n = n.number;
-                let fa = fraction(n.real().clone(), options, &colors, 0);
+                let fa = if exact.is_empty() {
+                    fraction(n.real().clone(), options, &colors, 0)
+                } else {
+                    exact
+                };
                 let fb = fraction(n.imag().clone(), options, &colors, 0);
                 let sign = if !output.0.is_empty() && n.imag().is_sign_positive() {
                     "+"
@@ -842,6 +855,9 @@ pub fn print_concurrent<
                 output.0 += &st;
                 frac_a += &st;
             }
+            if let Some(w) = word {
+                output.0 += &w
+            }
             let len1 = no_col_len(&output.0, options.color == Auto::True);
             let len2 = no_col_len(&output.1, options.color == Auto::True);
             if (frac == 1 && !options.frac.num)
@@ -1013,9 +1029,14 @@ pub fn print_concurrent<
             for (k, i) in v.iter().enumerate() {
                 let i = custom_units(i.clone(), options, &colors);
                 out = get_output(options, &colors, &i);
+                let exact = exact_fraction(&i, options);
                 let i = &i.number;
                 if frac == 1 {
-                    frac_temp = fraction(i.real().clone(), options, &colors, 1);
+                    frac_temp = if exact.is_empty() {
+                        fraction(i.real().clone(), options, &colors, 1)
+                    } else {
+                        exact
+                    };
                     frac_out += if !frac_temp.is_empty() {
                         &frac_temp
                     } else {
@@ -1211,9 +1232,14 @@ pub fn print_concurrent<
                 for (k, i) in j.iter().enumerate() {
                     let i = custom_units(i.clone(), options, &colors);
                     out = get_output(options, &colors, &i);
+                    let exact = exact_fraction(&i, options);
                     let i = &i.number;
                     if frac == 1 {
-                        frac_temp = fraction(i.real().clone(), options, &colors, 2);
+                        frac_temp = if exact.is_empty() {
+                            fraction(i.real().clone(), options, &colors, 2)
+                        } else {
+                            exact
+                        };
                         frac_out += if !frac_temp.is_empty() {
                             &frac_temp
                         } else {
@@ -1357,83 +1383,37 @@ pub fn print_concurrent<
                 }
                 frac_out += "\x1b[K\x1b[G\n\x1b[K";
             }
-            if length > width * height.saturating_sub(1) || num > height.saturating_sub(2) {
-                if long_output {
-                    print!(
-                        "\x1b[G\n\x1b[J{}\x1b[G{}{}",
-                        if frac == 1 && options.frac.mat {
-                            num *= 2;
-                            if options.multi {
-                                num += 1;
-                            }
-                            format!("{frac_out}\x1b[G\n")
-                        } else {
-                            String::new()
-                        },
-                        output,
-                        if options.color == Auto::True {
-                            "\x1b[0m"
-                        } else {
-                            ""
-                        }
-                    );
-                    frac += num;
-                } else {
-                    print!(
-                        "\x1b[G\x1b[J\ntoo long, will print on enter\x1b[G\x1b[A{}{}{}",
-                        prompt(options, &colors),
-                        to_output(
-                            &unmodified_input[start..end],
-                            vars,
-                            options.color == Auto::True,
-                            &colors
-                        ),
-                        if options.color == Auto::True {
-                            "\x1b[0m"
-                        } else {
-                            ""
-                        },
-                    );
-                    long = true;
-                    frac = 0;
-                }
-            } else {
-                if !options.frac.mat {
-                    frac = 0;
-                }
-                print!(
-                    "\x1b[G{}{}\x1b[K{}\x1b[G\n{}\x1b[J{}\x1b[G\x1b[A\x1b[{}C{}",
-                    prompt(options, &colors),
-                    to_output(
-                        &unmodified_input[start..end],
-                        vars,
-                        options.color == Auto::True,
-                        &colors
-                    ),
-                    if frac == 1 {
-                        num *= 2;
-                        if options.multi {
-                            num += 1;
-                        }
-                        format!("\x1b[G\n{frac_out}\x1b[K")
-                    } else {
-                        String::new()
-                    },
-                    output,
-                    if num + frac == 0 {
-                        String::new()
-                    } else {
-                        format!("\x1b[{}A", num + frac)
-                    },
-                    if options.prompt { 2 } else { 0 } + (end - start),
-                    if options.color == Auto::True {
-                        "\x1b[0m"
-                    } else {
-                        ""
-                    }
-                );
-                frac += num;
-            }
+            (frac, long) = print_block(
+                &output,
+                (frac == 1).then_some(frac_out.as_str()),
+                num,
+                length > width * height.saturating_sub(1) || num > height.saturating_sub(2),
+                long_output,
+                vars,
+                unmodified_input,
+                options,
+                &colors,
+                start,
+                end,
+            );
+        }
+        Tensor(s, v) => {
+            let output = tensor_output(&s, v, options, &colors);
+            let (width, height) = get_terminal_dimensions();
+            let length = no_col_len(&output, options.color == Auto::True);
+            (frac, long) = print_block(
+                &output,
+                None,
+                length.saturating_sub(1) / width,
+                length > width * height.saturating_sub(1),
+                long_output,
+                vars,
+                unmodified_input,
+                options,
+                &colors,
+                start,
+                end,
+            );
         }
         _ => handle_err(
             "str err",
@@ -1461,10 +1441,11 @@ pub fn print_answer<
             let n = custom_units(*n, options, colors);
             let a = get_output(options, colors, &n);
             print!(
-                "{}{}{}{}",
+                "{}{}{}{}{}",
                 a.0,
                 a.2.unwrap_or_default(),
                 a.1,
+                word_output(options, &n.number).unwrap_or_default(),
                 if options.color == Auto::True {
                     "\x1b[0m"
                 } else {
@@ -1604,9 +1585,163 @@ pub fn print_answer<
                 }
             );
         }
+        Tensor(s, v) => {
+            print!(
+                "{}{}",
+                tensor_output(&s, v, options, colors),
+                if options.color == Auto::True {
+                    "\x1b[0m"
+                } else {
+                    ""
+                }
+            );
+        }
         _ => {}
     }
 }
+// prints a matrix or tensor below the input, or defers it with a notice when it would not
+// fit the terminal, frac_out is the fraction form shown above it, returns the lines used
+// and whether printing was deferred
+#[allow(clippy::too_many_arguments)]
+fn print_block<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    output: &str,
+    frac_out: Option<&str>,
+    num: usize,
+    too_long: bool,
+    long_output: bool,
+    vars: &[Variable<Integer, Float, Complex>],
+    unmodified_input: &[char],
+    options: Options,
+    colors: &Colors<Integer, Float, Complex>,
+    start: usize,
+    end: usize,
+) -> (usize, bool) {
+    let (shown, num) = if frac_out.is_some() {
+        (1, num * 2 + options.multi as usize)
+    } else {
+        (0, num)
+    };
+    let reset = if options.color == Auto::True {
+        "\x1b[0m"
+    } else {
+        ""
+    };
+    if too_long && long_output {
+        print!(
+            "\x1b[G\n\x1b[J{}\x1b[G{}{}",
+            frac_out.map(|f| format!("{f}\x1b[G\n")).unwrap_or_default(),
+            output,
+            reset
+        );
+        (shown + num, false)
+    } else if too_long {
+        print!(
+            "\x1b[G\x1b[J\ntoo long, will print on enter\x1b[G\x1b[A{}{}{}",
+            prompt(options, colors),
+            to_output(
+                &unmodified_input[start..end],
+                vars,
+                options.color == Auto::True,
+                colors
+            ),
+            reset,
+        );
+        (0, true)
+    } else {
+        print!(
+            "\x1b[G{}{}\x1b[K{}\x1b[G\n{}\x1b[J{}\x1b[G\x1b[A\x1b[{}C{}",
+            prompt(options, colors),
+            to_output(
+                &unmodified_input[start..end],
+                vars,
+                options.color == Auto::True,
+                colors
+            ),
+            frac_out
+                .map(|f| format!("\x1b[G\n{f}\x1b[K"))
+                .unwrap_or_default(),
+            output,
+            if num + shown == 0 {
+                String::new()
+            } else {
+                format!("\x1b[{}A", num + shown)
+            },
+            if options.prompt { 2 } else { 0 } + (end - start),
+            reset
+        );
+        (shown + num, false)
+    }
+}
+fn tensor_output<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    shape: &[usize],
+    v: Vec<Number<Integer, Float, Complex>>,
+    options: Options,
+    colors: &Colors<Integer, Float, Complex>,
+) -> String {
+    tensor_string(
+        shape,
+        &v.into_iter()
+            .map(|i| {
+                let i = custom_units(i, options, colors);
+                let out = get_output(options, colors, &i);
+                out.0 + &out.1 + &out.2.unwrap_or_default()
+            })
+            .collect::<Vec<String>>(),
+        options,
+        colors,
+        0,
+    )
+}
+pub fn tensor_string<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    shape: &[usize],
+    items: &[String],
+    options: Options,
+    colors: &Colors<Integer, Float, Complex>,
+    depth: usize,
+) -> String {
+    let (left, right) = if options.color == Auto::True {
+        let bracket = &colors.brackets[depth % colors.brackets.len()];
+        (
+            format!("{bracket}{{{}", colors.text),
+            format!("{bracket}}}{}", colors.text),
+        )
+    } else {
+        ("{".to_string(), "}".to_string())
+    };
+    let inner = if shape.len() <= 1 {
+        items
+            .iter()
+            .map(|i| {
+                if options.color == Auto::True {
+                    i.clone() + &colors.text
+                } else {
+                    i.clone()
+                }
+            })
+            .collect::<Vec<String>>()
+            .join(",")
+    } else {
+        let size = shape[1..].iter().product::<usize>().max(1);
+        items
+            .chunks(size)
+            .map(|i| tensor_string(&shape[1..], i, options, colors, depth + 1))
+            .collect::<Vec<String>>()
+            .join(",")
+    };
+    left + &inner + &right
+}
 pub fn custom_units<
     Integer: crate::types::Integer<Float, Complex>,
     Float: crate::types::Float<Integer, Complex>,
@@ -1698,6 +1833,29 @@ pub fn custom_units<
     }
     number
 }
+// every digit of an exact integer in the output base, none past intdigits
+pub fn exact_digits<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    p: &Integer,
+    options: Options,
+) -> Option<String> {
+    if (p.significant_bits().saturating_sub(1) as f64 / (options.base.1 as f64).log2())
+        >= options.int_digits as f64
+    {
+        return None;
+    }
+    let p = p.to_string_radix(options.base.1);
+    if p.trim_start_matches('-').len() > options.int_digits {
+        None
+    } else if options.comma {
+        Some(add_commas(&p))
+    } else {
+        Some(p)
+    }
+}
 pub fn get_output<
     Integer: crate::types::Integer<Float, Complex>,
     Float: crate::types::Float<Integer, Complex>,
@@ -1709,7 +1867,15 @@ pub fn get_output<
 ) -> (String, String, Option<String>) {
     let num = number.number.clone();
     let units = number.units;
-    if options.notation != Normal {
+    // negabinary or balanced ternary replace the output base when both parts have digits
+    let alt = options.alt_base.and_then(|alt| {
+        let balanced = alt == AltBase::BalancedTernary;
+        Some((
+            alt_base_digits(num.real(), balanced, options.decimal_places)?,
+            alt_base_digits(num.imag(), balanced, options.decimal_places)?,
+        ))
+    });
+    if options.notation != Normal && alt.is_none() {
         if options.base.1 != 10 {
             let sign = if num.imag().is_sign_positive() && !num.real().is_zero() {
                 "+"
@@ -1883,12 +2049,36 @@ pub fn get_output<
             )
         }
     } else {
-        let mut re = if options.comma {
-            add_commas(&to_string(num.real(), options, false, options.base.1))
-        } else {
-            to_string(num.real(), options, false, options.base.1)
+        // exact integers print every digit up to intdigits, past that in scientific notation
+        // since the float only holds prec bits of them
+        let exact = number.rational().filter(|(_, q)| q == &1).map(|(p, _)| p);
+        let mut re = match alt
+            .as_ref()
+            .map(|a| a.0.clone())
+            .or_else(|| exact.as_ref().and_then(|p| exact_digits(p, options)))
+        {
+            Some(re) => re,
+            None if exact.is_some_and(|p| p.significant_bits() > num.prec()) => {
+                remove_trailing_zeros::<Integer, Float, Complex>(
+                    &format!("{:e}", num.real()),
+                    options,
+                )
+                .replace("e0", "")
+                .replace('e', &(notate(options, colors) + "e"))
+                    + if options.color == Auto::True {
+                        "\x1b[0m"
+                    } else {
+                        ""
+                    }
+            }
+            None if options.comma => {
+                add_commas(&to_string(num.real(), options, false, options.base.1))
+            }
+            None => to_string(num.real(), options, false, options.base.1),
         };
-        let mut im = if options.comma {
+        let mut im = if let Some((_, im)) = &alt {
+            im.clone()
+        } else if options.comma {
             add_commas(&to_string(num.imag(), options, true, options.base.1))
         } else {
             to_string(num.imag(), options, true, options.base.1)
@@ -1899,7 +2089,7 @@ pub fn get_output<
         if im == "-0" {
             im.remove(0);
         }
-        let sign = if num.imag().is_sign_positive() && re != "0" {
+        let sign = if (num.imag().is_sign_positive() || alt.is_some()) && re != "0" {
             "+"
         } else {
             ""
@@ -1956,6 +2146,30 @@ pub fn get_output<
         )
     }
 }
+// hex, octal and binary of the unsigned word, so negative values show their bit pattern
+fn word_output<
+    Integer: crate::types::Integer<Float, Complex>,
+    Float: crate::types::Float<Integer, Complex>,
+    Complex: crate::types::Complex<Integer, Float>,
+>(
+    options: Options,
+    num: &Complex,
+) -> Option<String> {
+    let (bits, _) = options.word?;
+    if !num.imag().is_zero() || !num.real().is_finite() {
+        return None;
+    }
+    let n = Float::with_val(
+        options.prec,
+        wrap_word(num.real().to_integer()?, bits, false),
+    );
+    Some(format!(
+        "  0x{} 0o{} 0b{}",
+        to_string(&n, options, false, 16),
+        to_string(&n, options, false, 8),
+        to_string(&n, options, false, 2)
+    ))
+}
 fn to_string<
     Integer: crate::types::Integer<Float, Complex>,
     Float: crate::types::Float<Integer, Complex>,
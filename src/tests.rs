@@ -3,12 +3,16 @@ use crate::{
     complex::NumStr::{
         Comma, Division, Exponent, Func, LeftBracket, Minus, Multiplication, Plus, RightBracket,
     },
-    load_vars::get_vars,
+    complex::{combinations, factorize, permutations, prime_factors, singular_values},
+    load_vars::{get_vars, set_commands_or_vars},
     math::do_math,
-    parse::input_var,
-    units::{Number, Options},
+    misc::INTERRUPT,
+    parse::{index_syntax, input_var, interval_syntax, set_syntax},
+    print::get_output,
+    units::{AltBase, Colors, Number, Options, Variable},
 };
 use rug::{Complex, Float, Integer, float::Constant::Pi};
+use std::sync::atomic::Ordering as AtomicOrdering;
 #[test]
 fn test_math() {
     let output = input_var(
@@ -92,3 +96,1032 @@ fn test_math() {
     assert_eq!(&out.number.real().to_string()[..20], "2.009877988310399125");
     assert_eq!(&out.number.imag().to_string()[..20], "4.535664430265577075");
 }
+fn eval(input: &str, options: Options) -> NumStr<Integer, Float, Complex> {
+    eval_with(input, options, &get_vars(options))
+}
+fn eval_with(
+    input: &str,
+    options: Options,
+    vars: &[Variable<Integer, Float, Complex>],
+) -> NumStr<Integer, Float, Complex> {
+    try_eval(input, options, vars).unwrap()
+}
+fn try_eval(
+    input: &str,
+    options: Options,
+    vars: &[Variable<Integer, Float, Complex>],
+) -> Result<NumStr<Integer, Float, Complex>, &'static str> {
+    let (output, funcvars, ..) = input_var(
+        input,
+        vars,
+        &mut Vec::new(),
+        &mut 0,
+        options,
+        false,
+        0,
+        Vec::new(),
+        false,
+        &mut Vec::new(),
+        None,
+        None,
+    )?;
+    do_math(output, options, funcvars)
+}
+fn error(input: &str, options: Options) -> &'static str {
+    try_eval(input, options, &get_vars(options)).unwrap_err()
+}
+fn real(input: &str, options: Options) -> f64 {
+    eval(input, options).num().unwrap().number.real().to_f64()
+}
+fn list(input: &str, options: Options) -> Vec<f64> {
+    eval(input, options)
+        .tensor()
+        .unwrap()
+        .1
+        .iter()
+        .map(|n| n.number.real().to_f64())
+        .collect()
+}
+#[test]
+fn test_bitwise() {
+    let options = Options::default();
+    assert_eq!(real("1<<3==8", options), 1.0);
+    assert_eq!(real("1<<2<5", options), 1.0);
+    assert_eq!(real("12&10", options), 8.0);
+    assert_eq!(real("12 bitor 3 bitxor 1", options), 14.0);
+    assert_eq!(real("bitnot(5)", options), -6.0);
+    let options = Options {
+        word: Some((8, false)),
+        ..Options::default()
+    };
+    assert_eq!(real("255+1", options), 0.0);
+    assert_eq!(real("200>>1", options), 100.0);
+    assert_eq!(real("1<<9", options), 0.0);
+    // each operation wraps, so brackets do not change the result
+    assert_eq!(real("200*2/2", options), 72.0);
+    assert_eq!(real("(200*2)/2", options), 72.0);
+    assert_eq!(real("-1", options), 255.0);
+    let options = Options {
+        word: Some((8, true)),
+        ..Options::default()
+    };
+    assert_eq!(real("127+1", options), -128.0);
+}
+#[test]
+fn test_factorize() {
+    let int = |n: usize| Integer::from(n);
+    assert_eq!(
+        prime_factors::<Float, Integer, Complex>(int(360)).unwrap(),
+        vec![(int(2), 3), (int(3), 2), (int(5), 1)]
+    );
+    assert_eq!(
+        factorize::<Integer, Float, Complex>(int(65537) * int(65537) * int(3), false).unwrap(),
+        vec![(int(3), 1), (int(65537), 2)]
+    );
+    assert_eq!(
+        factorize::<Integer, Float, Complex>(int(1000000007) * int(998244353), false).unwrap(),
+        vec![(int(998244353), 1), (int(1000000007), 1)]
+    );
+    assert_eq!(
+        factorize::<Integer, Float, Complex>(int(1), false).unwrap(),
+        Vec::new()
+    );
+    assert_eq!(real("totient(36)", Options::default()), 12.0);
+    assert_eq!(real("sigma(12)", Options::default()), 28.0);
+    INTERRUPT.store(true, AtomicOrdering::Relaxed);
+    assert_eq!(
+        factorize::<Integer, Float, Complex>(int(1000000007) * int(998244353), false).unwrap(),
+        vec![(int(998244353), 1), (int(1000000007), 1)]
+    );
+    INTERRUPT.store(true, AtomicOrdering::Relaxed);
+    assert_eq!(real("primepi(10^10)", Options::default()), 455052511.0);
+    INTERRUPT.store(true, AtomicOrdering::Relaxed);
+    assert_eq!(real("discrete_log(2,8,11)", Options::default()), 3.0);
+}
+#[test]
+fn test_arithmetic_functions() {
+    let options = Options::default();
+    assert_eq!(
+        list("{mobius(1),mobius(12),mobius(30),mobius(35)}", options),
+        [1.0, 0.0, -1.0, 1.0]
+    );
+    assert_eq!(
+        list("{carmichael(1),carmichael(8),carmichael(561)}", options),
+        [1.0, 2.0, 80.0]
+    );
+    assert_eq!(real("numdivisors(360)", options), 24.0);
+    // the sieve, the phi table period and lehmer's formula past the sieve
+    assert_eq!(real("primepi(1)", options), 0.0);
+    assert_eq!(real("primepi(30030)", options), 3248.0);
+    assert_eq!(real("primepi(123456789)", options), 7027260.0);
+    assert_eq!(real("primepi(10^10)", options), 455052511.0);
+    assert_eq!(
+        error("primepi(10^12+1)", options),
+        "primepi input too large"
+    );
+    assert_eq!(
+        list("primes(90,110)", options),
+        [97.0, 101.0, 103.0, 107.0, 109.0]
+    );
+    assert_eq!(list("primes(10)", options), [2.0, 3.0, 5.0, 7.0]);
+    assert_eq!(
+        list("primes(10^12,10^12+100)", options),
+        [
+            1000000000039.0,
+            1000000000061.0,
+            1000000000063.0,
+            1000000000091.0
+        ]
+    );
+    assert!(list("primes(5,2)", options).is_empty());
+}
+#[test]
+fn test_exact_rationals() {
+    let options = Options::default();
+    let frac = |p: i32, q: i32| Some((Integer::from(p), Integer::from(q)));
+    assert_eq!(
+        eval("1/3+1/6", options).num().unwrap().rational(),
+        frac(1, 2)
+    );
+    assert_eq!(
+        eval("0.1+0.2", options).num().unwrap().rational(),
+        frac(3, 10)
+    );
+    assert_eq!(real("1/3+1/6==1/2", options), 1.0);
+    assert_eq!(
+        eval("(2/3)^3", options).num().unwrap().rational(),
+        frac(8, 27)
+    );
+    let NumStr::Matrix(inv) = eval("inverse({{1,2},{3,4}})", options) else {
+        panic!("inverse is not a matrix")
+    };
+    let inv = inv
+        .iter()
+        .map(|r| r.iter().map(|n| n.rational()).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        inv,
+        vec![vec![frac(-2, 1), frac(1, 1)], vec![frac(3, 2), frac(-1, 2)]]
+    );
+}
+#[test]
+fn test_exact_integers() {
+    let mut options = Options::default();
+    let colors = Colors::default();
+    let output = |input: &str, options: Options| {
+        let n = eval(input, options).num().unwrap();
+        get_output(options, &colors, &n).0
+    };
+    let fact = output("fact(1000)", options);
+    assert_eq!(fact.len(), 2568);
+    assert!(fact.starts_with("402387260077") && fact.ends_with(&"0".repeat(249)));
+    let pow = output("2^10000", options);
+    assert_eq!(pow.len(), 3011);
+    assert!(pow.starts_with("199506311688") && pow.ends_with("96709376"));
+    let bi = output("binomial(5000,2500)", options);
+    assert_eq!(bi.len(), 1504);
+    assert!(bi.starts_with("159371868534") && bi.ends_with("83716320"));
+    // past intdigits only the leading digits the float holds are printed
+    options.int_digits = 100;
+    let short = output("fact(1000)", options);
+    assert!(short.len() < 100 && short.starts_with("4.0238726") && short.contains("2567"));
+    options.int_digits = 4096;
+    // past the work bound the recurrences give way to closed forms or an error
+    assert!((real("stirling2(100000,3)/3^99999", options) - 0.5).abs() < 1e-9);
+    assert!((real("log10(partitions(10^8))", options) - 11131.2457).abs() < 0.01);
+    assert!((real("harmonic(10^9)", options) - 21.3004815023).abs() < 1e-9);
+    assert!((real("bernoulli(600)/bernoulli(598)", options) + 9103.7083502640).abs() < 1e-6);
+    assert!((real("catalan(200000)/catalan(199999)", options) - 3.99997).abs() < 1e-5);
+    assert_eq!(real("fib(10^30)", options), f64::INFINITY);
+    assert_eq!(error("bell(100000)", options), "bell input too large");
+    assert_eq!(
+        error("stirling1(100000,50000)", options),
+        "stirling input too large"
+    );
+}
+#[test]
+fn test_sets() {
+    let options = Options::default();
+    assert_eq!(
+        interval_syntax("interval(0,1,\"[)\")+interval(2,3)"),
+        Ok("interval_co(0,1)+interval(2,3)".to_string())
+    );
+    assert_eq!(
+        interval_syntax("interval(interval(0,1,\"()\")[0][1],2,\"(]\")"),
+        Ok("interval_oc(interval_oo(0,1)[0][1],2)".to_string())
+    );
+    assert!(interval_syntax("interval(0,1,\"[x\")").is_err());
+    assert_eq!(
+        set_syntax("{x ∈ 1..20 | x%3==0}"),
+        "filter(x,x%3==0,1,20)".to_string()
+    );
+    assert_eq!(set_syntax("{1,2,3}"), "{1,2,3}".to_string());
+    assert_eq!(list("{x ∈ 1..10 | x%3==0}", options), vec![3.0, 6.0, 9.0]);
+    assert_eq!(list("{x ∈ 1..5 | |x-3|>1}", options), vec![1.0, 5.0]);
+    assert_eq!(
+        list("munion({1,1,2,3},{1,2,2,4})", options),
+        vec![1.0, 1.0, 2.0, 2.0, 3.0, 4.0]
+    );
+    assert_eq!(
+        list("mintersect({1,1,2,3},{1,2,2,4})", options),
+        vec![1.0, 2.0]
+    );
+    assert_eq!(real("card({1,2,2,3})", options), 3.0);
+    assert_eq!(real("is_disjoint({1,2},{3,4})", options), 1.0);
+    assert_eq!(
+        list("interval(0,1,\"[)\")", options),
+        vec![0.0, 1.0, 1.0, 0.0]
+    );
+    assert_eq!(
+        list("union(interval(0,1,\"[)\"),interval(1,2))", options),
+        vec![0.0, 2.0, 1.0, 1.0]
+    );
+    assert_eq!(
+        list("intersection(interval(0,3),interval(2,5,\"()\"))", options),
+        vec![2.0, 3.0, 0.0, 1.0]
+    );
+    assert_eq!(
+        list("set_difference(interval(0,5),interval(1,2))", options),
+        vec![0.0, 1.0, 1.0, 0.0, 2.0, 5.0, 0.0, 1.0]
+    );
+    assert_eq!(
+        list("complement(interval(0,1,\"[)\"))", options),
+        vec![
+            f64::NEG_INFINITY,
+            0.0,
+            0.0,
+            0.0,
+            1.0,
+            f64::INFINITY,
+            1.0,
+            0.0
+        ]
+    );
+    assert_eq!(real("card(interval(0,1))", options), f64::INFINITY);
+    assert_eq!(real("element(interval(0,1,\"[)\"),1)", options), 0.0);
+    assert_eq!(
+        real("is_disjoint(interval(0,1,\"[)\"),interval(1,2))", options),
+        1.0
+    );
+    assert_eq!(real("union({1,5},interval(2,3))[1][0]", options), 2.0);
+    // only the interval constructors make interval sets, a matrix of the same layout stays a matrix
+    for input in [
+        "union({{0,1,1,0}},{{0,2,1,1}})",
+        "complement({{0,1,1,0}})",
+        "is_disjoint({1},{{0,2,1,1}})",
+    ] {
+        assert!(!error(input, options).is_empty());
+    }
+}
+#[test]
+fn test_index() {
+    let mut options = Options::default();
+    let mut colors = Colors::default();
+    let mut vars = get_vars::<Integer, Float, Complex>(options);
+    let mut set = |vars: &mut Vec<Variable<Integer, Float, Complex>>, input: &str| {
+        set_commands_or_vars(
+            &mut colors,
+            &mut options,
+            vars,
+            &input.chars().collect::<Vec<char>>(),
+        )
+        .unwrap()
+    };
+    set(&mut vars, "v={5,6,7,8}");
+    set(&mut vars, "m={{1,2},{3,4}}");
+    let list = |input: &str, vars: &[Variable<Integer, Float, Complex>]| {
+        eval_with(input, Options::default(), vars)
+            .tensor()
+            .unwrap()
+            .1
+            .iter()
+            .map(|n| n.number.real().to_f64())
+            .collect::<Vec<f64>>()
+    };
+    assert_eq!(list("v[1]", &vars), vec![6.0]);
+    assert_eq!(list("v[-1]", &vars), vec![8.0]);
+    assert_eq!(list("v[1:3]", &vars), vec![6.0, 7.0]);
+    assert_eq!(list("v[::-2]", &vars), vec![8.0, 6.0]);
+    assert_eq!(list("v[v>6]", &vars), vec![7.0, 8.0]);
+    assert_eq!(list("m[1,0]", &vars), vec![3.0]);
+    assert_eq!(list("m[:,1]", &vars), vec![2.0, 4.0]);
+    assert_eq!(list("{5,6,7}[0]", &vars), vec![5.0]);
+    // "[" right after a name or bracket indexes, with an operator or space it is a polar vector
+    assert_eq!(index_syntax("v[1]"), "index(v,0,(1))");
+    assert_eq!(index_syntax("(v)[1]"), "index((v),0,(1))");
+    assert_eq!(index_syntax("2*[1,0]"), "2*[1,0]");
+    assert_eq!(index_syntax("v [1,0]"), "v [1,0]");
+    assert_eq!(list("2*[1,0]", &vars), vec![2.0, 0.0]);
+    assert_eq!(list("2 [1,0]", &vars), vec![2.0, 0.0]);
+    set(&mut vars, "v[0]=1/3");
+    set(&mut vars, "v[1:3]=v[1:3]*2");
+    set(&mut vars, "m[0,1]=9");
+    assert_eq!(list("v", &vars), vec![1.0 / 3.0, 12.0, 14.0, 8.0]);
+    assert_eq!(list("m", &vars), vec![1.0, 9.0, 3.0, 4.0]);
+    let v = vars.iter().find(|v| v.name == ['v']).unwrap();
+    assert!(!v.unparsed.contains("index_set"));
+    assert_eq!(
+        try_eval("v[1>0]", Options::default(), &vars).unwrap_err(),
+        "mask must be a list"
+    );
+    // the stored literal is written in the input base, where e is a digit
+    set(&mut vars, "base=16");
+    set(&mut vars, "w={1,2}");
+    set(&mut vars, "w[0]=1/3");
+    set(&mut vars, "w[1]=sqrt(2)/10^20");
+    let w = list("w", &vars);
+    assert!((w[0] - 1.0 / 3.0).abs() < 1e-15);
+    assert!((w[1] / 2f64.sqrt() * 16f64.powi(32) - 1.0).abs() < 1e-12);
+}
+#[test]
+#[cfg(feature = "fastrand")]
+fn test_bootstrap() {
+    let options = Options::default();
+    assert_eq!(list("bootstrap({2,2,2},mean,50)", options)[..2], [2.0, 2.0]);
+    assert_eq!(
+        list("bootstrap({2,2,2},median,50,0.9)", options)[..2],
+        [2.0, 2.0]
+    );
+    // with a seed the percentile and bias corrected intervals are reproducible, and on skewed
+    // data they bracket the estimate without coinciding
+    let seeded = Options {
+        seed: Some(3),
+        ..options
+    };
+    let input = "bootstrap({1,1,2,2,3,4,7,12,20,41},mean,400)";
+    fastrand::seed(3);
+    let first = list(input, seeded);
+    fastrand::seed(3);
+    assert_eq!(list(input, seeded), first);
+    assert_eq!(first.len(), 4);
+    for ci in first.chunks(2) {
+        assert!(ci[0] < 9.3 && 9.3 < ci[1], "{ci:?}");
+    }
+    assert_ne!(first[..2], first[2..]);
+    // only the statistic argument of bootstrap may be a bare function name
+    for input in ["max(sin,2)", "bootstrap({1,2,3},max(sin,2),5)", "{mean,1}"] {
+        assert!(try_eval(input, options, &get_vars(options)).is_err());
+    }
+}
+#[test]
+fn test_modular() {
+    let options = Options::default();
+    assert_eq!(real("modpow(3,200,7)", options), 2.0);
+    assert_eq!(real("modpow(3,2^60,1000000007)", options), 486117593.0);
+    assert_eq!(real("modinv(3,7)", options), 5.0);
+    assert_eq!(real("crt({2,3,2},{3,5,7})", options), 23.0);
+    assert_eq!(real("discrete_log(2,8,11)", options), 3.0);
+    assert_eq!(real("primitive_root(7)", options), 3.0);
+    assert_eq!(real("jacobi(2,7)", options), 1.0);
+    assert_eq!(real("jacobi(5,21)", options), 1.0);
+    assert_eq!(real("jacobi(2,5)", options), -1.0);
+    for input in [
+        "modpow(3.5,2,7)",
+        "crt({2,3},{3.5,5})",
+        "discrete_log(2,8,11.5)",
+        "primitive_root(7.5)",
+        "jacobi(2,7+i)",
+    ] {
+        assert_eq!(error(input, options), "expected integer");
+    }
+}
+#[test]
+fn test_stack() {
+    let options = Options::default();
+    assert_eq!(list("zeros(2,3)", options), vec![0.0; 6]);
+    assert_eq!(list("ones(3)", options), vec![1.0; 3]);
+    assert_eq!(list("repmat({1,2},2)", options), vec![1.0, 2.0, 1.0, 2.0]);
+    assert_eq!(list("diag({1,2})", options), vec![1.0, 0.0, 0.0, 2.0]);
+    assert_eq!(list("diag({{1,2},{3,4}})", options), vec![1.0, 4.0]);
+    assert_eq!(
+        list("hstack({{1},{2}},{{3},{4}})", options),
+        vec![1.0, 3.0, 2.0, 4.0]
+    );
+    assert_eq!(
+        list("vstack({1,2},{3,4})", options),
+        vec![1.0, 2.0, 3.0, 4.0]
+    );
+    assert_eq!(list("hstack({{1m},{2m}},{{3m},{4m}})", options).len(), 4);
+    for input in [
+        "zeros(-1)",
+        "ones(2.5,2)",
+        "repmat({1,2},2,-2)",
+        "repmat({1,2},1.5)",
+    ] {
+        assert!(!error(input, options).is_empty());
+    }
+    // every entry keeps its own units, a row or column may mix them
+    assert_eq!(
+        list("hstack({{1m},{2}},{{3m},{4s}})", options),
+        vec![1.0, 3.0, 2.0, 4.0]
+    );
+    assert_eq!(list("hstack({1m,2m},{3})", options), vec![1.0, 2.0, 3.0]);
+    assert_eq!(
+        list("vstack({{1m,2s}},{{3m,4}})", options),
+        vec![1.0, 2.0, 3.0, 4.0]
+    );
+    assert_eq!(
+        list("blockdiag({{1m}},{{2s}})", options),
+        vec![1.0, 0.0, 0.0, 2.0]
+    );
+}
+#[test]
+fn test_permutations() {
+    let options = Options::default();
+    assert_eq!(permutations(4, 2).unwrap().len(), 12);
+    assert_eq!(combinations(5, 2).unwrap()[..3], [[0, 1], [0, 2], [0, 3]]);
+    assert_eq!(combinations(64, 3).unwrap().len(), 41664);
+    assert!(permutations(12, 12).is_err());
+    assert!(combinations(64, 32).is_err());
+    assert_eq!(
+        list("combinations({1,2,3,4},3)", options),
+        vec![1.0, 2.0, 3.0, 1.0, 2.0, 4.0, 1.0, 3.0, 4.0, 2.0, 3.0, 4.0]
+    );
+    assert_eq!(list("permutations({1,2,3})", options).len(), 18);
+    assert_eq!(
+        error("permutations({1,2,3,4,5,6,7,8,9,10,11})", options),
+        "too many to enumerate"
+    );
+    assert_eq!(real("perm_rank({3,1,2})", options), 4.0);
+    assert_eq!(list("perm_unrank({1,2,3},4)", options), [3.0, 1.0, 2.0]);
+    for input in ["perm_rank({3,0/0,2})", "perm_unrank({1,0/0},1)"] {
+        assert_eq!(error(input, options), "elements must be comparable");
+    }
+}
+#[test]
+fn test_lp_qp() {
+    let options = Options::default();
+    let close = |input: &str, expected: &[f64]| {
+        let got = list(input, options);
+        assert_eq!(got.len(), expected.len(), "{input}");
+        for (a, b) in got.iter().zip(expected) {
+            assert!((a - b).abs() < 1e-6 || a == b, "{input}: {got:?}");
+        }
+    };
+    // {status, objective, x...}
+    close("lp({-1,-2},{{1,1}},{4})", &[0.0, -8.0, 0.0, 4.0]);
+    close(
+        "lp({1,1},{{-1,-1}},{-2},{{1,-1}},{0})",
+        &[0.0, 2.0, 1.0, 1.0],
+    );
+    close(
+        "lp({-1,-1},{{1,1}},{4},{{0,0}},{0},{0,1})",
+        &[0.0, -2.0, 1.0, 1.0],
+    );
+    // fractions and decimals are solved exactly, anything else falls back to floats
+    assert_eq!(
+        list("lp({-1,-1},{{3,1},{1,3}},{1,1})", options),
+        [0.0, -0.5, 0.25, 0.25]
+    );
+    assert_eq!(
+        list("lp({1},{{-3}},{-0.1})", options)[2],
+        real("0.1/3", options)
+    );
+    close(
+        "lp({-1,-1},{{1,2},{3,1}},{pi,6})",
+        &[
+            0.0,
+            -(6.0 + 2.0 * std::f64::consts::PI) / 5.0,
+            (12.0 - std::f64::consts::PI) / 5.0,
+            (3.0 * std::f64::consts::PI - 6.0) / 5.0,
+        ],
+    );
+    let infeasible = list("lp({1},{{1}},{-1})", options);
+    assert!(infeasible[0] == 1.0 && infeasible[1].is_nan() && infeasible[2].is_nan());
+    assert_eq!(
+        list("lp({-1,-1},{{1,-1}},{1})", options)[..2],
+        [2.0, f64::NEG_INFINITY]
+    );
+    close(
+        "qp({{2,0},{0,2}},{-2,-5},{{1,2}},{3})",
+        &[0.0, -5.45, 0.4, 1.3],
+    );
+    close(
+        "qp({{2,0},{0,2}},{-2,-5},{{0,0}},{0},{{1,1}},{1})",
+        &[0.0, -4.125, -0.25, 1.25],
+    );
+    // positive semidefinite Q
+    close(
+        "qp({{1,0},{0,0}},{-1,-1},{{0,1}},{1})",
+        &[0.0, -1.5, 1.0, 1.0],
+    );
+    let flat = list("qp({{1,1},{1,1}},{-1,-1},{{-1,0},{0,-1}},{0,0})", options);
+    assert!(
+        flat[0] == 0.0 && (flat[1] + 0.5).abs() < 1e-6 && (flat[2] + flat[3] - 1.0).abs() < 1e-6
+    );
+    assert_eq!(
+        list("qp({{1,0},{0,0}},{-1,1})", options)[..2],
+        [2.0, f64::NEG_INFINITY]
+    );
+}
+#[test]
+fn test_continued_fraction() {
+    let options = Options::default();
+    assert_eq!(list("cf(13/7)", options), vec![1.0, 1.0, 6.0]);
+    assert_eq!(list("cf(-0.5)", options), vec![-1.0, 2.0]);
+    assert_eq!(list("cf(pi,5)", options), vec![3.0, 7.0, 15.0, 1.0, 292.0]);
+    // periodic input stays a flat vector, period gives the repeating tail
+    assert_eq!(list("cf(sqrt(2))", options), vec![1.0, 2.0]);
+    assert_eq!(list("period(sqrt(2))", options), vec![2.0]);
+    assert!(list("period(13/7)", options).is_empty());
+    assert!((real("from_cf({1},{2})", options) - 2f64.sqrt()).abs() < 1e-9);
+    assert!((real("from_cf({3},{1,2,1,6})", options) - 14f64.sqrt()).abs() < 1e-9);
+    assert_eq!(real("from_cf({1,2,2})", options), 1.4);
+    assert_eq!(error("from_cf({1.5})", options), "expected integer");
+    assert_eq!(
+        list("convergents({1,2,2,2})", options),
+        vec![1.0, 1.0, 3.0, 2.0, 7.0, 5.0, 17.0, 12.0]
+    );
+    assert_eq!(list("convergents(pi,4)", options)[6..], [355.0, 113.0]);
+    assert_eq!(list("best_rational(pi,1000)", options), vec![355.0, 113.0]);
+}
+#[test]
+fn test_float_bits() {
+    let options = Options::default();
+    assert_eq!(real("float_bits(0.1,32)", options), 1036831949.0);
+    assert_eq!(real("float_bits(1/3,32)", options), 1051372203.0);
+    assert_eq!(real("float_bits(1E-45,32)", options), 1.0);
+    assert_eq!(real("float_bits(1E39,32)", options), 2139095040.0);
+    assert_eq!(real("float_bits(0.1,16)", options), 11878.0);
+    assert_eq!(real("float_bits(65519,16)", options), 31743.0);
+    assert_eq!(real("float_bits(65520,16)", options), 31744.0);
+    assert_eq!(real("float_bits(2^-25,16)", options), 0.0);
+    assert_eq!(real("float_bits(3*2^-26,16)", options), 1.0);
+    assert_eq!(real("float_bits(-2,16)", options), 49152.0);
+    assert_eq!(real("from_bits(15360,16)", options), 1.0);
+    if real("(1+2^-60)-1", options) != 0.0 {
+        // just past a tie for f32 but a tie once rounded to f64
+        assert_eq!(real("float_bits(1+2^-24+2^-60,32)", options), 1065353217.0);
+        assert_eq!(real("float_bits(1+2^-11+2^-60,16)", options), 15361.0);
+    }
+}
+#[test]
+fn test_alt_base() {
+    let mut options = Options::default();
+    let colors = Colors::default();
+    let output = |input: &str, options: Options| {
+        let n = eval(input, options).num().unwrap();
+        let (re, im, _) = get_output(options, &colors, &n);
+        re + &im
+    };
+    options.alt_base = Some(AltBase::Negabinary);
+    assert_eq!(output("6", options), "11010");
+    assert_eq!(output("-3", options), "1101");
+    assert_eq!(output("2.5", options), "111.1");
+    assert_eq!(output("1-2i", options), "1+10i");
+    options.alt_base = Some(AltBase::BalancedTernary);
+    assert_eq!(output("5", options), "1TT");
+    assert_eq!(output("-4", options), "TT");
+    options.alt_base = None;
+    assert_eq!(output("6", options), "6");
+}
+#[test]
+#[cfg(feature = "fastrand")]
+fn test_montecarlo() {
+    let options = Options::default();
+    let first = list("montecarlo(rand_norm(0,1)+rnd,5000,7)", options);
+    assert_eq!(first.len(), 4);
+    for _ in 0..4 {
+        assert_eq!(
+            list("montecarlo(rand_norm(0,1)+rnd,5000,7)", options),
+            first
+        );
+    }
+    assert!((first[0] - 0.5).abs() < 0.1);
+    // neighbouring seeds draw unrelated samples instead of the same stream shifted by one
+    let (a, b) = (
+        list("montecarlo(rnd,5000,7)", options),
+        list("montecarlo(rnd,5000,8)", options),
+    );
+    assert!((a[0] - b[0]).abs() > 1e-6);
+    assert_eq!(list("montecarlo(rnd,5000,7)", options), a);
+    assert_eq!(
+        error("montecarlo(rnd,1)", options),
+        "need at least 2 samples"
+    );
+}
+#[test]
+fn test_matrix_norms() {
+    let options = Options::default();
+    let close = |a: f64, b: f64| (a - b).abs() < 1e-9;
+    let svd = |input: &str| {
+        let NumStr::Matrix(m) = eval(input, options) else {
+            panic!("not a matrix")
+        };
+        singular_values(&m)
+            .unwrap()
+            .iter()
+            .map(|s| s.to_f64())
+            .collect::<Vec<f64>>()
+    };
+    let (s1, s2) = ((15.0 + 221f64.sqrt()).sqrt(), (15.0 - 221f64.sqrt()).sqrt());
+    let s = svd("{{1,2},{3,4}}");
+    assert!(close(s[0], s1) && close(s[1], s2));
+    // rank deficient, the zero diagonal left by the rotations must not turn into 0/0
+    let s = svd("{{1,2},{2,4}}");
+    assert!(close(s[0], 5.0) && close(s[1], 0.0));
+    let s = svd("{{1,0,0},{0,2,0}}");
+    assert!(close(s[0], 2.0) && close(s[1], 1.0));
+    let s = svd("{{i,0},{0,2}}");
+    assert!(close(s[0], 2.0) && close(s[1], 1.0));
+    // norm of a matrix is the frobenius norm, det stays the determinant
+    assert!(close(real("norm({{1,2},{3,4}})", options), 30f64.sqrt()));
+    assert!(close(
+        real("frobenius({{1,2},{3,4}})", options),
+        30f64.sqrt()
+    ));
+    assert_eq!(real("det({{1,2},{3,4}})", options), -2.0);
+    assert_eq!(real("norm({{1,2},{3,4}},1)", options), 6.0);
+    assert_eq!(real("norm({{1,2},{3,4}},inf)", options), 7.0);
+    assert!(close(real("norm({{1,2},{3,4}},2)", options), s1));
+    assert_eq!(error("norm({{1,2},{3,4}},0)", options), "unsupported norm");
+    assert_eq!(real("norm({3,4},1)", options), 7.0);
+    assert_eq!(real("norm({3,4},inf)", options), 4.0);
+    assert_eq!(error("norm({3,4},0)", options), "unsupported norm");
+    assert!(close(real("cond({{1,2},{3,4}})", options), s1 / s2));
+    assert_eq!(real("cond({{1,2},{2,4}})", options), f64::INFINITY);
+    let q = list("gram_schmidt({{3,4},{1,0}})", options);
+    for (q, e) in q.iter().zip([0.6, 0.8, 0.8, -0.6]) {
+        assert!(close(*q, e))
+    }
+    assert_eq!(list("orth({{1,1},{0,1}})", options), [1.0, 0.0, 0.0, 1.0]);
+    for (input, expected) in [
+        ("is_orthogonal({{0,1},{-1,0}})", 1.0),
+        ("is_orthogonal({{1,1},{0,1}})", 0.0),
+        ("is_hermitian({{1,i},{-i,2}})", 1.0),
+        ("is_hermitian({{1,i},{i,2}})", 0.0),
+        ("is_posdef({{2,1},{1,2}})", 1.0),
+        ("is_posdef({{1,2},{2,1}})", 0.0),
+    ] {
+        assert_eq!(real(input, options), expected, "{input}");
+    }
+}
+#[test]
+fn test_statistics_axis() {
+    let options = Options::default();
+    let close = |a: Vec<f64>, b: &[f64]| {
+        a.len() == b.len() && a.iter().zip(b).all(|(a, b)| (a - b).abs() < 1e-9)
+    };
+    // max and min stay per row and the other statistics use every entry unless given an axis
+    assert_eq!(list("max({{1,5},{3,2}})", options), [5.0, 3.0]);
+    assert_eq!(list("max({{1,5},{3,2}},0)", options), [3.0, 5.0]);
+    assert_eq!(list("min({{1,5},{3,2}},1)", options), [1.0, 2.0]);
+    assert_eq!(real("mean({{1,5},{3,2}})", options), 2.75);
+    assert_eq!(list("mean({{1,5},{3,2}},0)", options), [2.0, 3.5]);
+    assert!((real("sd({{1,5},{3,2}})", options) - 1.707825127659933).abs() < 1e-12);
+    assert!(close(
+        list("sd({{1,5},{3,2}},0)", options),
+        &[2f64.sqrt(), 4.5f64.sqrt()]
+    ));
+    for axis in ["-1", "0.5", "2"] {
+        assert_eq!(
+            error(&format!("sd({{{{1,5}},{{3,2}}}},{axis})"), options),
+            "axis must be 0 or 1"
+        );
+    }
+    let r = 0.9933992677987828;
+    assert!(close(
+        list("corr({{1,2},{2,4},{3,7}})", options),
+        &[1.0, r, r, 1.0]
+    ));
+    assert!((real("corr({1,2,3},{2,4,7})", options) - r).abs() < 1e-12);
+    assert_eq!(list("spearman({{1,2},{2,4},{3,7}})", options), [1.0; 4]);
+    assert!(close(
+        list("kendall({{1,3},{2,1},{3,2}})", options),
+        &[1.0, -1.0 / 3.0, -1.0 / 3.0, 1.0]
+    ));
+    assert_eq!(list("zscore({1,2,3})", options), [-1.0, 0.0, 1.0]);
+    assert_eq!(
+        list("zscore({{1,5},{2,5},{3,5}})[:,0]", options),
+        [-1.0, 0.0, 1.0]
+    );
+    assert!(close(
+        list("describe({1,2,3,4})", options),
+        &[4.0, 2.5, 1.2909944487358056, 1.0, 1.5, 2.5, 3.5, 4.0]
+    ));
+    assert_eq!(
+        list("describe({{1,4},{2,3},{3,2},{4,1}})[:,1]", options),
+        list("describe({4,3,2,1})", options)
+    );
+}
+#[test]
+fn test_tensor() {
+    let options = Options::default();
+    let tensor = |input: &str| eval(input, options).tensor().unwrap().0;
+    // equally sized matrices stack into a tensor instead of a broken matrix
+    assert_eq!(tensor("{{{1,2},{3,4}},{{5,6},{7,8}}}"), vec![2, 2, 2]);
+    assert_eq!(tensor("{{{1,2,3}},{{4,5,6}}}*2"), vec![2, 1, 3]);
+    assert_eq!(
+        list("{{{1,2},{3,4}},{{5,6},{7,8}}}+1", options),
+        vec![2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0]
+    );
+    assert_eq!(
+        error("{{{1,2},{3,4}},{{5,6}}}", options),
+        "tensor dimension mismatch"
+    );
+}
+#[test]
+fn test_quantile() {
+    let options = Options::default();
+    let close = |input: &str, expected: f64| {
+        let got = real(input, options);
+        assert!((got - expected).abs() < 1e-9, "{input}: {got}");
+    };
+    assert_eq!(real("binomial_inv(0.5,10,0.5)", options), 5.0);
+    assert_eq!(real("poisson_inv(0.5,3)", options), 3.0);
+    assert_eq!(real("geometric_inv(0.5,0.5)", options), 1.0);
+    assert_eq!(real("neg_binomial_inv(0.1,3,0.5)", options), 0.0);
+    assert_eq!(real("neg_binomial_inv(0.6,3,0.5)", options), 3.0);
+    assert_eq!(real("norm_inv(0)", options), f64::NEG_INFINITY);
+    close("norm_inv(0.975)", 1.959963984540054);
+    close("norm_inv(0.5,1,2)", 1.0);
+    close("lognorm_inv(0.5,0,1)", 1.0);
+    close("gamma_inv(0.5,2,1)", 1.678346990016661);
+    close("gamma_inv(0.5,2,3)", 3.0 * 1.678346990016661);
+    close("gamma_inv(0.5,1,3)", 3.0 * 2f64.ln());
+    close("beta_inv(0.5,2,2)", 0.5);
+    close("norm_cdf(norm_inv(0.3,2,3),2,3)", 0.3);
+}
+#[test]
+fn test_multivariate() {
+    let options = Options::default();
+    let close = |input: &str, expected: f64| {
+        let got = real(input, options);
+        assert!((got - expected).abs() < 1e-9, "{input}: {got}");
+    };
+    close("multinomial(2,1)", 3.0);
+    close("multinomial_pmf({1,1},{0.5,0.5})", 0.5);
+    close("multinomial_pmf({2,1},{0.5,0.5})", 3.0 / 8.0);
+    close("dirichlet_pdf({0.2,0.8},{2,1})", 0.4);
+    // off the simplex the density vanishes even when every coordinate is in [0,1]
+    close("dirichlet_pdf({0.2,0.3},{1,1})", 0.0);
+    close("dirichlet_pdf({0.2,0.3,0.6},{1,1,1})", 0.0);
+    let counts = list("rand_multinomial(1000,{0.2,0.3,0.5})", options);
+    assert_eq!(counts.len(), 3);
+    assert_eq!(counts.iter().sum::<f64>(), 1000.0);
+    assert!(counts.iter().all(|c| *c >= 0.0 && c.fract() == 0.0));
+    assert_eq!(
+        list("rand_multinomial(10,{0,1,0})", options),
+        [0.0, 10.0, 0.0]
+    );
+    assert_eq!(list("rand_multinomial(7,{1})", options), [7.0]);
+}
+#[test]
+fn test_continuous_quantiles() {
+    let options = Options::default();
+    let close = |input: &str, expected: f64| {
+        let got = real(input, options);
+        assert!((got - expected).abs() < 1e-9, "{input}: {got}");
+    };
+    close("exponential_inv(0.5,2)", 2f64.ln() / 2.0);
+    close("uniform_inv(0.25,2,6)", 3.0);
+    close("cauchy_inv(0.75,0,1)", 1.0);
+    close("laplace_inv(0.5,3,1)", 3.0);
+    close("weibull_inv(0.5,1,2)", 2.0 * 2f64.ln());
+    close("t_inv(0.975,10)", 2.228138851986274);
+    close("chi2_inv(0.95,2)", 5.991464547107979);
+    close("f_inv(0.5,2,2)", 1.0);
+    assert_eq!(real("exponential_inv(1,2)", options), f64::INFINITY);
+    assert_eq!(
+        error("uniform_inv(1.2,0,1)", options),
+        "probability out of range"
+    );
+}
+#[test]
+fn test_histogram() {
+    let options = Options::default();
+    let data = "{1,2,2,3,3,3,4,4,5,9}";
+    assert_eq!(
+        list(&format!("quartiles({data})"), options),
+        vec![2.0, 3.0, 4.0]
+    );
+    let counts = |input: &str| {
+        list(input, options)
+            .chunks(3)
+            .map(|r| r[2])
+            .collect::<Vec<f64>>()
+    };
+    // sturges gives ceil(log2(10))+1 bins
+    assert_eq!(
+        counts(&format!("hist({data})")),
+        vec![3.0, 5.0, 1.0, 0.0, 1.0]
+    );
+    // freedman–diaconis bins are 2 iqr/cbrt(n) wide, with an iqr of 4-2
+    assert_eq!(
+        counts(&format!("hist_fd({data})")),
+        vec![3.0, 5.0, 1.0, 0.0, 1.0]
+    );
+    assert_eq!(counts("hist_fd(1..27)"), vec![9.0; 3]);
+    // scott bins are 3.49 sd/cbrt(n) wide
+    assert_eq!(counts(&format!("hist_scott({data})")), vec![6.0, 3.0, 1.0]);
+    // the last bin includes its right edge, values outside the edges are dropped
+    assert_eq!(
+        list("hist({1,2,3,7},{0,2,3})", options),
+        vec![0.0, 2.0, 1.0, 2.0, 3.0, 2.0]
+    );
+    assert_eq!(counts("hist({1,2,3},2)"), vec![1.0, 2.0]);
+    assert_eq!(
+        error("hist({1,2},{2,1})", options),
+        "edges must be increasing"
+    );
+}
+#[test]
+#[cfg(feature = "gnuplot")]
+fn test_bars() {
+    let options = Options::default();
+    let outline = crate::graph::bars(eval("hist({1,2,3},{0,2,4})", options).mat().unwrap())
+        .iter()
+        .flatten()
+        .map(|n| n.number.real().to_f64())
+        .collect::<Vec<f64>>();
+    // each bar goes up its left edge, across at its count and down its right edge
+    assert_eq!(
+        outline,
+        vec![
+            0.0, 0.0, 0.0, 1.0, 2.0, 1.0, 2.0, 0.0, 2.0, 0.0, 2.0, 2.0, 4.0, 2.0, 4.0, 0.0
+        ]
+    );
+}
+#[test]
+fn test_kde_ecdf() {
+    let options = Options::default();
+    let close = |input: &str, expected: f64| {
+        let got = real(input, options);
+        assert!((got - expected).abs() < 1e-9, "{input}: {got}");
+    };
+    let tau = 2.0 * std::f64::consts::PI;
+    close("kde({2,2,2},2,0.5)", 2.0 / tau.sqrt());
+    let at = |x: f64| {
+        [1.0, 2.0, 3.0, 4.0]
+            .iter()
+            .map(|d: &f64| (-(x - d) * (x - d) / 2.0).exp())
+            .sum::<f64>()
+            / (4.0 * tau.sqrt())
+    };
+    let density = list("kde({1,2,3,4},{0,2.5},1)", options);
+    assert!((density[0] - at(0.0)).abs() < 1e-9 && (density[1] - at(2.5)).abs() < 1e-9);
+    // silverman's rule takes the smaller of the sd and iqr/1.34 as the spread
+    close(
+        "kde({1,2,3,4},2.5)-kde({1,2,3,4},2.5,0.9*sqrt(5/3)*4^(-1/5))",
+        0.0,
+    );
+    for input in ["kde({2,2,2},2)", "kde({2},2)"] {
+        assert_eq!(error(input, options), "constant data needs a bandwidth");
+    }
+    assert_eq!(
+        error("kde({1,2},1,0)", options),
+        "bandwidth must be positive"
+    );
+    assert_eq!(real("ecdf({2,2,2},2)", options), 1.0);
+    assert_eq!(
+        list("ecdf({1,2,3,4},{0,2,2.5,4})", options),
+        vec![0.0, 0.5, 0.5, 1.0]
+    );
+}
+#[test]
+fn test_hypothesis_tests() {
+    let options = Options::default();
+    let close = |input: &str, expected: &[f64]| {
+        let got = list(input, options);
+        assert_eq!(got.len(), expected.len(), "{input}");
+        for (g, e) in got.iter().zip(expected) {
+            assert!(g == e || (g - e).abs() < 1e-9, "{input}: {got:?}");
+        }
+    };
+    close(
+        "ttest({5.1,4.9,5.6,5.8,6.0,5.3},5)",
+        &[2.605323299939319, 5.0, 0.04793992466633431],
+    );
+    // welch's test with the satterthwaite degrees of freedom
+    close(
+        "ttest({1,2,3,4,5},{2,4,6,8,10})",
+        &[-1.8973665961010276, 5.882352941176471, 0.10753119493062724],
+    );
+    // a paired test is a one sample test on the differences, here with each one sided tail
+    close(
+        "ttest({1,2,3,4},{1.5,2.1,3.9,4.2},1,1)",
+        &[-2.365068368376859, 3.0, 0.950527701522988],
+    );
+    close(
+        "ttest({1,2,3,4},{1.5,2.1,3.9,4.2},-1,1)",
+        &[-2.365068368376859, 3.0, 0.04947229847701206],
+    );
+    close(
+        "ztest({1,2,3,4,5},2,1)",
+        &[2.23606797749979, f64::INFINITY, 0.025347318677468264],
+    );
+    close(
+        "chi2test({{10,20},{30,40}})",
+        &[0.7936507936507936, 1.0, 0.3729984836134871],
+    );
+    close("chi2test({10,20,30})", &[10.0, 2.0, 0.006737946999085467]);
+    close(
+        "chi2test({10,20,30},{1,1,2})",
+        &[10.0 / 3.0, 2.0, 0.18887560283756182],
+    );
+    close(
+        "kstest({0.1,-0.4,0.7,1.2,-1.5})",
+        &[0.15803634777692697, 5.0, 0.9987086740526474],
+    );
+    close(
+        "kstest({1,2,3},{2.5,4,5,6},1)",
+        &[0.75, 12.0 / 7.0, 0.14535570123384663],
+    );
+    assert_eq!(error("ttest({1},2)", options), "not enough samples");
+    assert_eq!(
+        error("ttest({1,2},{1,2,3},0,1)", options),
+        "paired samples differ in length"
+    );
+    assert_eq!(
+        error("chi2test({10,20},{1,2,3})", options),
+        "dimension mismatch"
+    );
+}
+#[test]
+#[cfg(feature = "fastrand")]
+fn test_seed() {
+    let mut options = Options::default();
+    let mut colors = Colors::default();
+    let mut vars = get_vars::<Integer, Float, Complex>(options);
+    let mut set = |options: &mut Options, input: &str| {
+        set_commands_or_vars(
+            &mut colors,
+            options,
+            &mut vars,
+            &input.chars().collect::<Vec<char>>(),
+        )
+    };
+    let draw = |options: Options| {
+        list(
+            "{rand_norm(0,1),rand_uniform(0,1),rand_int(1,100),rnd}",
+            options,
+        )
+    };
+    set(&mut options, "seed=5").unwrap();
+    let first = draw(options);
+    set(&mut options, "seed=5").unwrap();
+    assert_eq!(draw(options), first);
+    set(&mut options, "seed=6").unwrap();
+    assert_ne!(draw(options), first);
+    eval("seed(5)", options);
+    assert_eq!(draw(options), first);
+    for input in ["seed=-1", "seed=1.5", "seed=2^64"] {
+        assert_eq!(
+            set(&mut options, input),
+            Err("seed must be an integer from 0 to 2^64-1")
+        );
+    }
+    assert_eq!(options.seed, Some(6));
+    assert!(set(&mut options, "seed=2^63").is_ok());
+    for input in ["seed(-3)", "montecarlo(rnd,10,-1)"] {
+        assert_eq!(
+            error(input, options),
+            "seed must be an integer from 0 to 2^64-1"
+        );
+    }
+}
+#[test]
+#[cfg(all(feature = "fastrand", feature = "gnuplot"))]
+fn test_graph_seed() {
+    // each graphed function samples from its own stream of the seed, so replotting repeats it
+    let sample = |seed: u64| {
+        let options = Options {
+            seed: Some(seed),
+            ..Options::default()
+        };
+        let vars = get_vars::<Integer, Float, Complex>(options);
+        let f = input_var(
+            "rand_norm(x,1)",
+            &vars,
+            &mut Vec::new(),
+            &mut 0,
+            options,
+            false,
+            0,
+            Vec::new(),
+            true,
+            &mut Vec::new(),
+            None,
+            None,
+        )
+        .unwrap();
+        let dir = std::env::temp_dir().join(format!("kalc-test-seed-{seed}-{}", fastrand::u64(..)));
+        std::fs::create_dir_all(&dir).unwrap();
+        crate::graph::get_data(
+            Colors::default(),
+            (f.0, f.1, options, f.2),
+            "rand_norm(x,1)".to_string(),
+            0,
+            dir.to_str().unwrap().to_string(),
+        )
+        .join()
+        .unwrap();
+        let data = std::fs::read(dir.join("re0")).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+        data
+    };
+    let first = sample(3);
+    assert!(!first.is_empty());
+    assert_eq!(sample(3), first);
+    assert_ne!(sample(4), first);
+}
@@ -177,6 +177,12 @@ pub trait Integer<F: Float<Self, C>, C: Complex<Self, F>>:
     fn from_str_radix(src: &str, radix: i32) -> Option<Self>;
     fn is_probably_prime(&self, reps: u32) -> IsPrime;
     fn abs(self) -> Self;
+    fn gcd(self, other: &Self) -> Self;
+    fn significant_bits(&self) -> u32;
+    fn max_bits() -> u32;
+    fn bit_and(self, other: &Self) -> Self;
+    fn bit_or(self, other: &Self) -> Self;
+    fn bit_xor(self, other: &Self) -> Self;
     fn new() -> Self;
 }
 #[derive(PartialEq)]
@@ -644,13 +644,24 @@ impl types::Integer<Float<f64>, Complex<f64>> for Integer<i128> {
         (self / rhs, self % rhs)
     }
     fn next_prime(self) -> Self {
-        //TODO
-        self + 1
+        let mut n = Self(self.0.max(1) + 1);
+        while n.is_probably_prime(0) == IsPrime::No {
+            n.0 += 1
+        }
+        n
     }
     #[allow(unused_variables)]
     fn binomial(self, k: u32) -> Self {
-        //TODO
-        self
+        let (n, k) = (self.0, k as i128);
+        if n >= 0 && k > n {
+            return Self(0);
+        }
+        let k = if n >= 0 { k.min(n - k) } else { k };
+        let mut r = 1;
+        for i in 0..k {
+            r = r * (n - i) / (i + 1)
+        }
+        Self(r)
     }
     fn to_u32(&self) -> Option<u32> {
         Some(self.0 as u32)
@@ -681,25 +692,92 @@ impl types::Integer<Float<f64>, Complex<f64>> for Integer<i128> {
         s
     }
     fn from_str_radix(src: &str, radix: i32) -> Option<Self> {
-        let neg = src.starts_with('-');
-        let len = src.len() - if neg { 1 } else { 0 };
-        let mut n = Integer::new();
-        for (i, d) in src.chars().skip(if neg { 1 } else { 0 }).enumerate() {
-            if !d.is_digit(radix as u32) {
-                return None;
-            }
-            n += Integer::from(radix).pow((len - i) as u32) * d.to_string().parse::<i128>().unwrap()
-        }
-        Some(n)
+        i128::from_str_radix(src, radix as u32).ok().map(Self)
     }
+    // miller rabin with the first 13 prime bases, deterministic below 3.3e24
     #[allow(unused_variables)]
     fn is_probably_prime(&self, reps: u32) -> IsPrime {
-        //TODO
-        IsPrime::Yes
+        fn mul_mod(a: u128, b: u128, m: u128) -> u128 {
+            if a.leading_zeros() + b.leading_zeros() >= 128 {
+                return a * b % m;
+            }
+            let (mut a, mut b, mut r) = (a % m, b, 0u128);
+            while b != 0 {
+                if b & 1 == 1 {
+                    r = (r + a) % m
+                }
+                a = (a << 1) % m;
+                b >>= 1
+            }
+            r
+        }
+        const BASES: [u128; 13] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41];
+        if self.0 < 2 {
+            return IsPrime::No;
+        }
+        let n = self.0 as u128;
+        for p in BASES {
+            if n == p {
+                return IsPrime::Yes;
+            }
+            if n % p == 0 {
+                return IsPrime::No;
+            }
+        }
+        let s = (n - 1).trailing_zeros();
+        let d = (n - 1) >> s;
+        'outer: for a in BASES {
+            let (mut x, mut b, mut e) = (1u128, a, d);
+            while e != 0 {
+                if e & 1 == 1 {
+                    x = mul_mod(x, b, n)
+                }
+                b = mul_mod(b, b, n);
+                e >>= 1
+            }
+            if x == 1 || x == n - 1 {
+                continue;
+            }
+            for _ in 1..s {
+                x = mul_mod(x, x, n);
+                if x == n - 1 {
+                    continue 'outer;
+                }
+            }
+            return IsPrime::No;
+        }
+        if n < 3_317_044_064_679_887_385_961_981 {
+            IsPrime::Yes
+        } else {
+            IsPrime::Probably
+        }
     }
     fn abs(self) -> Self {
         Self(self.0.abs())
     }
+    fn gcd(self, other: &Self) -> Self {
+        let (mut a, mut b) = (self.0.abs(), other.0.abs());
+        while b != 0 {
+            (a, b) = (b, a % b)
+        }
+        Self(a)
+    }
+    fn significant_bits(&self) -> u32 {
+        128 - self.0.unsigned_abs().leading_zeros()
+    }
+    // so that the product of two fits
+    fn max_bits() -> u32 {
+        63
+    }
+    fn bit_and(self, other: &Self) -> Self {
+        Self(self.0 & other.0)
+    }
+    fn bit_or(self, other: &Self) -> Self {
+        Self(self.0 | other.0)
+    }
+    fn bit_xor(self, other: &Self) -> Self {
+        Self(self.0 ^ other.0)
+    }
     fn new() -> Self {
         Self::default()
     }
@@ -174,6 +174,24 @@ impl Integer<rug::Float, rug::Complex> for rug::Integer {
     fn abs(self) -> Self {
         self.abs()
     }
+    fn gcd(self, other: &Self) -> Self {
+        self.gcd(other)
+    }
+    fn significant_bits(&self) -> u32 {
+        self.significant_bits()
+    }
+    fn max_bits() -> u32 {
+        1 << 26
+    }
+    fn bit_and(self, other: &Self) -> Self {
+        self & other
+    }
+    fn bit_or(self, other: &Self) -> Self {
+        self | other
+    }
+    fn bit_xor(self, other: &Self) -> Self {
+        self ^ other
+    }
     fn new() -> Self {
         Self::new()
     }
@@ -51,13 +51,23 @@ pub struct Units {
     pub usd: f64,
     pub unit: f64,
 }
-#[derive(Clone, PartialEq, Debug)]
+#[derive(Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Number<I: Integer<F, C>, F: crate::types::Float<I, C>, C: crate::types::Complex<I, F>> {
     pub number: C,
     pub units: Option<Units>,
+    // exact numerator and denominator of number, kept through + - * / and integer powers
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub rational: Option<(I, I)>,
     pub phantom: PhantomData<(I, F)>,
 }
+impl<I: Integer<F, C>, F: crate::types::Float<I, C>, C: crate::types::Complex<I, F>> PartialEq
+    for Number<I, F, C>
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.number == other.number && self.units == other.units
+    }
+}
 #[derive(Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Data<
@@ -189,6 +199,12 @@ pub enum Notation {
 }
 #[derive(Copy, Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum AltBase {
+    Negabinary,
+    BalancedTernary,
+}
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum GraphType {
     Normal,
     Domain,
@@ -211,6 +227,7 @@ pub struct Options {
     pub gnuplot: bool,
     pub real_time_output: bool,
     pub decimal_places: usize,
+    pub int_digits: usize,
     pub color: Auto,
     pub prompt: bool,
     pub comma: bool,
@@ -231,6 +248,9 @@ pub struct Options {
     pub allow_vars: bool,
     pub debug: bool,
     pub slowcheck: u128,
+    pub seed: Option<u64>,
+    pub word: Option<(u32, bool)>,
+    pub alt_base: Option<AltBase>,
     pub interactive: bool,
     pub surface: bool,
     pub scale_graph: bool,
@@ -262,6 +282,7 @@ impl Default for Options {
             },
             real_time_output: true,
             decimal_places: 12,
+            int_digits: 4096,
             color: Auto::Auto,
             prompt: true,
             comma: false,
@@ -282,6 +303,9 @@ impl Default for Options {
             allow_vars: true,
             debug: false,
             slowcheck: 256,
+            seed: None,
+            word: None,
+            alt_base: None,
             interactive: true,
             surface: false,
             scale_graph: false,